@@ -28,6 +28,7 @@ use syn::{
     LitFloat,
     LitInt,
     LitStr,
+    Path,
     Token,
 };
 
@@ -158,7 +159,7 @@ pub struct CommandOption {
     pub name: String,
     pub description: String,
     pub required: bool,
-    pub choices: Option<List<CommandOptionChoice>>,
+    pub choices: Option<Choices>,
     //pub channel_types: Option<Vec<ChannelType>>,
     pub min_value: Option<i32>,
     pub max_value: Option<i32>,
@@ -187,12 +188,7 @@ impl Parse for CommandOption {
                 v.base10_parse()
                     .unwrap()
             }),
-            choices: get_field!(
-                false,
-                "choices",
-                fields,
-                List<CommandOptionChoice>
-            ),
+            choices: get_field!(false, "choices", fields, Choices),
         })
     }
 }
@@ -362,6 +358,33 @@ impl ToTokens for CommandOptionType {
     }
 }
 
+/// The choices of an option, either listed in place or the path of a constant
+/// so options that several commands have can share them.
+#[derive(Debug, Clone)]
+pub enum Choices {
+    List(List<CommandOptionChoice>),
+    Path(Path),
+}
+
+impl Parse for Choices {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        if input.peek(Bracket) {
+            Ok(Self::List(input.parse()?))
+        } else {
+            Ok(Self::Path(input.parse()?))
+        }
+    }
+}
+
+impl ToTokens for Choices {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::List(l) => l.to_tokens(stream),
+            Self::Path(p) => p.to_tokens(stream),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CommandOptionChoice {
     pub name: String,
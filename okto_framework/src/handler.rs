@@ -10,18 +10,22 @@ use serenity::{
 
 use crate::structs::{
     Command,
+    Component,
+    ComponentId,
     DiscordCommandDetails,
 };
 
 #[derive(Clone)]
 pub struct Handler {
     cmds: HashMap<String, &'static Command>,
+    components: HashMap<String, &'static Component>,
 }
 
 impl Handler {
     pub fn new() -> Self {
         Self {
             cmds: HashMap::new(),
+            components: HashMap::new(),
         }
     }
 
@@ -67,6 +71,39 @@ impl Handler {
         Ok(())
     }
 
+    pub fn add_component(&mut self, component: &'static Component) -> std::result::Result<(), String> {
+        if component
+            .prefix
+            .is_empty()
+            || component
+                .prefix
+                .contains(':')
+        {
+            return Err(format!(
+                "Component prefix \"{}\" is empty or contains a ':'",
+                component.prefix
+            ));
+        } else if self
+            .components
+            .contains_key(component.prefix)
+        {
+            return Err(format!(
+                "Component prefix {} has already been registered",
+                component.prefix
+            ));
+        }
+
+        self.components
+            .insert(
+                component
+                    .prefix
+                    .to_owned(),
+                component,
+            );
+
+        Ok(())
+    }
+
     pub async fn handle_interaction(
         &self,
         ctx: &Context,
@@ -85,6 +122,21 @@ impl Handler {
             }
         }
 
+        if let Interaction::Component(component_interaction) = interaction {
+            if let Some(id) = ComponentId::parse(
+                &component_interaction
+                    .data
+                    .custom_id,
+            ) {
+                if let Some(component) = self
+                    .components
+                    .get(&id.prefix)
+                {
+                    return (component.func)(ctx, component_interaction, &id.args).await;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -7,7 +7,10 @@ use serenity::{
     client::Context,
     framework::standard::CommandResult,
     model::{
-        application::CommandInteraction,
+        application::{
+            CommandInteraction,
+            ComponentInteraction,
+        },
         channel::ChannelType,
         Permissions,
     },
@@ -25,6 +28,26 @@ pub type CommandFunc = for<'fut> fn(
     &'fut CommandInteraction,
 ) -> BoxFuture<'fut, CommandResult>;
 
+#[derive(Clone)]
+pub struct Component {
+    pub prefix: &'static str,
+    pub func: ComponentFunc,
+}
+
+pub type ComponentFunc = for<'fut> fn(
+    &'fut Context,
+    &'fut ComponentInteraction,
+    &'fut [String],
+) -> BoxFuture<'fut, CommandResult>;
+
+/// The custom_id of a message component that gets routed to a [`Component`]
+/// by its prefix, with the state it needs encoded as arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentId {
+    pub prefix: String,
+    pub args: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CommandDetails {
     pub name: &'static str,
@@ -108,6 +131,61 @@ impl fmt::Debug for Command {
     }
 }
 
+impl fmt::Debug for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Component")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl ComponentId {
+    pub const MAX_LENGTH: usize = 100;
+    const SEPARATOR: char = ':';
+
+    pub fn new(prefix: impl ToString) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl ToString) -> Self {
+        self.args
+            .push(arg.to_string());
+        self
+    }
+
+    pub fn parse(custom_id: &str) -> Option<Self> {
+        let mut parts = custom_id.split(Self::SEPARATOR);
+        let prefix = parts
+            .next()
+            .filter(|p| !p.is_empty())?;
+
+        // only custom_ids with at least one separator are routed
+        if !custom_id.contains(Self::SEPARATOR) {
+            return None;
+        }
+
+        Some(Self {
+            prefix: prefix.to_owned(),
+            args: parts
+                .map(ToOwned::to_owned)
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for ComponentId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.prefix)?;
+        for arg in &self.args {
+            write!(f, "{}{}", Self::SEPARATOR, arg)?;
+        }
+        Ok(())
+    }
+}
+
 impl From<CommandDetails> for DiscordCommandDetails {
     fn from(c: CommandDetails) -> Self {
         DiscordCommandDetails {
@@ -0,0 +1,166 @@
+use std::fmt::Write;
+
+use chrono::Utc;
+use okto_framework::macros::command;
+use serenity::{
+    all::InteractionResponseFlags,
+    builder::{
+        CreateEmbed,
+        CreateEmbedAuthor,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    framework::standard::CommandResult,
+    model::application::CommandInteraction,
+    prelude::Context,
+};
+
+use crate::{
+    events::{
+        paginator::{
+            PageSource,
+            Paginator,
+        },
+        statefulembed::EmbedSession,
+    },
+    models::{
+        caches::EventsCacheKey,
+        events::EventData,
+    },
+    utils::{
+        constants::*,
+        cutoff_on_last_dot,
+        default_embed,
+    },
+};
+
+#[command]
+/// Get a list of upcoming space events, like spacewalks, dockings and static
+/// fires
+#[options({
+    option_type: String,
+    name: "type",
+    description: "Only show events of this type, like EVA or Docking"
+})]
+async fn events(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let event_type = interaction
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "type")
+        .and_then(|o| {
+            o.value
+                .as_str()
+        });
+
+    let events: Vec<EventData> = ctx
+        .data
+        .read()
+        .await
+        .get::<EventsCacheKey>()
+        .ok_or("Can't get events cache")?
+        .read()
+        .await
+        .iter()
+        .filter(|e| {
+            event_type.is_none_or(|t| {
+                e.event_type
+                    .to_lowercase()
+                    .contains(&t.to_lowercase())
+            })
+        })
+        .cloned()
+        .collect();
+
+    if events.is_empty() {
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .flags(InteractionResponseFlags::EPHEMERAL)
+                        .embed(default_embed(
+                            "There are no upcoming events of this type :(",
+                            false,
+                        )),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let session = EmbedSession::new(ctx, interaction.clone(), false).await?;
+
+    Paginator::new(
+        session,
+        PageSource::List(events),
+        5,
+        |events, _| {
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .author(CreateEmbedAuthor::new("List of upcoming events").icon_url(DEFAULT_ICON))
+                .timestamp(Utc::now())
+                .fields(
+                    events
+                        .iter()
+                        .map(|event| {
+                            (
+                                format!(
+                                    "{} - {}",
+                                    &event.name, &event.event_type
+                                ),
+                                event_details(event),
+                                false,
+                            )
+                        }),
+                )
+        },
+    )
+    .set_footer(&format!("Source: {LAUNCH_LIBRARY_URL}"))
+    .show_page(0)
+    .await;
+
+    Ok(())
+}
+
+fn event_details(event: &EventData) -> String {
+    let mut details = format!(
+        "**Date:** <t:{}>",
+        event
+            .date
+            .timestamp()
+    );
+
+    if let Some(location) = &event.location {
+        write!(details, "\n**Location:** {location}").expect("write to String: can't fail");
+    }
+
+    if !event
+        .description
+        .is_empty()
+    {
+        // keep some room for the links within the limit of a field
+        write!(
+            details,
+            "\n{}",
+            cutoff_on_last_dot(&event.description, 600)
+        )
+        .expect("write to String: can't fail");
+    }
+
+    let links = [
+        ("News", &event.news_url),
+        ("Watch", &event.video_url),
+    ]
+    .into_iter()
+    .filter_map(|(name, url)| {
+        url.as_ref()
+            .map(|url| format!("[{name}]({url})"))
+    })
+    .collect::<Vec<String>>();
+    if !links.is_empty() {
+        write!(details, "\n{}", links.join(" • ")).expect("write to String: can't fail");
+    }
+
+    details
+}
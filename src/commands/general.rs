@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+};
 
 use chrono::Utc;
 use itertools::Itertools;
@@ -26,8 +29,16 @@ use serenity::{
 };
 
 use crate::{
-    models::caches::PictureCacheKey,
-    utils::constants::*,
+    models::{
+        caches::PictureCacheKey,
+        crew::Astronaut,
+    },
+    utils::{
+        constants::*,
+        crew::CREW_SOURCE,
+        cutoff_on_last_dot,
+        default_embed,
+    },
 };
 
 #[command]
@@ -147,61 +158,318 @@ async fn websites(ctx: &Context, interaction: &CommandInteraction) -> CommandRes
     Ok(())
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct PersonInSpace {
-    pub name: String,
-    pub craft: String,
+/// One line about a person in space, with how long their flight has been
+/// going on.
+fn astronaut_line(person: &Astronaut) -> String {
+    let mut line = format!("**{}**", person.name);
+
+    let origin = [
+        person
+            .agency
+            .as_deref(),
+        Some(
+            person
+                .nationality
+                .as_str(),
+        )
+        .filter(|n| !n.is_empty()),
+    ]
+    .into_iter()
+    .flatten()
+    .join(", ");
+    if !origin.is_empty() {
+        write!(line, " ({origin})").expect("write to String: can't fail");
+    }
+
+    if let Some(role) = &person.role {
+        write!(line, " - {role}").expect("write to String: can't fail");
+    }
+
+    if let Some(launched) = person.launched {
+        let days = (Utc::now().naive_utc() - launched).num_days();
+        write!(line, ", {days} days in orbit").expect("write to String: can't fail");
+    }
+
+    line
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct PeopleInSpaceResp {
-    pub people: Vec<PersonInSpace>,
-    pub number: i32,
+/// Put lines into as many embed fields as needed to stay within the length
+/// limit of a field.
+fn line_fields(name: &str, lines: &[String]) -> Vec<(String, String, bool)> {
+    let mut fields: Vec<(String, String, bool)> = Vec::new();
+    let mut current = String::new();
+
+    for line in lines {
+        if current.len() + line.len() + 1 > 1024 {
+            fields.push((
+                name.to_owned(),
+                std::mem::take(&mut current),
+                false,
+            ));
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    if !current.is_empty() || fields.is_empty() {
+        fields.push((name.to_owned(), current, false));
+    }
+
+    fields
+}
+
+/// Replace the "thinking" of a deferred response with an error, so it doesn't
+/// hang when getting the data fails.
+async fn edit_error(ctx: &Context, interaction: &CommandInteraction, text: &str) -> CommandResult {
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(default_embed(text, false)),
+        )
+        .await?;
+    Ok(())
 }
 
 #[command]
 /// Get a list of all humans currently in space
 async fn peopleinspace(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
-    let pis: PeopleInSpaceResp = DEFAULT_CLIENT
-        .get("http://api.open-notify.org/astros.json")
-        .send()
-        .await?
-        .error_for_status()?
-        .json()
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new()),
+        )
         .await?;
 
-    let mut text_vec: Vec<String> = Vec::with_capacity(
-        pis.people
-            .len(),
-    );
-    for person in &pis.people {
-        text_vec.push(format!(
-            "{}: {}\n",
-            person.name, person.craft
-        ));
+    let people = match CREW_SOURCE
+        .people_in_space()
+        .await
+    {
+        Ok(people) => people,
+        Err(e) => {
+            edit_error(
+                ctx,
+                interaction,
+                "I couldn't get the people in space right now, please try again later",
+            )
+            .await?;
+            return Err(e.into());
+        },
+    };
+
+    let mut em = CreateEmbed::new()
+        .title(format!(
+            "There are currently {} people in space",
+            people.len()
+        ))
+        .author(CreateEmbedAuthor::new("People in space").icon_url(DEFAULT_ICON))
+        .footer(CreateEmbedFooter::new(format!(
+            "Source: {}",
+            CREW_SOURCE.url()
+        )))
+        .timestamp(Utc::now())
+        .color(DEFAULT_COLOR);
+
+    for ((station, expedition), group) in &people
+        .iter()
+        .group_by(|p| {
+            (
+                p.station
+                    .clone(),
+                p.expedition
+                    .clone(),
+            )
+        })
+    {
+        let name = match (station, expedition) {
+            (Some(station), Some(expedition)) => format!("{station} - {expedition}"),
+            (Some(station), None) => station,
+            _ => "Other spacecraft".to_owned(),
+        };
+        let lines = group
+            .map(astronaut_line)
+            .collect::<Vec<String>>();
+
+        em = em.fields(line_fields(&name, &lines));
     }
 
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(em),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+/// Get the crew, docked vehicles and upcoming visitors of a space station
+#[options({
+    option_type: String,
+    name: "station",
+    description: "The space station to get information about",
+    required: true,
+    choices: [
+        {
+            name: "International Space Station",
+            value: "International Space Station"
+        },
+        {
+            name: "Tiangong",
+            value: "Tiangong"
+        }
+    ]
+})]
+async fn station(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let name = interaction
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "station")
+        .and_then(|o| {
+            o.value
+                .as_str()
+        })
+        .ok_or("No station provided while it was a required argument")?;
+
     interaction
         .create_response(
             &ctx.http,
-            CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new().embed(
+            CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new()),
+        )
+        .await?;
+
+    let station = match CREW_SOURCE
+        .station(name)
+        .await
+    {
+        Ok(station) => station,
+        Err(e) => {
+            edit_error(
+                ctx,
+                interaction,
+                "I couldn't get information about this station right now, please try again later",
+            )
+            .await?;
+            return Err(e.into());
+        },
+    };
+
+    let Some(station) = station else {
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(
                     CreateEmbed::new()
-                        .title(format!(
-                            "There are currently {} people in space",
-                            pis.number
-                        ))
-                        .description(
-                            text_vec
-                                .iter()
-                                .map(std::string::String::as_str)
-                                .collect::<String>(),
-                        )
-                        .author(CreateEmbedAuthor::new("People in space").icon_url(DEFAULT_ICON))
-                        .timestamp(Utc::now())
+                        .description("I couldn't find any information about this station :(")
                         .color(DEFAULT_COLOR),
                 ),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    let mut em = CreateEmbed::new()
+        .title(&station.name)
+        .author(CreateEmbedAuthor::new("Space station").icon_url(DEFAULT_ICON))
+        .footer(CreateEmbedFooter::new(format!(
+            "Source: {}",
+            CREW_SOURCE.url()
+        )))
+        .timestamp(Utc::now())
+        .color(DEFAULT_COLOR)
+        .field(
+            "General information",
+            format!(
+                "**Owners:** {}\n\
+                **Orbit:** {}\n\
+                **Founded:** {}\n\
+                **Current expedition:** {}",
+                station
+                    .owners
+                    .join(", "),
+                station.orbit,
+                station
+                    .founded
+                    .as_deref()
+                    .unwrap_or("unknown"),
+                if station
+                    .expeditions
+                    .is_empty()
+                {
+                    "none".to_owned()
+                } else {
+                    station
+                        .expeditions
+                        .join(", ")
+                },
             ),
+            false,
+        );
+
+    if !station
+        .description
+        .is_empty()
+    {
+        em = em.description(cutoff_on_last_dot(
+            &station.description,
+            500,
+        ));
+    }
+
+    let crew = station
+        .crew
+        .iter()
+        .map(astronaut_line)
+        .collect::<Vec<String>>();
+    if !crew.is_empty() {
+        em = em.fields(line_fields(
+            &format!("Crew ({})", crew.len()),
+            &crew,
+        ));
+    }
+
+    let docked = station
+        .docked
+        .iter()
+        .map(|(vehicle, port)| format!("**{vehicle}** at {port}"))
+        .collect::<Vec<String>>();
+    em = em.field(
+        "Docked vehicles",
+        if docked.is_empty() {
+            "Nothing is docked right now".to_owned()
+        } else {
+            docked.join("\n")
+        },
+        false,
+    );
+
+    let visits = station
+        .visits
+        .iter()
+        .map(|(vehicle, docking)| {
+            docking.map_or_else(
+                || format!("**{vehicle}**, date unknown"),
+                |d| format!("**{vehicle}**, <t:{}:R>", d.timestamp()),
+            )
+        })
+        .collect::<Vec<String>>();
+    if !visits.is_empty() {
+        em = em.field(
+            "Upcoming visits",
+            visits.join("\n"),
+            false,
+        );
+    }
+
+    if let Some(img) = &station.image {
+        em = em.thumbnail(img);
+    }
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(em),
         )
         .await?;
 
@@ -1,25 +1,53 @@
-use std::sync::Arc;
+use std::{
+    fmt::Write,
+    sync::Arc,
+};
 
-use chrono::Utc;
+use chrono::{
+    Duration,
+    Utc,
+};
 use itertools::Itertools;
-use okto_framework::macros::command;
+use mongodb::bson::{
+    doc,
+    Document,
+};
+use okto_framework::{
+    macros::command,
+    structs::{
+        CommandOptionChoice,
+        CommandOptionValue,
+        Component,
+        ComponentId,
+    },
+};
 use serenity::{
-    all::InteractionResponseFlags,
+    all::{
+        ComponentInteractionDataKind,
+        InteractionResponseFlags,
+    },
     builder::{
+        CreateActionRow,
         CreateEmbed,
         CreateEmbedAuthor,
-        CreateEmbedFooter,
         CreateInteractionResponse,
         CreateInteractionResponseMessage,
+        CreateMessage,
+        CreateSelectMenu,
+        CreateSelectMenuKind,
+        CreateSelectMenuOption,
+        EditInteractionResponse,
     },
     framework::standard::CommandResult,
     model::{
         application::{
             ButtonStyle,
             CommandInteraction,
+            ComponentInteraction,
         },
         channel::ReactionType,
         id::EmojiId,
+        Permissions,
         Timestamp,
     },
     prelude::{
@@ -29,28 +57,119 @@ use serenity::{
 };
 
 use crate::{
-    events::statefulembed::{
-        ButtonType,
-        EmbedSession,
-        StatefulEmbed,
+    events::{
+        modal::Validator,
+        paginator::{
+            parse_page,
+            PageSource,
+            Paginator,
+        },
+        statefulembed::{
+            deny_route,
+            session_author,
+            ButtonType,
+            EmbedSession,
+        },
     },
     models::{
-        caches::LaunchesCacheKey,
+        caches::{
+            DatabaseKey,
+            LaunchesCacheKey,
+        },
         launches::{
             LaunchData,
             LaunchStatus,
+            LauncherConfigDetail,
+            PadData,
+            PadInfo,
         },
     },
+    reminders::countdown_embed,
     utils::{
+        catalog::{
+            get_catalog,
+            shared_catalog,
+            update_catalog,
+            Catalog,
+        },
         constants::*,
         cutoff_on_last_dot,
         default_embed,
         format_duration,
         launches::*,
+        stats::{
+            launch_stats,
+            LaunchStats,
+            StatsGrouping,
+        },
         StandardButton,
     },
 };
 
+/// The statuses the launch commands can filter upcoming launches on.
+const STATUS_CHOICES: &[CommandOptionChoice] = &[
+    CommandOptionChoice {
+        name: "Go",
+        value: CommandOptionValue::String("go"),
+    },
+    CommandOptionChoice {
+        name: "TBD",
+        value: CommandOptionValue::String("tbd"),
+    },
+    CommandOptionChoice {
+        name: "Hold",
+        value: CommandOptionValue::String("hold"),
+    },
+];
+
+/// The mission types the launch commands can filter on.
+const MISSION_CHOICES: &[CommandOptionChoice] = &[
+    CommandOptionChoice {
+        name: "Communications",
+        value: CommandOptionValue::String("communications"),
+    },
+    CommandOptionChoice {
+        name: "Earth Science",
+        value: CommandOptionValue::String("earth science"),
+    },
+    CommandOptionChoice {
+        name: "Government/Top Secret",
+        value: CommandOptionValue::String("government"),
+    },
+    CommandOptionChoice {
+        name: "Human Exploration",
+        value: CommandOptionValue::String("human exploration"),
+    },
+    CommandOptionChoice {
+        name: "Resupply",
+        value: CommandOptionValue::String("resupply"),
+    },
+    CommandOptionChoice {
+        name: "Planetary Science",
+        value: CommandOptionValue::String("planetary science"),
+    },
+    CommandOptionChoice {
+        name: "Astrophysics",
+        value: CommandOptionValue::String("astrophysics"),
+    },
+    CommandOptionChoice {
+        name: "Technology",
+        value: CommandOptionValue::String("technology"),
+    },
+    CommandOptionChoice {
+        name: "Test Flight",
+        value: CommandOptionValue::String("test flight"),
+    },
+    CommandOptionChoice {
+        name: "Dedicated Rideshare",
+        value: CommandOptionValue::String("rideshare"),
+    },
+    CommandOptionChoice {
+        name: "Tourism",
+        value: CommandOptionValue::String("tourism"),
+    },
+];
+
 #[command]
 /// Get information about the next launch that has been marked as certain
 #[options(
@@ -65,10 +184,48 @@ use crate::{
         name: "rocket",
         description: "Rocket name to filter the launches on",
         required: false
+    },
+    {
+        option_type: String,
+        name: "status",
+        description: "Only show launches with this status",
+        required: false,
+        choices: STATUS_CHOICES
+    },
+    {
+        option_type: String,
+        name: "from",
+        description: "Only show launches on or after this date, as YYYY-MM-DD",
+        required: false
+    },
+    {
+        option_type: String,
+        name: "to",
+        description: "Only show launches on or before this date, as YYYY-MM-DD",
+        required: false
+    },
+    {
+        option_type: String,
+        name: "mission",
+        description: "Only show launches with this type of mission",
+        required: false,
+        choices: MISSION_CHOICES
+    },
+    {
+        option_type: String,
+        name: "country",
+        description: "Three letter code of the country to launch from, like USA or CHN",
+        required: false
     }
 )]
 async fn nextlaunch(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
-    let mut launches: Vec<LaunchData> = {
+    let catalog = Arc::new(get_catalog(ctx).await?);
+    let query = match read_query(interaction, &catalog) {
+        Ok(query) => query,
+        Err(err) => return respond_error(ctx, interaction, &err).await,
+    };
+
+    let launches: Vec<LaunchData> = {
         if let Some(launch_cache) = ctx
             .data
             .read()
@@ -82,52 +239,50 @@ async fn nextlaunch(ctx: &Context, interaction: &CommandInteraction) -> CommandR
         } else {
             Err("Can't get launch cache")
         }
-    }?
-    .into_iter()
-    .filter(|l| l.status == LaunchStatus::Go)
-    .collect();
+    }?;
 
     if launches.is_empty() {
-        interaction
-            .create_response(
-                &ctx.http,
-                CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new()
-                        .flags(InteractionResponseFlags::EPHEMERAL)
-                        .embed(default_embed(
-                            "I found no upcoming launches that have been marked as certain :(",
-                            false,
-                        )),
-                ),
-            )
-            .await?;
-        return Ok(());
+        return respond_error(
+            ctx,
+            interaction,
+            "I found no upcoming launches :(",
+        )
+        .await;
     }
 
-    launches = match filter_launches(launches, interaction) {
+    let launches = match query.apply(&catalog, launches) {
         Ok(ls) => ls,
         Err(err) => {
-            interaction
-                .create_response(
-                    &ctx.http,
-                    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().flags(InteractionResponseFlags::EPHEMERAL)
-                                .embed(default_embed(
-                                    &if let FilterErrorType::Invalid = err {
-                                        "This is not a valid filter, please take a look at those listed in `/filtersinfo`".to_owned()
-                                    } else {
-                                        format!("This {err} does not have any upcoming launches listed as certain :(")
-                                    },
-                                    false
-                                ))
-                        )
-                    ,
-                )
-                .await?;
-            return Ok(());
+            return respond_error(
+                ctx,
+                interaction,
+                &filter_error_text(&err, "upcoming launches"),
+            )
+            .await;
         },
     };
 
-    let launch = &launches[0];
+    // without a status filter the next launch is the next certain one
+    let Some(launch) = launches
+        .iter()
+        .find(|l| {
+            query
+                .status
+                .is_some()
+                || l.status == LaunchStatus::Go
+        })
+    else {
+        return respond_error(
+            ctx,
+            interaction,
+            &format!(
+                "None of the upcoming launches with {} have been marked as certain yet, \
+                use the `status` option to see the launches that aren't",
+                query.describe()
+            ),
+        )
+        .await;
+    };
 
     let mut window = format_duration(launch.launch_window, true);
     if window.is_empty() {
@@ -175,6 +330,10 @@ async fn nextlaunch(ctx: &Context, interaction: &CommandInteraction) -> CommandR
             false,
         );
 
+    if let Some(conditions) = launch_conditions(launch) {
+        em = em.field("Conditions:", conditions, false);
+    }
+
     if let Some(img) = &launch.rocket_img {
         em = em.thumbnail(img);
     }
@@ -193,230 +352,292 @@ async fn nextlaunch(ctx: &Context, interaction: &CommandInteraction) -> CommandR
     Ok(())
 }
 
-fn list_page(
-    session: Arc<RwLock<EmbedSession>>,
-    list: Vec<LaunchData>,
-    page_num: usize,
-    all: bool,
-) -> futures::future::BoxFuture<'static, ()> {
-    Box::pin(async move {
-        let launches = if all {
-            list.clone()
-        } else {
-            list.iter()
-                .filter(|l| l.status == LaunchStatus::Go)
-                .cloned()
-                .collect()
-        };
+#[command]
+/// Post a countdown to a launch that keeps updating until the launch is over
+#[options({
+    option_type: String,
+    name: "launch",
+    description: "The name, payload, provider, rocket or ID of the launch, by default the next certain launch",
+    required: false,
+})]
+async fn countdown(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    // a countdown stays around for a long time, so in servers only moderators can
+    // start one
+    if interaction
+        .guild_id
+        .is_some()
+        && !interaction
+            .member
+            .as_ref()
+            .and_then(|member| member.permissions)
+            .is_some_and(Permissions::manage_messages)
+    {
+        return respond_error(
+            ctx,
+            interaction,
+            "You need the manage messages permission to start a countdown here",
+        )
+        .await;
+    }
 
-        let min = page_num * 10;
-        let max_page = (launches.len() - 1) / 10;
+    let launches: Vec<LaunchData> = get_launches(ctx).await?;
 
-        let top = if (page_num * 10 + 10) < launches.len() {
-            page_num * 10 + 10
-        } else {
-            launches.len()
+    let query = interaction
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "launch")
+        .and_then(|o| {
+            o.value
+                .as_str()
+        });
+
+    let launch = if let Some(query) = query {
+        match search_launches(launches, query).as_slice() {
+            [] => {
+                return respond_error(
+                    ctx,
+                    interaction,
+                    "No upcoming launch matches that search :(",
+                )
+                .await
+            },
+            [launch] => launch.clone(),
+            found => {
+                return respond_error(
+                    ctx,
+                    interaction,
+                    &format!(
+                        "{} launches match that search, please use the ID of the launch from `/listlaunches`",
+                        found.len()
+                    ),
+                )
+                .await
+            },
+        }
+    } else {
+        let Some(launch) = launches
+            .into_iter()
+            .find(|l| l.status == LaunchStatus::Go)
+        else {
+            return respond_error(
+                ctx,
+                interaction,
+                "I found no upcoming launches that have been marked as certain :(",
+            )
+            .await;
         };
+        launch
+    };
+
+    if launch
+        .status
+        .is_finished()
+    {
+        return respond_error(
+            ctx,
+            interaction,
+            "This launch is already over",
+        )
+        .await;
+    }
+
+    let db = ctx
+        .data
+        .read()
+        .await
+        .get::<DatabaseKey>()
+        .cloned()
+        .ok_or("Can't get the database")?;
+
+    let message = interaction
+        .channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new().embed(countdown_embed(&launch)),
+        )
+        .await?;
+
+    db.collection::<Document>("countdowns")
+        .insert_one(
+            doc! {
+                "channel": interaction.channel_id.get() as i64,
+                "message": message.id.get() as i64,
+                "launch": &launch.ll_id,
+                "net": launch.net.timestamp(),
+                "missed_fetches": 0,
+            },
+            None,
+        )
+        .await?;
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .flags(InteractionResponseFlags::EPHEMERAL)
+                    .embed(default_embed(
+                        "The countdown has started, it keeps updating until the launch is over",
+                        true,
+                    )),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The text to show when a [`LaunchQuery`] couldn't be applied, `what` says
+/// what kind of launches were searched for.
+fn filter_error_text(err: &FilterErrorType, what: &str) -> String {
+    match err {
+        FilterErrorType::Invalid(filter) => format!(
+            "This is not a valid {filter} filter, please take a look at those listed in `/filtersinfo`"
+        ),
+        FilterErrorType::NoMatches(_) => format!("There are no {what} with {err} :("),
+    }
+}
+
+/// Read the filters of a launch command, the provider is typed in freely so it
+/// gets checked against the known agencies.
+fn read_query(
+    interaction: &CommandInteraction,
+    catalog: &Arc<Catalog>,
+) -> Result<LaunchQuery, String> {
+    let query = LaunchQuery::from_interaction(interaction)?;
+    if let Some(lsp) = &query.lsp {
+        Validator::Agency(catalog.clone()).validate(lsp)?;
+    }
+    Ok(query)
+}
+
+/// The route of the buttons of a launch list, there is none when the filters
+/// can't be put in a custom id, the session handles the buttons then.
+fn list_route(all: bool, query: &LaunchQuery) -> Option<ComponentId> {
+    let route = query
+        .route_args()?
+        .into_iter()
+        .fold(
+            ComponentId::new(LAUNCHES_COMPONENT.prefix)
+                .arg("list")
+                .arg(u8::from(all)),
+            ComponentId::arg,
+        );
+
+    // "first" is the longest argument the buttons add to it
+    (route
+        .clone()
+        .arg("first")
+        .to_string()
+        .len()
+        <= ComponentId::MAX_LENGTH)
+        .then_some(route)
+}
+
+fn launch_list(
+    session: Arc<RwLock<EmbedSession>>,
+    list: Vec<LaunchData>,
+    all: bool,
+    query: &LaunchQuery,
+) -> Paginator<LaunchData> {
+    let any_certain = list
+        .iter()
+        .any(|l| l.status == LaunchStatus::Go);
+    let launches: Vec<LaunchData> = if all {
+        list.clone()
+    } else {
+        list.iter()
+            .filter(|l| l.status == LaunchStatus::Go)
+            .cloned()
+            .collect()
+    };
 
-        let mut em = StatefulEmbed::new_with_embed(
-            session.clone(),
+    let paginator = Paginator::new(
+        session.clone(),
+        PageSource::List(launches),
+        10,
+        move |launches, _| {
             CreateEmbed::new().color(DEFAULT_COLOR)
-                    .author(CreateEmbedAuthor::new("List of upcoming launches").icon_url(DEFAULT_ICON))
-                    .timestamp(Utc::now())
-                    .footer(CreateEmbedFooter::new(format!("Source: {LAUNCH_LIBRARY_URL}",))
-                    ).description(if all {"
+                .author(CreateEmbedAuthor::new("List of upcoming launches").icon_url(DEFAULT_ICON))
+                .timestamp(Utc::now())
+                .description(if all {"
             This list shows the upcoming launches (max 100), both certain and uncertain.\n\
             Use the arrow reactions to get to other pages and the green reaction to filter on only the launches that are certain.
             "} else {"
             This list shows upcoming launches that are certain.\n\
             Use the arrow reactions to get to other pages and the red reaction to get all the launches.
-            "}).fields(launches[min..top].iter().map(|launch| (format!(
-                "{}: {} - {}",
-                launch.id,
+            "}).fields(launches.iter().map(|launch| (format!(
+                "{} - {}",
                 &launch.vehicle,
                 launch
                     .status
                     .as_str()
             ),
             format!(
-                "**Payload:** {}\n**NET:** <t:{}>\n**Provider:** {}\n**Location:** {}",
+                "**Payload:** {}\n**NET:** <t:{}>\n**Provider:** {}\n**Location:** {}\n**ID:** `{}`",
                 &launch.payload,
                 launch
                     .net
                     .timestamp(),
                 &launch.lsp,
-                &launch.location
+                &launch.location,
+                &launch.ll_id
             ),
             false,)))
-        );
-
-        if page_num > 0 {
-            let first_page_launches = list.clone();
-            let first_page_session = session.clone();
-            em.add_option(
-                &StandardButton::First.to_button(),
-                move |_| {
-                    let first_page_session = first_page_session.clone();
-                    let first_page_launches = first_page_launches.clone();
-                    Box::pin(async move {
-                        list_page(
-                            first_page_session.clone(),
-                            first_page_launches.clone(),
-                            0,
-                            true,
-                        )
-                        .await
-                    })
-                },
-            );
-        }
-
-        if page_num > 0 {
-            let last_page_launches = list.clone();
-            let last_page_session = session.clone();
-            em.add_option(
-                &StandardButton::Back.to_button(),
-                move |_| {
-                    let last_page_launches = last_page_launches.clone();
-                    let last_page_session = last_page_session.clone();
-                    Box::pin(async move {
-                        list_page(
-                            last_page_session.clone(),
-                            last_page_launches.clone(),
-                            page_num - 1,
-                            true,
-                        )
-                        .await
-                    })
-                },
-            );
-        }
-
-        if all
-            && launches
-                .iter()
-                .any(|l| l.status == LaunchStatus::Go)
-        {
-            let certain_page_launches = list.clone();
-            let certain_page_session = session.clone();
-            em.add_option(
-                &ButtonType {
-                    label: "Only certain launches".to_owned(),
-                    style: ButtonStyle::Primary,
-                    emoji: Some(ReactionType::Custom {
-                        animated: false,
-                        name: Some("certain".to_owned()),
-                        id: EmojiId::from(CERTAIN_EMOJI),
-                    }),
-                },
-                move |_| {
-                    let certain_page_session = certain_page_session.clone();
-                    let certain_page_launches = certain_page_launches.clone();
-                    Box::pin(async move {
-                        list_page(
-                            certain_page_session.clone(),
-                            certain_page_launches.clone(),
-                            0,
-                            false,
-                        )
-                        .await
-                    })
-                },
-            );
-        } else if !all {
-            let uncertain_page_launches = list.clone();
-            let uncertain_page_session = session.clone();
-            em.add_option(
-                &ButtonType {
-                    label: "Include uncertain launches".to_owned(),
-                    style: ButtonStyle::Primary,
-                    emoji: Some(ReactionType::Custom {
-                        animated: false,
-                        name: Some("uncertain".to_owned()),
-                        id: EmojiId::from(UNCERTAIN_EMOJI),
-                    }),
-                },
-                move |_| {
-                    let uncertain_page_session = uncertain_page_session.clone();
-                    let uncertain_page_launches = uncertain_page_launches.clone();
-                    Box::pin(async move {
-                        list_page(
-                            uncertain_page_session.clone(),
-                            uncertain_page_launches.clone(),
-                            0,
-                            true,
-                        )
-                        .await
-                    })
-                },
-            );
-        }
-
-        if page_num < max_page {
-            let next_page_launches = list.clone();
-            let next_page_session = session.clone();
-            em.add_option(
-                &StandardButton::Forward.to_button(),
-                move |_| {
-                    let next_page_launches = next_page_launches.clone();
-                    let next_page_session = next_page_session.clone();
-                    Box::pin(async move {
-                        list_page(
-                            next_page_session.clone(),
-                            next_page_launches.clone(),
-                            page_num + 1,
-                            true,
-                        )
-                        .await
-                    })
-                },
-            );
-        }
+        },
+    )
+    .set_footer(&format!("Source: {LAUNCH_LIBRARY_URL}"));
+    let paginator = match list_route(all, query) {
+        Some(route) => paginator.set_route(route),
+        None => paginator,
+    };
 
-        if page_num < max_page {
-            let final_page_launches = list;
-            let final_page_session = session.clone();
-            em.add_option(
-                &StandardButton::Last.to_button(),
-                move |_| {
-                    let final_page_launches = final_page_launches.clone();
-                    let final_page_session = final_page_session.clone();
-                    Box::pin(async move {
-                        list_page(
-                            final_page_session.clone(),
-                            final_page_launches.clone(),
-                            final_page_launches.len() / 10 - 1,
-                            true,
-                        )
-                        .await
-                    })
-                },
-            );
-        }
+    let toggle = if all && any_certain {
+        Some(ButtonType {
+            label: "Only certain launches".to_owned(),
+            style: ButtonStyle::Primary,
+            emoji: Some(ReactionType::Custom {
+                animated: false,
+                name: Some("certain".to_owned()),
+                id: EmojiId::from(CERTAIN_EMOJI),
+            }),
+        })
+    } else if !all {
+        Some(ButtonType {
+            label: "Include uncertain launches".to_owned(),
+            style: ButtonStyle::Primary,
+            emoji: Some(ReactionType::Custom {
+                animated: false,
+                name: Some("uncertain".to_owned()),
+                id: EmojiId::from(UNCERTAIN_EMOJI),
+            }),
+        })
+    } else {
+        None
+    };
 
-        em.add_option(
-            &StandardButton::Exit.to_button(),
-            move |_| {
-                let session = session.clone();
-                Box::pin(async move {
-                    let lock = session
-                        .read()
-                        .await;
-                    let _ = lock
-                        .interaction
-                        .delete_response(&lock.http)
-                        .await;
-                })
-            },
-        );
+    let paginator = match (toggle, list_route(!all, query)) {
+        (Some(button), Some(route)) => paginator.add_route(button, route.arg("first")),
+        (Some(button), None) => {
+            let query = query.clone();
+            paginator.add_option(button, move |_| {
+                launch_list(
+                    session.clone(),
+                    list.clone(),
+                    !all,
+                    &query,
+                )
+                .show_page(0)
+            })
+        },
+        (None, _) => paginator,
+    };
 
-        let res = em
-            .show()
-            .await;
-        if res.is_err() {
-            dbg!(res.unwrap_err());
-        }
-    })
+    paginator.add_route(
+        StandardButton::Exit.to_button(),
+        ComponentId::new(LAUNCHES_COMPONENT.prefix).arg("exit"),
+    )
 }
 
 #[command]
@@ -425,130 +646,372 @@ fn list_page(
     {
         option_type: String,
         name: "lsp",
-        description: "Launch Service Provider to filter the launches on"
+        description: "Launch Service Provider to filter the launches on",
+        required: false
     },
     {
         option_type: String,
         name: "rocket",
-        description: "Rocket name to filter the launches on"
+        description: "Rocket name to filter the launches on",
+        required: false
+    },
+    {
+        option_type: String,
+        name: "status",
+        description: "Only show launches with this status",
+        required: false,
+        choices: STATUS_CHOICES
+    },
+    {
+        option_type: String,
+        name: "from",
+        description: "Only show launches on or after this date, as YYYY-MM-DD",
+        required: false
+    },
+    {
+        option_type: String,
+        name: "to",
+        description: "Only show launches on or before this date, as YYYY-MM-DD",
+        required: false
+    },
+    {
+        option_type: String,
+        name: "mission",
+        description: "Only show launches with this type of mission",
+        required: false,
+        choices: MISSION_CHOICES
+    },
+    {
+        option_type: String,
+        name: "country",
+        description: "Three letter code of the country to launch from, like USA or CHN",
+        required: false
     }
 )]
 async fn listlaunches(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
-    let mut launches: Vec<LaunchData> = {
-        if let Some(launch_cache) = ctx
-            .data
-            .read()
-            .await
-            .get::<LaunchesCacheKey>()
-        {
-            Ok(launch_cache
-                .read()
-                .await
-                .to_vec())
-        } else {
-            Err("Can't get launch cache")
-        }
-    }?;
+    let mut launches: Vec<LaunchData> = get_launches(ctx).await?;
 
     if launches.is_empty() {
         return Err("No launches found".into());
     }
 
-    launches = match filter_launches(launches, interaction) {
+    let catalog = Arc::new(get_catalog(ctx).await?);
+    let query = match read_query(interaction, &catalog) {
+        Ok(query) => query,
+        Err(err) => return respond_error(ctx, interaction, &err).await,
+    };
+
+    launches = match query.apply(&catalog, launches) {
         Ok(ls) => ls,
         Err(err) => {
-            interaction
-                .create_response(
-                    &ctx.http,
-                    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().flags(InteractionResponseFlags::EPHEMERAL)
-                            .embed(default_embed(
-                                &if let FilterErrorType::Invalid = err {
-                                    "This is not a valid filter, please take a look at those listed in `/filtersinfo`".to_owned()
-                                } else {
-                                    format!("This {err} does not have any upcoming launches :(")
-                                },
-                                false
-                            ))
-                        )
-                    ,
-                )
-                .await?;
-            return Ok(());
+            return respond_error(
+                ctx,
+                interaction,
+                &filter_error_text(&err, "upcoming launches"),
+            )
+            .await;
         },
     };
 
     let session = EmbedSession::new(ctx, interaction.clone(), false).await?;
 
-    list_page(session, launches, 0, true).await;
+    launch_list(session, launches, true, &query)
+        .show_page(0)
+        .await;
 
     Ok(())
 }
 
-#[command]
-/// Get more detailed information about a launch
+pub static LAUNCHES_COMPONENT: Component = Component {
+    prefix: "launches",
+    func: launches_component,
+};
+
+fn launches_component<'fut>(
+    ctx: &'fut Context,
+    interaction: &'fut ComponentInteraction,
+    args: &'fut [String],
+) -> futures::future::BoxFuture<'fut, CommandResult> {
+    Box::pin(async move {
+        if session_author(interaction).is_some_and(|author| {
+            author
+                != interaction
+                    .user
+                    .id
+        }) {
+            deny_route(ctx, interaction).await?;
+            return Ok(());
+        }
+
+        match args {
+            [action, all, query @ .., page] if action == "list" => {
+                let query = LaunchQuery::from_route_args(query);
+
+                let launches = query
+                    .apply(
+                        &get_catalog(ctx).await?,
+                        get_launches(ctx).await?,
+                    )
+                    .unwrap_or_default();
+
+                if page == "jump" {
+                    let session =
+                        EmbedSession::from_component(ctx, interaction.clone(), false).await?;
+                    launch_list(session, launches, all == "1", &query)
+                        .ask_page(interaction.clone())
+                        .await;
+                } else {
+                    let page_num = parse_page(page).ok_or("invalid page in launches component")?;
+                    let session =
+                        EmbedSession::from_component(ctx, interaction.clone(), true).await?;
+                    launch_list(session, launches, all == "1", &query)
+                        .show_page(page_num)
+                        .await;
+                }
+            },
+            [action] if action == "info" => {
+                let ComponentInteractionDataKind::StringSelect {
+                    values,
+                } = &interaction
+                    .data
+                    .kind
+                else {
+                    return Err("launch picker is not a string select".into());
+                };
+                let ll_id = values
+                    .first()
+                    .ok_or("no launch picked in launch picker")?;
+
+                let response = match get_launches(ctx)
+                    .await?
+                    .into_iter()
+                    .find(|l| &l.ll_id == ll_id)
+                {
+                    Some(launch) => {
+                        CreateInteractionResponseMessage::new()
+                            .content("")
+                            .embed(launch_info_embed(&launch))
+                    },
+                    None => {
+                        CreateInteractionResponseMessage::new()
+                            .content("")
+                            .embed(default_embed(
+                                "This launch is no longer in the list of upcoming launches :(",
+                                false,
+                            ))
+                    },
+                };
+
+                interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::UpdateMessage(response.components(Vec::new())),
+                    )
+                    .await?;
+            },
+            [action] if action == "vehicle" => {
+                let ComponentInteractionDataKind::StringSelect {
+                    values,
+                } = &interaction
+                    .data
+                    .kind
+                else {
+                    return Err("vehicle picker is not a string select".into());
+                };
+                let config_id = values
+                    .first()
+                    .and_then(|id| {
+                        id.parse::<i32>()
+                            .ok()
+                    })
+                    .ok_or("no valid rocket picked in vehicle picker")?;
+
+                let session = EmbedSession::from_component(ctx, interaction.clone(), true).await?;
+                show_vehicle(session, config_id).await?;
+            },
+            [action] if action == "pad" => {
+                let ComponentInteractionDataKind::StringSelect {
+                    values,
+                } = &interaction
+                    .data
+                    .kind
+                else {
+                    return Err("pad picker is not a string select".into());
+                };
+                let pad_id = values
+                    .first()
+                    .and_then(|id| {
+                        id.parse::<i32>()
+                            .ok()
+                    })
+                    .ok_or("no valid pad picked in pad picker")?;
+
+                // launch library can take a while to answer
+                interaction
+                    .defer(&ctx.http)
+                    .await?;
+
+                let pad = PadData::from(&fetch_pad(pad_id).await?);
+                let launches = get_launches(ctx).await?;
+
+                interaction
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new()
+                            .content("")
+                            .embed(pad_embed(&pad, &launches))
+                            .components(Vec::new()),
+                    )
+                    .await?;
+            },
+            [action] if action == "exit" => {
+                interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Acknowledge,
+                    )
+                    .await?;
+                interaction
+                    .delete_response(&ctx.http)
+                    .await?;
+            },
+            _ => return Err(format!("unknown launches component: {args:?}").into()),
+        }
+
+        Ok(())
+    })
+}
+
+async fn get_launches(ctx: &Context) -> Result<Vec<LaunchData>, &'static str> {
+    if let Some(launch_cache) = ctx
+        .data
+        .read()
+        .await
+        .get::<LaunchesCacheKey>()
+    {
+        Ok(launch_cache
+            .read()
+            .await
+            .to_vec())
+    } else {
+        Err("Can't get launch cache")
+    }
+}
+
+#[command]
+/// Get more detailed information about a launch
 #[options({
-    option_type: Integer,
+    option_type: String,
     name: "launch",
-    description: "The number of the launch to get more information about",
+    description: "The name, payload, provider, rocket or ID of the launch to get more information about",
     required: true,
 })]
 async fn launchinfo(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
-    let launches: Vec<LaunchData> = {
-        if let Some(launch_cache) = ctx
-            .data
-            .read()
-            .await
-            .get::<LaunchesCacheKey>()
-        {
-            Ok(launch_cache
-                .read()
-                .await
-                .to_vec())
-        } else {
-            Err("Can't get launch cache")
-        }
-    }?;
+    let launches: Vec<LaunchData> = get_launches(ctx).await?;
 
     if launches.is_empty() {
         return Err("No launches found".into());
     }
 
-    let launch_id = interaction
+    let query = interaction
         .data
         .options
         .iter()
         .find(|o| o.name == "launch")
         .and_then(|o| {
             o.value
-                .as_i64()
-                .map(|i| {
-                    let o: i32 = i
-                        .try_into()
-                        .expect("Got a launch id that was too big to be possible");
-                    o
-                })
+                .as_str()
         })
-        .ok_or("No launch id provided while it was a required argument")?;
+        .ok_or("No launch provided while it was a required argument")?;
 
-    let Some(launch) = launches
-        .into_iter()
-        .find(|l| l.id == launch_id)
-    else {
-        interaction
-            .create_response(
-                &ctx.http,
-                CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new()
-                        .flags(InteractionResponseFlags::EPHEMERAL)
-                        .embed(default_embed(
-                            "No launch was found with that ID :(",
-                            false,
-                        )),
-                ),
-            )
-            .await?;
-        return Ok(());
+    let found = search_launches(launches, query);
+
+    let response = match found.as_slice() {
+        [] => {
+            CreateInteractionResponseMessage::new()
+                .flags(InteractionResponseFlags::EPHEMERAL)
+                .embed(default_embed(
+                    "No upcoming launch matches that search :(",
+                    false,
+                ))
+        },
+        [launch] => CreateInteractionResponseMessage::new().embed(launch_info_embed(launch)),
+        _ => {
+            CreateInteractionResponseMessage::new()
+                .content(if found.len() > LAUNCH_PICKER_SIZE {
+                    format!(
+                        "{} launches match that search, showing the first {LAUNCH_PICKER_SIZE}, which one did you mean?",
+                        found.len()
+                    )
+                } else {
+                    format!(
+                        "{} launches match that search, which one did you mean?",
+                        found.len()
+                    )
+                })
+                .components(vec![launch_picker(&found)])
+        },
     };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(response),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The most options discord allows in a select menu.
+const LAUNCH_PICKER_SIZE: usize = 25;
+
+/// A select menu to pick one of several launches that matched a search, which
+/// gets routed to the launches component with the `ll_id` of the launch.
+fn launch_picker(launches: &[LaunchData]) -> CreateActionRow {
+    let options = launches
+        .iter()
+        .take(LAUNCH_PICKER_SIZE)
+        .map(|launch| {
+            // discord allows at most 100 characters for the label and description
+            CreateSelectMenuOption::new(
+                launch
+                    .launch_name
+                    .chars()
+                    .take(100)
+                    .collect::<String>(),
+                launch
+                    .ll_id
+                    .clone(),
+            )
+            .description(format!(
+                "{} • {}",
+                launch
+                    .lsp
+                    .chars()
+                    .take(70)
+                    .collect::<String>(),
+                launch
+                    .net
+                    .format("%Y-%m-%d %H:%M UTC")
+            ))
+        })
+        .collect();
+
+    CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            ComponentId::new(LAUNCHES_COMPONENT.prefix)
+                .arg("info")
+                .to_string(),
+            CreateSelectMenuKind::String {
+                options,
+            },
+        )
+        .placeholder("Pick a launch"),
+    )
+}
+
+fn launch_info_embed(launch: &LaunchData) -> CreateEmbed {
     let mut window = format_duration(launch.launch_window, true);
     if window.is_empty() {
         window.push_str("instantaneous")
@@ -622,6 +1085,22 @@ async fn launchinfo(ctx: &Context, interaction: &CommandInteraction) -> CommandR
 
     em = em.field("Desciption:", description, false);
 
+    if let Some(conditions) = launch_conditions(launch) {
+        em = em.field("Conditions:", conditions, false);
+    }
+
+    if let Some(boosters) = format_boosters(&launch.boosters) {
+        em = em.field("Boosters:", boosters, false);
+    }
+
+    if let Some(pad) = &launch.pad {
+        em = em.field(
+            format!("Pad: {}", pad.name),
+            pad_details(pad),
+            false,
+        );
+    }
+
     if let Some(img) = &launch.rocket_img {
         em = em.thumbnail(img);
     }
@@ -630,79 +1109,1022 @@ async fn launchinfo(ctx: &Context, interaction: &CommandInteraction) -> CommandR
         em = em.field("vids", links, false);
     }
 
-    em = em.field(
+    em.field(
                     "links",
                     format!(
                         "**My Source:** [The Space Devs]({0})\n\
                         **Rocket Watch:** [rocket.watch](https://rocket.watch/#id={1})\n\
                         **Go4Liftoff:** [go4liftoff.com](https://go4liftoff.com/#page=singleLaunch?filters=launchID={1})",
-                        LAUNCH_LIBRARY_URL, launch.id,
+                        LAUNCH_LIBRARY_URL, launch.ll_id,
                     ),
                     false,
-                );
+                )
+}
 
-    interaction
-        .create_response(
-            &ctx.http,
-            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().embed(em)),
+#[command]
+/// Get information about a rocket and the launches it has done
+#[options({
+    option_type: String,
+    name: "rocket",
+    description: "The name of the rocket to get information about",
+    required: true,
+})]
+async fn vehicle(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let query = interaction
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "rocket")
+        .and_then(|o| {
+            o.value
+                .as_str()
+        })
+        .ok_or("No rocket provided while it was a required argument")?;
+
+    // launch library can take a while to answer
+    let session = EmbedSession::new(ctx, interaction.clone(), false).await?;
+
+    let configs = match search_launcher_configs(query).await {
+        Ok(configs) => configs,
+        Err(err) => {
+            dbg!(err);
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().embed(default_embed(
+                        "I couldn't reach launch library, please try again later",
+                        false,
+                    )),
+                )
+                .await?;
+            return Ok(());
+        },
+    };
+
+    let exact = configs
+        .iter()
+        .find(|c| {
+            c.name
+                .eq_ignore_ascii_case(query)
+                || c.full_name
+                    .eq_ignore_ascii_case(query)
+        });
+
+    match (exact, configs.as_slice()) {
+        (Some(config), _) | (None, [config]) => show_vehicle(session, config.id).await?,
+        (None, []) => {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().embed(default_embed(
+                        "No rocket matches that search :(",
+                        false,
+                    )),
+                )
+                .await?;
+        },
+        (None, _) => {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(format!(
+                            "{} rockets match that search, which one did you mean?",
+                            configs.len()
+                        ))
+                        .components(vec![vehicle_picker(&configs)]),
+                )
+                .await?;
+        },
+    }
+
+    Ok(())
+}
+
+/// A select menu to pick one of several rockets that matched a search, which
+/// gets routed to the launches component with the id of the configuration.
+fn vehicle_picker(configs: &[LauncherConfigDetail]) -> CreateActionRow {
+    let options = configs
+        .iter()
+        .take(25)
+        .map(|config| {
+            CreateSelectMenuOption::new(
+                config
+                    .full_name
+                    .chars()
+                    .take(100)
+                    .collect::<String>(),
+                config
+                    .id
+                    .to_string(),
+            )
+            .description(
+                config
+                    .manufacturer
+                    .as_ref()
+                    .map_or("Unknown manufacturer", |m| &m.name)
+                    .chars()
+                    .take(100)
+                    .collect::<String>(),
+            )
+        })
+        .collect();
+
+    CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            ComponentId::new(LAUNCHES_COMPONENT.prefix)
+                .arg("vehicle")
+                .to_string(),
+            CreateSelectMenuKind::String {
+                options,
+            },
         )
+        .placeholder("Pick a rocket"),
+    )
+}
+
+#[derive(Clone)]
+enum VehiclePage {
+    Details(Box<LauncherConfigDetail>),
+    Launches(Vec<LaunchData>),
+}
+
+async fn show_vehicle(session: Arc<RwLock<EmbedSession>>, config_id: i32) -> CommandResult {
+    let config = fetch_launcher_config(config_id).await?;
+    let launches = match fetch_vehicle_launches(config_id).await {
+        Ok(launches) => launches,
+        Err(err) => {
+            dbg!(err);
+            Vec::new()
+        },
+    };
+
+    let mut pages = vec![VehiclePage::Details(Box::new(
+        config.clone(),
+    ))];
+    pages.extend(
+        launches
+            .chunks(8)
+            .map(|chunk| VehiclePage::Launches(chunk.to_vec())),
+    );
+
+    Paginator::new(
+        session,
+        PageSource::List(pages),
+        1,
+        move |page, _| {
+            match page.first() {
+                Some(VehiclePage::Details(config)) => vehicle_embed(config),
+                Some(VehiclePage::Launches(launches)) => {
+                    CreateEmbed::new()
+                        .color(DEFAULT_COLOR)
+                        .author(CreateEmbedAuthor::new("Launch history").icon_url(DEFAULT_ICON))
+                        .title(&config.full_name)
+                        .timestamp(Utc::now())
+                        .fields(
+                            launches
+                                .iter()
+                                .map(|launch| {
+                                    (
+                                        format!(
+                                            "{} - {}",
+                                            &launch.launch_name,
+                                            launch
+                                                .status
+                                                .as_str()
+                                        ),
+                                        vehicle_launch_details(launch),
+                                        false,
+                                    )
+                                }),
+                        )
+                },
+                None => default_embed("This rocket has no information", false),
+            }
+        },
+    )
+    .set_footer(&format!("Source: {LAUNCH_LIBRARY_URL}"))
+    .show_page(0)
+    .await;
+
+    Ok(())
+}
+
+fn vehicle_embed(config: &LauncherConfigDetail) -> CreateEmbed {
+    let unknown = || "unknown".to_owned();
+
+    let mut em = CreateEmbed::new()
+        .color(DEFAULT_COLOR)
+        .author(CreateEmbedAuthor::new("Rocket information").icon_url(DEFAULT_ICON))
+        .title(&config.full_name)
+        .timestamp(Utc::now())
+        .field(
+            "General information",
+            format!(
+                "**Family:** {}\n\
+                **Variant:** {}\n\
+                **Manufacturer:** {}\n\
+                **Maiden flight:** {}",
+                Some(&config.family)
+                    .filter(|f| !f.is_empty())
+                    .cloned()
+                    .unwrap_or_else(unknown),
+                Some(&config.variant)
+                    .filter(|v| !v.is_empty())
+                    .cloned()
+                    .unwrap_or_else(unknown),
+                config
+                    .manufacturer
+                    .as_ref()
+                    .map_or_else(unknown, |m| {
+                        m.name
+                            .clone()
+                    }),
+                config
+                    .maiden_flight
+                    .clone()
+                    .unwrap_or_else(unknown),
+            ),
+            false,
+        )
+        .field(
+            "Specifications",
+            format!(
+                "**Length:** {}\n\
+                **Diameter:** {}\n\
+                **Stages:** {}",
+                config
+                    .length
+                    .map_or_else(unknown, |l| format!("{l} m")),
+                config
+                    .diameter
+                    .map_or_else(unknown, |d| format!("{d} m")),
+                match (config.min_stage, config.max_stage) {
+                    (Some(min), Some(max)) if min != max => format!("{min} to {max}"),
+                    (Some(stages), _) | (None, Some(stages)) => stages.to_string(),
+                    (None, None) => unknown(),
+                },
+            ),
+            false,
+        );
+
+    if let Some(total) = config.total_launch_count {
+        em = em.field(
+            "Launches",
+            format!(
+                "**Total:** {total}\n\
+                **Successful:** {}\n\
+                **Failed:** {}\n\
+                **Upcoming:** {}",
+                config
+                    .successful_launches
+                    .unwrap_or_default(),
+                config
+                    .failed_launches
+                    .unwrap_or_default(),
+                config
+                    .pending_launches
+                    .unwrap_or_default(),
+            ),
+            false,
+        );
+    }
+
+    if !config
+        .description
+        .is_empty()
+    {
+        em = em.description(cutoff_on_last_dot(
+            &config.description,
+            2000,
+        ));
+    }
+
+    let links = [
+        ("Wikipedia", &config.wiki_url),
+        ("Info", &config.info_url),
+    ]
+    .into_iter()
+    .filter_map(|(name, url)| {
+        url.as_ref()
+            .map(|url| format!("[{name}]({url})"))
+    })
+    .join(", ");
+    if !links.is_empty() {
+        em = em.field("Links", links, false);
+    }
+
+    if let Some(img) = &config.image_url {
+        em = em.thumbnail(img);
+    }
+
+    em
+}
+
+fn vehicle_launch_details(launch: &LaunchData) -> String {
+    let mut details = format!(
+        "**NET:** <t:{}>\n**Provider:** {}",
+        launch
+            .net
+            .timestamp(),
+        &launch.lsp,
+    );
+
+    let serials = launch
+        .boosters
+        .iter()
+        .filter_map(|b| {
+            b.serial
+                .as_deref()
+        })
+        .join(", ");
+    if !serials.is_empty() {
+        write!(details, "\n**Boosters:** {serials}").expect("write to String: can't fail");
+    }
+
+    details
+}
+
+#[command]
+/// Get information about a launch pad and the launches planned from it
+#[options({
+    option_type: String,
+    name: "pad",
+    description: "The name of the launch pad or the site it is on",
+    required: true,
+})]
+async fn pad(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let query = interaction
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "pad")
+        .and_then(|o| {
+            o.value
+                .as_str()
+        })
+        .ok_or("No pad provided while it was a required argument")?;
+
+    // launch library can take a while to answer
+    interaction
+        .defer(&ctx.http)
+        .await?;
+
+    let pads = match search_pads(query).await {
+        Ok(pads) => pads,
+        Err(err) => {
+            dbg!(err);
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().embed(default_embed(
+                        "I couldn't reach launch library, please try again later",
+                        false,
+                    )),
+                )
+                .await?;
+            return Ok(());
+        },
+    };
+
+    let exact = pads
+        .iter()
+        .find(|p| {
+            p.name
+                .eq_ignore_ascii_case(query)
+        });
+
+    let response = match (exact, pads.as_slice()) {
+        (Some(pad), _) | (None, [pad]) => {
+            let pad = PadData::from(pad);
+            let launches = get_launches(ctx).await?;
+            EditInteractionResponse::new().embed(pad_embed(&pad, &launches))
+        },
+        (None, []) => {
+            EditInteractionResponse::new().embed(default_embed(
+                "No launch pad matches that search :(",
+                false,
+            ))
+        },
+        (None, _) => {
+            EditInteractionResponse::new()
+                .content(format!(
+                    "{} pads match that search, which one did you mean?",
+                    pads.len()
+                ))
+                .components(vec![pad_picker(&pads)])
+        },
+    };
+
+    interaction
+        .edit_response(&ctx.http, response)
         .await?;
 
     Ok(())
 }
 
+/// A select menu to pick one of several pads that matched a search, which gets
+/// routed to the launches component with the id of the pad.
+fn pad_picker(pads: &[PadInfo]) -> CreateActionRow {
+    let options = pads
+        .iter()
+        .take(25)
+        .map(|pad| {
+            CreateSelectMenuOption::new(
+                pad.name
+                    .chars()
+                    .take(100)
+                    .collect::<String>(),
+                pad.id
+                    .to_string(),
+            )
+            .description(
+                pad.location
+                    .name
+                    .chars()
+                    .take(100)
+                    .collect::<String>(),
+            )
+        })
+        .collect();
+
+    CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            ComponentId::new(LAUNCHES_COMPONENT.prefix)
+                .arg("pad")
+                .to_string(),
+            CreateSelectMenuKind::String {
+                options,
+            },
+        )
+        .placeholder("Pick a pad"),
+    )
+}
+
+fn pad_embed(pad: &PadData, launches: &[LaunchData]) -> CreateEmbed {
+    let mut em = CreateEmbed::new()
+        .color(DEFAULT_COLOR)
+        .author(CreateEmbedAuthor::new("Launch pad information").icon_url(DEFAULT_ICON))
+        .title(&pad.name)
+        .timestamp(Utc::now())
+        .field("Statistics", pad_details(pad), false);
+
+    let upcoming = launches
+        .iter()
+        .filter(|l| {
+            l.pad
+                .as_ref()
+                .is_some_and(|p| p.id == pad.id)
+        })
+        .take(5)
+        .map(|l| {
+            format!(
+                "<t:{}:R> - {}",
+                l.net
+                    .timestamp(),
+                l.launch_name
+            )
+        })
+        .join("\n");
+
+    em = em.field(
+        "Next launches",
+        if upcoming.is_empty() {
+            "No launches are scheduled from this pad".to_owned()
+        } else {
+            upcoming
+        },
+        false,
+    );
+
+    if let Some(map) = &pad.map_image {
+        em = em.image(map);
+    }
+
+    em
+}
+
+/// How many past launches `/pastlaunches` looks for.
+const PAST_LAUNCHES_WANTED: usize = 100;
+/// How many pages of past launches to fetch at most to find them.
+const PAST_LAUNCH_PAGES: usize = 5;
+
 #[command]
-/// Get a list of all things you can filter launches on
-async fn filtersinfo(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
-    let sorted_agencies = LAUNCH_AGENCIES
+/// Get a list of launches that have already happened and how they went
+#[options(
+    {
+        option_type: String,
+        name: "lsp",
+        description: "Launch Service Provider to filter the launches on"
+    },
+    {
+        option_type: String,
+        name: "rocket",
+        description: "Rocket name to filter the launches on"
+    },
+    {
+        option_type: String,
+        name: "from",
+        description: "Only show launches on or after this date, as YYYY-MM-DD"
+    },
+    {
+        option_type: String,
+        name: "to",
+        description: "Only show launches on or before this date, as YYYY-MM-DD"
+    }
+)]
+async fn pastlaunches(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let catalog = Arc::new(get_catalog(ctx).await?);
+    let query = match read_query(interaction, &catalog) {
+        Ok(query) => query,
+        Err(err) => return respond_error(ctx, interaction, &err).await,
+    };
+
+    // filtering nothing only tells if the filters exist
+    if let Err(err @ FilterErrorType::Invalid(_)) = query.apply(&catalog, Vec::new()) {
+        return respond_error(
+            ctx,
+            interaction,
+            &filter_error_text(&err, "past launches"),
+        )
+        .await;
+    }
+
+    // launch library can take a while to answer
+    let session = EmbedSession::new(ctx, interaction.clone(), false).await?;
+
+    // the other filters are only applied afterwards, so rare launches can take a
+    // few pages
+    let fetched = match fetch_past_launches(
+        query
+            .lsp
+            .as_deref()
+            .and_then(|lsp| catalog.agency(lsp)),
+        query.range,
+        PAST_LAUNCH_PAGES,
+        |found| {
+            query
+                .apply(&catalog, found.to_vec())
+                .is_ok_and(|matching| matching.len() >= PAST_LAUNCHES_WANTED)
+        },
+    )
+    .await
+    {
+        Ok(launches) => launches,
+        Err(err) => {
+            dbg!(err);
+            Vec::new()
+        },
+    };
+
+    let db = ctx
+        .data
+        .read()
+        .await
+        .get::<DatabaseKey>()
+        .cloned()
+        .ok_or("Can't get the database")?;
+    let shared = shared_catalog(ctx).await?;
+    update_catalog(&db, &shared, &fetched).await;
+    // the fetched launches can have taught the catalog more about the filters
+    let catalog = shared
+        .read()
+        .await
+        .clone();
+    let stored = stored_past_launches(
+        &db,
+        query.range,
+        PAST_LAUNCHES_WANTED as i64,
+    )
+    .await;
+
+    let launches = query
+        .apply(
+            &catalog,
+            merge_past_launches(fetched, stored)
+                .into_iter()
+                .map(|r| r.launch)
+                .collect(),
+        )
+        .unwrap_or_default();
+
+    if launches.is_empty() {
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(default_embed(
+                    "I found no past launches matching this search :(",
+                    false,
+                )),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    Paginator::new(
+        session,
+        PageSource::List(launches),
+        5,
+        |launches, _| {
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .author(CreateEmbedAuthor::new("List of past launches").icon_url(DEFAULT_ICON))
+                .timestamp(Utc::now())
+                .fields(
+                    launches
+                        .iter()
+                        .map(|launch| {
+                            (
+                                format!(
+                                    "{} - {}",
+                                    &launch.vehicle,
+                                    launch
+                                        .status
+                                        .as_str()
+                                ),
+                                past_launch_details(launch),
+                                false,
+                            )
+                        }),
+                )
+        },
+    )
+    .set_footer(&format!("Source: {LAUNCH_LIBRARY_URL}"))
+    .show_page(0)
+    .await;
+
+    Ok(())
+}
+
+#[command]
+/// Get statistics about past launches per provider, vehicle family or period
+#[options(
+    {
+        option_type: String,
+        name: "group",
+        description: "What to split the statistics up by, the launch provider by default",
+        choices: [
+            {
+                name: "launch provider",
+                value: "provider"
+            },
+            {
+                name: "vehicle family",
+                value: "family"
+            },
+            {
+                name: "year",
+                value: "year"
+            },
+            {
+                name: "month",
+                value: "month"
+            }
+        ]
+    },
+    {
+        option_type: String,
+        name: "lsp",
+        description: "Launch Service Provider to filter the launches on"
+    },
+    {
+        option_type: String,
+        name: "rocket",
+        description: "Rocket name to filter the launches on"
+    },
+    {
+        option_type: String,
+        name: "from",
+        description: "Only count launches on or after this date, as YYYY-MM-DD"
+    },
+    {
+        option_type: String,
+        name: "to",
+        description: "Only count launches on or before this date, as YYYY-MM-DD"
+    }
+)]
+async fn launchstats(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let catalog = Arc::new(get_catalog(ctx).await?);
+    let query = match read_query(interaction, &catalog) {
+        Ok(query) => query,
+        Err(err) => return respond_error(ctx, interaction, &err).await,
+    };
+    let grouping = interaction
+        .data
+        .options
         .iter()
-        .sorted()
-        .map(|(k, v)| (*k, *v))
-        .collect::<Vec<(&str, &str)>>();
-    let half_agency_count = sorted_agencies.len() / 2;
+        .find(|o| o.name == "group")
+        .and_then(|o| {
+            o.value
+                .as_str()
+        })
+        .and_then(StatsGrouping::from_option)
+        .unwrap_or(StatsGrouping::Provider);
+
+    if let Err(err @ FilterErrorType::Invalid(_)) = query.apply(&catalog, Vec::new()) {
+        return respond_error(
+            ctx,
+            interaction,
+            &filter_error_text(&err, "past launches"),
+        )
+        .await;
+    }
+
+    // launch library can take a while to answer, especially for multiple pages
+    let session = EmbedSession::new(ctx, interaction.clone(), false).await?;
+
+    let fetched = match fetch_past_launches(
+        query
+            .lsp
+            .as_deref()
+            .and_then(|lsp| catalog.agency(lsp)),
+        query.range,
+        3,
+        |_| false,
+    )
+    .await
+    {
+        Ok(launches) => launches,
+        Err(err) => {
+            dbg!(err);
+            Vec::new()
+        },
+    };
+
+    let db = ctx
+        .data
+        .read()
+        .await
+        .get::<DatabaseKey>()
+        .cloned()
+        .ok_or("Can't get the database")?;
+    let shared = shared_catalog(ctx).await?;
+    update_catalog(&db, &shared, &fetched).await;
+    // the fetched launches can have taught the catalog more about the filters
+    let catalog = shared
+        .read()
+        .await
+        .clone();
+    let stored = stored_past_launches(&db, query.range, 1000).await;
+
+    let mut records = merge_past_launches(fetched, stored);
+    let matching = query
+        .apply(
+            &catalog,
+            records
+                .iter()
+                .map(|r| {
+                    r.launch
+                        .clone()
+                })
+                .collect(),
+        )
+        .unwrap_or_default();
+    records.retain(|r| {
+        matching
+            .iter()
+            .any(|l| {
+                l.ll_id
+                    == r.launch
+                        .ll_id
+            })
+    });
+
+    let (overall, groups) = launch_stats(&records, grouping);
+
+    if overall.total == 0 {
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(default_embed(
+                    "I found no past launches to make statistics of :(",
+                    false,
+                )),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let mut period = String::new();
+    if let Some(from) = query
+        .range
+        .from
+    {
+        write!(
+            period,
+            " from {}",
+            from.format("%Y-%m-%d")
+        )
+        .expect("write to String: can't fail");
+    }
+    if let Some(to) = query
+        .range
+        .to
+    {
+        write!(
+            period,
+            " up to {}",
+            to.format("%Y-%m-%d")
+        )
+        .expect("write to String: can't fail");
+    }
+
+    Paginator::new(
+        session,
+        PageSource::List(groups),
+        6,
+        move |groups, _| {
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .author(CreateEmbedAuthor::new("Launch statistics").icon_url(DEFAULT_ICON))
+                .timestamp(Utc::now())
+                .description(format!(
+                    "Statistics of {} past launches{period} per {}, made from the most recent launches on launch library and the outcomes I kept track of:\n\n{}",
+                    overall.total,
+                    grouping.as_str(),
+                    stats_summary(&overall)
+                ))
+                .fields(
+                    groups
+                        .iter()
+                        .map(|group| {
+                            (
+                                group
+                                    .name
+                                    .clone(),
+                                stats_summary(group),
+                                true,
+                            )
+                        }),
+                )
+        },
+    )
+    .set_footer(&format!("Source: {LAUNCH_LIBRARY_URL}"))
+    .show_page(0)
+    .await;
+
+    Ok(())
+}
+
+fn stats_summary(stats: &LaunchStats) -> String {
+    let mut summary = format!(
+        "**Launches:** {}\n**Success:** {} ({:.0}%)",
+        stats.total,
+        stats.successes,
+        stats.percentage(stats.successes)
+    );
+
+    if stats.failures > 0 {
+        write!(
+            summary,
+            "\n**Failure:** {} ({:.0}%)",
+            stats.failures,
+            stats.percentage(stats.failures)
+        )
+        .expect("write to String: can't fail");
+    }
+
+    if stats.partial_failures > 0 {
+        write!(
+            summary,
+            "\n**Partial failure:** {} ({:.0}%)",
+            stats.partial_failures,
+            stats.percentage(stats.partial_failures)
+        )
+        .expect("write to String: can't fail");
+    }
+
+    if let Some(slip) = stats.average_slip() {
+        write!(
+            summary,
+            "\n**Average NET slip:** {}",
+            if slip.is_zero() {
+                "none".to_owned()
+            } else if slip < Duration::zero() {
+                format!(
+                    "{} earlier",
+                    format_duration(-slip, false)
+                )
+            } else {
+                format_duration(slip, false)
+            }
+        )
+        .expect("write to String: can't fail");
+    }
+
+    summary
+}
 
+async fn respond_error(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    text: &str,
+) -> CommandResult {
     interaction
         .create_response(
             &ctx.http,
             CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new().embed(
-                    CreateEmbed::new()
-                        .color(DEFAULT_COLOR)
-                        .author(CreateEmbedAuthor::new("Filters Info").icon_url(DEFAULT_ICON))
-                        .timestamp(Utc::now())
-                        .title("The following filters can be used to filter launches:")
-                        .field(
-                            "Vehicles:",
-                            LAUNCH_VEHICLES
-                                .keys()
-                                .sorted()
-                                .join(", "),
-                            false,
-                        )
-                        .field(
-                            "Launch Service Provider abbreviations with their full names (part 1):",
-                            sorted_agencies
-                                .iter()
-                                .take(half_agency_count)
-                                .map(|(k, v)| format!("**{k}**: {v}"))
-                                .collect::<Vec<String>>()
-                                .join("\n"),
-                            false,
-                        )
-                        .field(
-                            "Launch Service Provider abbreviations with their full names (part 2):",
-                            sorted_agencies
-                                .iter()
-                                .skip(half_agency_count)
-                                .map(|(k, v)| format!("**{k}**: {v}"))
-                                .collect::<Vec<String>>()
-                                .join("\n"),
-                            false,
-                        ),
-                ),
+                CreateInteractionResponseMessage::new()
+                    .flags(InteractionResponseFlags::EPHEMERAL)
+                    .embed(default_embed(text, false)),
             ),
         )
         .await?;
+    Ok(())
+}
+
+fn past_launch_details(launch: &LaunchData) -> String {
+    let mut details = format!(
+        "**Payload:** {}\n**NET:** <t:{}>\n**Provider:** {}",
+        &launch.payload,
+        launch
+            .net
+            .timestamp(),
+        &launch.lsp,
+    );
+
+    if let Some(reason) = launch
+        .failreason
+        .as_ref()
+        .filter(|r| !r.is_empty())
+    {
+        // keep some room for the links within the limit of a field
+        write!(
+            details,
+            "\n**Failure reason:** {}",
+            reason
+                .chars()
+                .take(400)
+                .collect::<String>()
+        )
+        .expect("write to String: can't fail");
+    }
+
+    if let Some(links) = replay_links(&launch.vid_urls) {
+        write!(details, "\n**Replay:** {links}").expect("write to String: can't fail");
+    }
+
+    details
+}
+
+#[command]
+/// Get a list of all things you can filter launches on
+async fn filtersinfo(ctx: &Context, interaction: &CommandInteraction) -> CommandResult {
+    let catalog = get_catalog(ctx).await?;
+    let (agencies, vehicles) = (
+        catalog.agency_filters(),
+        catalog.vehicle_filters(),
+    );
+
+    // the vehicles learned from launch library can get past the length limit
+    // of a single field
+    let mut vehicle_fields: Vec<String> = vec![String::new()];
+    for vehicle in vehicles {
+        let field = vehicle_fields
+            .last_mut()
+            .expect("starts with a field");
+        if field.len() + vehicle.len() + 2 > 1024 {
+            vehicle_fields.push(vehicle);
+        } else {
+            if !field.is_empty() {
+                field.push_str(", ");
+            }
+            field.push_str(&vehicle);
+        }
+    }
+
+    let session = EmbedSession::new(ctx, interaction.clone(), false).await?;
+
+    Paginator::new(
+        session,
+        PageSource::List(agencies),
+        12,
+        move |agencies, _| {
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .author(CreateEmbedAuthor::new("Filters Info").icon_url(DEFAULT_ICON))
+                .timestamp(Utc::now())
+                .title("The following filters can be used to filter launches:")
+                .fields(
+                    vehicle_fields
+                        .iter()
+                        .map(|field| ("Vehicles:", field.clone(), false)),
+                )
+                .field(
+                    "Launch Service Provider abbreviations with their full names:",
+                    agencies
+                        .iter()
+                        .map(|(k, v)| format!("**{k}**: {v}"))
+                        .collect::<Vec<String>>()
+                        .join("\n"),
+                    false,
+                )
+        },
+    )
+    .set_open(true)
+    .await
+    .show_page(0)
+    .await;
 
     Ok(())
 }
@@ -1,3 +1,4 @@
+pub mod events;
 pub mod general;
 pub mod help;
 pub mod launches;
@@ -4,12 +4,47 @@ mod settings;
 use std::sync::Arc;
 
 use chrono::Utc;
-use okto_framework::macros::command;
+use okto_framework::{
+    macros::command,
+    structs::Component,
+};
 use pages::{
+    add_allow_filter_menu,
+    add_booster_menu,
+    add_event_filter_menu,
+    add_filter_menu,
+    add_launch_mention_menu,
+    add_mention_menu,
+    add_mention_rule_menu,
+    add_payload_filter_menu,
+    add_reminder_menu,
+    allow_filters_page,
+    boosters_page,
+    disallow_filters_page,
+    edit_template_menu,
+    event_filters_page,
     filters_page,
+    launch_mention_menu,
+    mention_rules_page,
     mentions_page,
+    notification_channel_menu,
     other_page,
+    payload_filters_page,
+    preview_templates_menu,
     reminders_page,
+    remove_allow_filter_menu,
+    remove_booster_menu,
+    remove_event_filter_menu,
+    remove_filter_menu,
+    remove_launch_mention_menu,
+    remove_mention_menu,
+    remove_mention_rule_menu,
+    remove_payload_filter_menu,
+    remove_reminder_menu,
+    route,
+    template_color_menu,
+    templates_page,
+    type_reminder_menu,
 };
 use serenity::{
     all::InteractionResponseFlags,
@@ -20,18 +55,28 @@ use serenity::{
         CreateInteractionResponseMessage,
     },
     framework::standard::CommandResult,
-    model::application::{
-        ButtonStyle,
-        CommandInteraction,
+    model::{
+        application::{
+            ButtonStyle,
+            CommandInteraction,
+            ComponentInteraction,
+        },
+        Permissions,
     },
     prelude::{
         Context,
         RwLock,
     },
 };
+use settings::{
+    toggle_plain_text,
+    toggle_setting,
+};
 
 use crate::{
     events::statefulembed::{
+        deny_route,
+        session_author,
         ButtonType,
         EmbedSession,
         StatefulEmbed,
@@ -164,8 +209,7 @@ fn main_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) -> futures::future::BoxFutu
                 .author(CreateEmbedAuthor::new(name).icon_url(DEFAULT_ICON)),
         );
 
-        let reminder_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Reminders",
             "Set at which times you want to get launch reminders",
             false,
@@ -174,14 +218,10 @@ fn main_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) -> futures::future::BoxFutu
                 style: ButtonStyle::Primary,
                 label: "Reminders".to_owned(),
             },
-            move |_| {
-                let reminder_ses = reminder_ses.clone();
-                Box::pin(async move { reminders_page(reminder_ses.clone(), id).await })
-            },
+            route("reminders", id),
         );
 
-        let filters_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Filters",
             "Set filters for which launches you do and don't want to see",
             false,
@@ -190,15 +230,23 @@ fn main_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) -> futures::future::BoxFutu
                 style: ButtonStyle::Primary,
                 label: "Filters".to_owned(),
             },
-            move |_| {
-                let filters_ses = filters_ses.clone();
-                Box::pin(async move { filters_page(filters_ses.clone(), id).await })
+            route("filters", id),
+        );
+
+        em = em.add_route_field(
+            "Boosters",
+            "Follow specific boosters to get reminders and landing outcomes for their launches",
+            false,
+            &ButtonType {
+                emoji: Some('🚀'.into()),
+                style: ButtonStyle::Primary,
+                label: "Boosters".to_owned(),
             },
+            route("boosters", id),
         );
 
         if id.guild_specific() {
-            let mention_ses = ses.clone();
-            em = em.add_field(
+            em = em.add_route_field(
                 "Mentions",
                 "Set which roles should be mentioned when posting reminders",
                 false,
@@ -207,15 +255,23 @@ fn main_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) -> futures::future::BoxFutu
                     style: ButtonStyle::Primary,
                     label: "Mentions".to_owned(),
                 },
-                move |_| {
-                    let mention_ses = mention_ses.clone();
-                    Box::pin(async move { mentions_page(mention_ses.clone(), id).await })
+                route("mentions", id),
+            );
+
+            em = em.add_route_field(
+                "Message Style",
+                "Change the wording, color and image of the messages",
+                false,
+                &ButtonType {
+                    emoji: Some('🎨'.into()),
+                    style: ButtonStyle::Primary,
+                    label: "Message Style".to_owned(),
                 },
+                route("templates", id),
             );
         }
 
-        let other_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Other",
             "Enable other notifications",
             false,
@@ -224,34 +280,16 @@ fn main_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) -> futures::future::BoxFutu
                 style: ButtonStyle::Primary,
                 label: "Other".to_owned(),
             },
-            move |_| {
-                let other_ses = other_ses.clone();
-                Box::pin(async move { other_page(other_ses.clone(), id).await })
-            },
+            route("other", id),
         );
 
         if id.guild_specific() {
-            let close_ses = ses.clone();
-            em = em.add_field(
+            em = em.add_route_field(
                 "Close",
                 "Close this menu",
                 false,
                 &StandardButton::Exit.to_button(),
-                move |_| {
-                    let close_ses = close_ses.clone();
-                    Box::pin(async move {
-                        let lock = close_ses
-                            .read()
-                            .await;
-                        let r = lock
-                            .interaction
-                            .delete_response(&lock.http)
-                            .await;
-                        if let Err(e) = r {
-                            dbg!(e);
-                        }
-                    })
-                },
+                route("close", id),
             );
         }
 
@@ -263,3 +301,198 @@ fn main_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) -> futures::future::BoxFutu
         }
     })
 }
+
+pub static REMINDERS_COMPONENT: Component = Component {
+    prefix: "reminders",
+    func: reminders_component,
+};
+
+/// Check if the user clicking a reminders menu is allowed to use it, falling
+/// back on the target of the menu when discord no longer tells us who opened
+/// it.
+fn may_use_menu(interaction: &ComponentInteraction, id: ID) -> bool {
+    if let Some(author) = session_author(interaction) {
+        return author
+            == interaction
+                .user
+                .id;
+    }
+
+    match id {
+        ID::User(user_id) => {
+            user_id
+                == interaction
+                    .user
+                    .id
+        },
+        ID::Channel((_, guild_id)) => {
+            interaction.guild_id == Some(guild_id)
+                && interaction
+                    .member
+                    .as_ref()
+                    .and_then(|member| member.permissions)
+                    .is_some_and(Permissions::manage_guild)
+        },
+    }
+}
+
+fn reminders_component<'fut>(
+    ctx: &'fut Context,
+    interaction: &'fut ComponentInteraction,
+    args: &'fut [String],
+) -> futures::future::BoxFuture<'fut, CommandResult> {
+    Box::pin(async move {
+        let [action, id, extra @ ..] = args else {
+            return Err(format!("invalid reminders component: {args:?}").into());
+        };
+        let id: ID = id.parse()?;
+
+        if !may_use_menu(interaction, id) {
+            deny_route(ctx, interaction).await?;
+            return Ok(());
+        }
+
+        // modals have to be the response to the click itself
+        let ses = EmbedSession::from_component(
+            ctx,
+            interaction.clone(),
+            ![
+                "add-payload-filter",
+                "add-booster",
+                "type-reminder",
+                "edit-template",
+                "template-color",
+                "preview-templates",
+            ]
+            .contains(&action.as_str()),
+        )
+        .await?;
+
+        match (action.as_str(), extra) {
+            ("main", []) => main_menu(ses, id).await,
+            ("close", []) => {
+                let lock = ses
+                    .read()
+                    .await;
+                lock.interaction
+                    .delete_response(&lock.http)
+                    .await?;
+            },
+            ("reminders", []) => reminders_page(ses, id).await,
+            ("add-reminder", []) => add_reminder_menu(ses, id).await,
+            ("type-reminder", []) => type_reminder_menu(ses, id, interaction.clone()).await,
+            ("remove-reminder", []) => remove_reminder_menu(ses, id, interaction.clone()).await,
+            ("filters", []) => filters_page(ses, id).await,
+            ("disallow-filters", []) => disallow_filters_page(ses, id).await,
+            ("add-filter", []) => add_filter_menu(ses, id, interaction.clone()).await,
+            ("remove-filter", []) => remove_filter_menu(ses, id, interaction.clone()).await,
+            ("allow-filters", []) => allow_filters_page(ses, id).await,
+            ("add-allow-filter", []) => add_allow_filter_menu(ses, id, interaction.clone()).await,
+            ("remove-allow-filter", []) => {
+                remove_allow_filter_menu(ses, id, interaction.clone()).await;
+            },
+            ("payload-filters", []) => payload_filters_page(ses, id).await,
+            ("add-payload-filter", []) => {
+                add_payload_filter_menu(ses, id, interaction.clone()).await;
+            },
+            ("remove-payload-filter", []) => {
+                remove_payload_filter_menu(ses, id, interaction.clone()).await;
+            },
+            ("event-filters", []) => event_filters_page(ses, id).await,
+            ("add-event-filter", []) => add_event_filter_menu(ses, id, interaction.clone()).await,
+            ("remove-event-filter", []) => {
+                remove_event_filter_menu(ses, id, interaction.clone()).await;
+            },
+            ("boosters", []) => boosters_page(ses, id).await,
+            ("add-booster", []) => add_booster_menu(ses, id, interaction.clone()).await,
+            ("remove-booster", []) => remove_booster_menu(ses, id, interaction.clone()).await,
+            ("mentions", []) => mentions_page(ses, id).await,
+            ("add-mention", []) => add_mention_menu(ses, id, interaction.clone()).await,
+            ("remove-mention", []) => remove_mention_menu(ses, id, interaction.clone()).await,
+            ("mention-rules", []) => mention_rules_page(ses, id).await,
+            ("add-mention-rule", [kind, minutes]) => {
+                let minutes = (minutes != "all")
+                    .then(|| minutes.parse())
+                    .transpose()?;
+                add_mention_rule_menu(
+                    ses,
+                    id,
+                    interaction.clone(),
+                    kind.parse()?,
+                    minutes,
+                )
+                .await;
+            },
+            ("remove-mention-rule", []) => {
+                remove_mention_rule_menu(ses, id, interaction.clone()).await;
+            },
+            ("launch-mention", [kind]) => {
+                launch_mention_menu(
+                    ses,
+                    id,
+                    interaction.clone(),
+                    kind.parse()?,
+                )
+                .await;
+            },
+            ("add-launch-mention", [kind, filter]) => {
+                add_launch_mention_menu(
+                    ses,
+                    id,
+                    interaction.clone(),
+                    kind.parse()?,
+                    filter.clone(),
+                )
+                .await;
+            },
+            ("remove-launch-mention", []) => {
+                remove_launch_mention_menu(ses, id, interaction.clone()).await;
+            },
+            ("templates", []) => templates_page(ses, id).await,
+            ("edit-template", [kind]) => {
+                edit_template_menu(
+                    ses,
+                    id,
+                    interaction.clone(),
+                    kind.parse()?,
+                )
+                .await;
+            },
+            ("template-color", []) => template_color_menu(ses, id, interaction.clone()).await,
+            ("preview-templates", []) => {
+                preview_templates_menu(ses, id, interaction.clone()).await;
+            },
+            ("other", []) => other_page(ses, id).await,
+            ("toggle", [setting, value])
+                if [
+                    "scrub_notifications",
+                    "outcome_notifications",
+                    "mention_others",
+                    "launch_threads",
+                ]
+                .contains(&setting.as_str()) =>
+            {
+                toggle_setting(&ses, id, setting, value.parse()?).await;
+                other_page(ses, id).await;
+            },
+            ("toggle", [setting, value]) if setting == "plain_text" => {
+                toggle_plain_text(&ses, id, value.parse()?).await;
+                other_page(ses, id).await;
+            },
+            ("toggle", [setting, value]) if setting == "launch_mentions_only" => {
+                toggle_setting(&ses, id, setting, value.parse()?).await;
+                mentions_page(ses, id).await;
+            },
+            ("toggle", [setting, value]) if setting == "hide_thumbnail" => {
+                toggle_setting(&ses, id, setting, value.parse()?).await;
+                templates_page(ses, id).await;
+            },
+            ("notification-channel", []) => {
+                notification_channel_menu(ses, id, interaction.clone()).await;
+            },
+            _ => return Err(format!("unknown reminders component: {args:?}").into()),
+        }
+
+        Ok(())
+    })
+}
@@ -3,56 +3,108 @@ use std::{
     sync::Arc,
 };
 
-use chrono::Utc;
+use chrono::{
+    Duration,
+    Utc,
+};
+use itertools::Itertools;
+use okto_framework::structs::ComponentId;
+use regex::Regex;
 use serenity::{
     builder::{
         CreateEmbed,
         CreateEmbedAuthor,
+        CreateInteractionResponse,
+        CreateInteractionResponseFollowup,
+        CreateInteractionResponseMessage,
     },
+    http::Http,
     model::{
         application::{
             ButtonStyle,
+            ComponentInteraction,
             InputTextStyle,
             Interaction,
         },
-        channel::ReactionType,
+        channel::{
+            ChannelType,
+            ReactionType,
+        },
+        id::{
+            RoleId,
+            UserId,
+        },
+    },
+    prelude::{
+        RwLock,
+        TypeMap,
     },
-    prelude::RwLock,
 };
 
 use super::{
-    main_menu,
     settings::{
         add_filter,
+        add_launch_mention,
         add_mention,
+        add_mention_rule,
         add_reminder,
         get_reminders,
         remove_filter,
+        remove_launch_mention,
         remove_mention,
+        remove_mention_rule,
         remove_reminder,
+        set_embed_color,
         set_notification_channel,
-        toggle_setting,
+        set_template,
     },
+    REMINDERS_COMPONENT,
 };
 use crate::{
     events::{
         modal::{
             Field,
             Modal,
+            Validator,
         },
         select_menu::SelectMenu,
         statefulembed::{
             ButtonType,
             EmbedSession,
+            SessionInteraction,
             StatefulEmbed,
         },
         time_embed::TimeEmbed,
     },
-    models::reminders::Reminder,
+    models::{
+        caches::{
+            CatalogKey,
+            EventsCacheKey,
+            LaunchesCacheKey,
+        },
+        reminders::{
+            LaunchMention,
+            LaunchMentionKind,
+            MentionKind,
+            MentionRule,
+            MessageTemplate,
+            Reminder,
+            ReminderSettings,
+        },
+    },
+    reminders::{
+        check_template,
+        plain_messages,
+        preview_templates,
+        Notice,
+        PLACEHOLDERS,
+    },
     utils::{
+        catalog::Catalog,
         constants::*,
         default_select_menus::{
             channel_select_menu,
+            role_choice_menu,
             role_select_menu,
         },
         format_duration,
@@ -69,13 +121,123 @@ use crate::{
     },
 };
 
+/// Build the `custom_id` for a reminders menu button, so the click can be
+/// handled even after a restart.
+pub fn route(action: &str, id: ID) -> ComponentId {
+    ComponentId::new(REMINDERS_COMPONENT.prefix)
+        .arg(action)
+        .arg(id)
+}
+
+async fn session_parts(
+    ses: &Arc<RwLock<EmbedSession>>,
+) -> (UserId, Arc<Http>, Arc<RwLock<TypeMap>>) {
+    let s = ses
+        .read()
+        .await;
+    (
+        s.author,
+        s.http
+            .clone(),
+        s.data
+            .clone(),
+    )
+}
+
+/// A copy of the catalog for the menus to pick agencies and vehicles from.
+async fn session_catalog(ses: &Arc<RwLock<EmbedSession>>) -> Catalog {
+    let data = ses
+        .read()
+        .await
+        .data
+        .clone();
+    let shared = data
+        .read()
+        .await
+        .get::<CatalogKey>()
+        .cloned();
+
+    if let Some(catalog) = shared {
+        catalog
+            .read()
+            .await
+            .clone()
+    } else {
+        eprintln!("Could not get the catalog");
+        Catalog::default()
+    }
+}
+
+/// Show a select menu in place of the page, or let the user know there is
+/// nothing to pick when the menu can't be made.
+async fn listen_menu(
+    menu: serenity::Result<SelectMenu>,
+    nothing_to_pick: &str,
+    http: Arc<Http>,
+    button_click: ComponentInteraction,
+    data: Arc<RwLock<TypeMap>>,
+) {
+    match menu {
+        Ok(menu) => {
+            menu.listen(
+                http,
+                &Interaction::Component(button_click),
+                data,
+            )
+            .await;
+        },
+        Err(e) => {
+            eprintln!("Can't show select menu: {e}");
+            tell_nothing_to_pick(&http, &button_click, nothing_to_pick).await;
+        },
+    }
+}
+
+/// Let the user know a menu has no options, only to the one who clicked.
+async fn tell_nothing_to_pick(http: &Http, button_click: &ComponentInteraction, text: &str) {
+    if let Err(e) = button_click
+        .create_followup(
+            http,
+            CreateInteractionResponseFollowup::new()
+                .ephemeral(true)
+                .content(text),
+        )
+        .await
+    {
+        eprintln!("Can't send followup: {e}");
+    }
+}
+
+async fn settings_list<T, F>(ses: &Arc<RwLock<EmbedSession>>, id: ID, pick: F) -> Vec<T>
+where
+    F: Fn(&dyn ReminderSettings) -> Vec<T>,
+{
+    let Some(db) = get_db(ses).await else {
+        return Vec::new();
+    };
+
+    match id {
+        ID::Channel((_, guild_id)) => {
+            get_guild_settings(&db, guild_id.into())
+                .await
+                .map(|settings| pick(&settings))
+        },
+        ID::User(user_id) => {
+            get_user_settings(&db, user_id.into())
+                .await
+                .map(|settings| pick(&settings))
+        },
+    }
+    .unwrap_or_default()
+}
+
 pub fn reminders_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
 ) -> futures::future::BoxFuture<'static, ()> {
     Box::pin(async move {
         let reminders_res = get_reminders(&ses, id).await;
-        let (description, durations) = match reminders_res {
+        let description = match reminders_res {
             Ok(ref reminders) if !reminders.is_empty() => {
                 let mut text = "The following reminders have been set:".to_owned();
                 for reminder in reminders {
@@ -86,20 +248,9 @@ pub fn reminders_page(
                     )
                     .expect("write to String: can't fail");
                 }
-                (
-                    text,
-                    reminders
-                        .iter()
-                        .map(Reminder::get_duration)
-                        .collect::<Vec<_>>(),
-                )
-            },
-            _ => {
-                (
-                    "No reminders have been set yet".to_owned(),
-                    Vec::new(),
-                )
+                text
             },
+            _ => "No reminders have been set yet".to_owned(),
         };
 
         let mut em = StatefulEmbed::new_with_embed(
@@ -111,109 +262,42 @@ pub fn reminders_page(
                 .description(description),
         );
 
-        let add_ses = ses.clone();
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Add reminder".to_owned(),
                 style: ButtonStyle::Primary,
                 emoji: Some(PROGRADE.clone()),
             },
-            move |_| {
-                Box::pin({
-                    let add_ses = add_ses.clone();
-                    async move {
-                        let inner_ses = add_ses.clone();
-                        let wait_ses = add_ses.clone();
-
-                        TimeEmbed::new(inner_ses, move |dur| {
-                            let wait_ses = wait_ses.clone();
-                            Box::pin(async move {
-                                if !dur.is_zero() {
-                                    add_reminder(&wait_ses.clone(), id, dur).await;
-                                }
-                                reminders_page(wait_ses.clone(), id).await;
-                            })
-                        })
-                        .listen()
-                        .await;
-                    }
-                })
+            route("add-reminder", id),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Type it in".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some('⌨'.into()),
             },
+            route("type-reminder", id),
         );
 
         if reminders_res.is_ok() {
-            let remove_ses = ses.clone();
-            em.add_option(
+            em.add_route(
                 &ButtonType {
                     label: "Remove reminder".to_owned(),
                     style: ButtonStyle::Primary,
                     emoji: Some(RETROGRADE.clone()),
                 },
-                move |button_click| {
-                    let remove_ses = remove_ses.clone();
-                    let durations = durations.clone();
-                    Box::pin(async move {
-                        let inner_ses = remove_ses.clone();
-                        let wait_ses = remove_ses.clone();
-
-                        let (user_id, http, data) = {
-                            let s = inner_ses
-                                .read()
-                                .await;
-                            (
-                                s.author,
-                                s.http
-                                    .clone(),
-                                s.data
-                                    .clone(),
-                            )
-                        };
-
-                        SelectMenu::builder(move |(choice, _)| {
-                            let wait_ses = wait_ses.clone();
-                            Box::pin(async move {
-                                remove_reminder(
-                                    &wait_ses.clone(),
-                                    id,
-                                    parse_duration(&choice),
-                                )
-                                .await;
-                                reminders_page(wait_ses.clone(), id).await;
-                            })
-                        })
-                        .set_description("Select the reminder you want to remove")
-                        .set_custom_id(&format!("{user_id}-remove-reminder"))
-                        .set_user(user_id)
-                        .set_options(
-                            durations
-                                .iter()
-                                .map(|dur| format_duration(*dur, true))
-                                .map(|s| (s.clone(), s))
-                                .collect(),
-                        )
-                        .build()
-                        .unwrap()
-                        .listen(
-                            http,
-                            &Interaction::Component(button_click),
-                            data,
-                        )
-                        .await;
-                    })
-                },
+                route("remove-reminder", id),
             );
         }
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Back to main menu".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { main_menu(ses.clone(), id).await })
-            },
+            route("main", id),
         );
 
         let result = em
@@ -225,6 +309,168 @@ pub fn reminders_page(
     })
 }
 
+pub async fn add_reminder_menu(ses: Arc<RwLock<EmbedSession>>, id: ID) {
+    let wait_ses = ses.clone();
+
+    TimeEmbed::new(ses, move |dur| {
+        let wait_ses = wait_ses.clone();
+        Box::pin(async move {
+            if !dur.is_zero() {
+                add_reminder(&wait_ses.clone(), id, dur).await;
+            }
+            reminders_page(wait_ses.clone(), id).await;
+        })
+    })
+    .listen()
+    .await;
+}
+
+pub async fn type_reminder_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let existing = get_reminders(&ses, id)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|r| r.minutes)
+        .collect::<Vec<_>>();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    Modal::builder(move |modal_submit, inputs| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            // the button click was answered with the modal, so only the submit can edit the
+            // menu
+            wait_ses
+                .write()
+                .await
+                .set_interaction(SessionInteraction::Modal(modal_submit));
+
+            if let Some(Ok(dur)) = inputs
+                .first()
+                .map(|(_, value)| parse_duration(value))
+            {
+                add_reminder(&wait_ses.clone(), id, dur).await;
+            }
+            reminders_page(wait_ses.clone(), id).await;
+        })
+    })
+    .set_title("Add a reminder")
+    .set_custom_id(&format!("{user_id}-type-reminder"))
+    .set_user(user_id)
+    .add_field(
+        Field::new(
+            InputTextStyle::Short,
+            "reminder_offset",
+            "How long before launch",
+        )
+        .set_max_length(50)
+        .set_min_length(2)
+        .set_placeholder("For example 1d2h30m, 90m or 1 day and 2 hours")
+        .set_required()
+        .add_validator(Validator::Duration)
+        .add_validator(Validator::custom(move |input| {
+            check_reminder_offset(input, &existing)
+        })),
+    )
+    .build()
+    .unwrap()
+    .listen(
+        http,
+        &Interaction::Component(button_click),
+        data,
+    )
+    .await;
+}
+
+/// Check that a typed in reminder is within bounds and not set already.
+fn check_reminder_offset(input: &str, existing: &[i64]) -> Result<(), String> {
+    let dur = parse_duration(input).map_err(|err| err.to_string())?;
+
+    if dur.num_seconds() % 60 != 0 {
+        return Err("reminders can only be set in whole minutes".to_owned());
+    }
+
+    let minutes = dur.num_minutes();
+    if !(MIN_REMINDER_MINUTES..=MAX_REMINDER_MINUTES).contains(&minutes) {
+        return Err(format!(
+            "reminders have to be between {} and {} before launch",
+            format_duration(
+                Duration::minutes(MIN_REMINDER_MINUTES),
+                false
+            ),
+            format_duration(
+                Duration::minutes(MAX_REMINDER_MINUTES),
+                false
+            )
+        ));
+    }
+
+    if existing.contains(&minutes) {
+        return Err(format!(
+            "a reminder for {} before launch has already been set",
+            format_duration(dur, false)
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn remove_reminder_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let durations = get_reminders(&ses, id)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(Reminder::get_duration)
+        .collect::<Vec<_>>();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            if let Ok(minutes) = choice.parse() {
+                remove_reminder(
+                    &wait_ses.clone(),
+                    id,
+                    Duration::minutes(minutes),
+                )
+                .await;
+            }
+            reminders_page(wait_ses.clone(), id).await;
+        })
+    })
+    .set_description("Select the reminder you want to remove")
+    .set_custom_id(&format!("{user_id}-remove-reminder"))
+    .set_user(user_id)
+    .set_options(
+        durations
+            .iter()
+            .map(|dur| {
+                (
+                    dur.num_minutes()
+                        .to_string(),
+                    format_duration(*dur, true),
+                )
+            })
+            .collect(),
+    )
+    .build();
+    listen_menu(
+        menu,
+        "There are no reminders to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
 pub fn filters_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
@@ -238,32 +484,23 @@ pub fn filters_page(
                 .author(CreateEmbedAuthor::new("Filters").icon_url(DEFAULT_ICON)),
         );
 
-        let filters_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Filters",
             "Set which agencies to filter out of launch reminders, making you not get any reminders for these agencies again",
             false,
             &ButtonType{ emoji: Some('⛔'.into()), style: ButtonStyle::Primary, label: "Disallow Filters".to_owned()},
-            move |_| {
-                let filters_ses = filters_ses.clone();
-                Box::pin(async move { disallow_filters_page(filters_ses.clone(), id).await })
-            },
+            route("disallow-filters", id),
         );
 
-        let allow_filters_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Allow Filters",
             "Set which agencies to filter launch reminders for, making you get **only** reminders for these agencies",
             false,
             &ButtonType{ emoji: Some('🔍'.into()), style: ButtonStyle::Primary, label: "Allow Filters".to_owned()},
-            move |_| {
-                let allow_filters_ses = allow_filters_ses.clone();
-                Box::pin(async move { allow_filters_page(allow_filters_ses.clone(), id).await })
-            },
+            route("allow-filters", id),
         );
 
-        let payload_filters_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Payload Filters",
             "Add word or regex filters to filter out launches with specific payloads",
             false,
@@ -272,22 +509,28 @@ pub fn filters_page(
                 style: ButtonStyle::Primary,
                 label: "Payload Filters".to_owned(),
             },
-            move |_| {
-                let allow_filters_ses = payload_filters_ses.clone();
-                Box::pin(async move { payload_filters_page(allow_filters_ses.clone(), id).await })
+            route("payload-filters", id),
+        );
+
+        em = em.add_route_field(
+            "Event Filters",
+            "Set which types of space events, like spacewalks or dockings, to not get reminders for",
+            false,
+            &ButtonType {
+                emoji: Some('📅'.into()),
+                style: ButtonStyle::Primary,
+                label: "Event Filters".to_owned(),
             },
+            route("event-filters", id),
         );
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Back to main menu".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { main_menu(ses.clone(), id).await })
-            },
+            route("main", id),
         );
 
         let result = em
@@ -299,7 +542,7 @@ pub fn filters_page(
     })
 }
 
-fn disallow_filters_page(
+pub fn disallow_filters_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
 ) -> futures::future::BoxFuture<'static, ()> {
@@ -308,6 +551,7 @@ fn disallow_filters_page(
             return;
         };
 
+        let catalog = session_catalog(&ses).await;
         let (description, filters) = match id {
             ID::Channel(channel_id) => {
                 let settings_res = get_guild_settings(
@@ -328,9 +572,9 @@ fn disallow_filters_page(
                             write!(
                                 text,
                                 "\n`{}`",
-                                LAUNCH_AGENCIES
-                                    .get(filter.as_str())
-                                    .unwrap_or(&"unknown agency")
+                                catalog
+                                    .agency_name(filter)
+                                    .unwrap_or_else(|| "unknown agency".to_owned())
                             )
                             .expect("write to String: can't fail");
                         }
@@ -362,9 +606,9 @@ fn disallow_filters_page(
                             write!(
                                 text,
                                 "\n`{}`",
-                                LAUNCH_AGENCIES
-                                    .get(filter.as_str())
-                                    .unwrap_or(&"unknown agency")
+                                catalog
+                                    .agency_name(filter)
+                                    .unwrap_or_else(|| "unknown agency".to_owned())
                             )
                             .expect("write to String: can't fail");
                         }
@@ -396,144 +640,33 @@ fn disallow_filters_page(
                 .description(description),
         );
 
-        let add_ses = ses.clone();
-        let filters_clone = filters.clone();
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Add filter".to_owned(),
                 style: ButtonStyle::Primary,
                 emoji: Some(PROGRADE.clone()),
             },
-            move |button_click| {
-                let add_ses = add_ses.clone();
-                let filters_clone = filters_clone.clone();
-                Box::pin(async move {
-                    let inner_ses = add_ses.clone();
-                    let wait_ses = add_ses.clone();
-
-                    let (user_id, http, data) = {
-                        let s = inner_ses
-                            .read()
-                            .await;
-                        (
-                            s.author,
-                            s.http
-                                .clone(),
-                            s.data
-                                .clone(),
-                        )
-                    };
-
-                    SelectMenu::builder(move |(choice, _)| {
-                        let wait_ses = wait_ses.clone();
-                        Box::pin(async move {
-                            if LAUNCH_AGENCIES.contains_key(choice.as_str()) {
-                                add_filter(&wait_ses.clone(), id, choice, "filters").await;
-                            } else {
-                                eprintln!("select menu returned unknown choice")
-                            }
-                            disallow_filters_page(wait_ses.clone(), id).await
-                        })
-                    })
-                    .set_description(
-                        "Select the name of the agency you do not want to receive reminders for",
-                    )
-                    .set_custom_id(&format!("{user_id}-add-filter"))
-                    .set_user(user_id)
-                    .set_options(
-                        LAUNCH_AGENCIES
-                            .iter()
-                            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
-                            .filter(|(k, _)| !filters_clone.contains(k))
-                            .collect(),
-                    )
-                    .build()
-                    .unwrap()
-                    .listen(
-                        http,
-                        &Interaction::Component(button_click),
-                        data,
-                    )
-                    .await;
-                })
-            },
+            route("add-filter", id),
         );
 
         if !filters.is_empty() {
-            let remove_ses = ses.clone();
-            let filters_clone = filters.clone();
-            em.add_option(
+            em.add_route(
                 &ButtonType {
                     label: "Remove filter".to_owned(),
                     style: ButtonStyle::Primary,
                     emoji: Some(RETROGRADE.clone()),
                 },
-                move |button_click| {
-                    let remove_ses = remove_ses.clone();
-                    let filters_clone = filters_clone.clone();
-                    Box::pin(async move {
-                        let inner_ses = remove_ses.clone();
-                        let wait_ses = remove_ses.clone();
-
-                        let (user_id, http, data) = {
-                            let s = inner_ses
-                                .read()
-                                .await;
-                            (
-                                s.author,
-                                s.http
-                                    .clone(),
-                                s.data
-                                    .clone(),
-                            )
-                        };
-
-                        SelectMenu::builder(move |(choice, _)| {
-                            let wait_ses = wait_ses.clone();
-                            Box::pin(async move {
-                                if LAUNCH_AGENCIES.contains_key(choice.as_str()) {
-                                    remove_filter(&wait_ses.clone(), id, choice, "filters").await;
-                                } else {
-                                    eprintln!("select menu returned unknown choice")
-                                }
-                                disallow_filters_page(wait_ses.clone(), id).await
-                            })
-                        })
-                        .set_description(
-                            "Select the name of the agency you want to receive reminders for again",
-                        )
-                        .set_custom_id(&format!("{user_id}-remove-filter"))
-                        .set_user(user_id)
-                        .set_options(
-                            LAUNCH_AGENCIES
-                                .iter()
-                                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
-                                .filter(|(k, _)| filters_clone.contains(k))
-                                .collect(),
-                        )
-                        .build()
-                        .unwrap()
-                        .listen(
-                            http,
-                            &Interaction::Component(button_click),
-                            data,
-                        )
-                        .await;
-                    })
-                },
+                route("remove-filter", id),
             );
         }
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Back to the filters page".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { filters_page(ses.clone(), id).await })
-            },
+            route("filters", id),
         );
 
         let result = em
@@ -545,7 +678,105 @@ fn disallow_filters_page(
     })
 }
 
-fn allow_filters_page(
+pub async fn add_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let filters = settings_list(&ses, id, |s| {
+        s.get_filters()
+            .clone()
+    })
+    .await;
+    let catalog = session_catalog(&ses).await;
+    let agency_filters = catalog.agency_filters();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        let known = catalog
+            .agency(&choice)
+            .is_some();
+        Box::pin(async move {
+            if known {
+                add_filter(&wait_ses.clone(), id, choice, "filters").await;
+            } else {
+                eprintln!("select menu returned unknown choice")
+            }
+            disallow_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the name of the agency you do not want to receive reminders for")
+    .set_custom_id(&format!("{user_id}-add-filter"))
+    .set_user(user_id)
+    .set_options(
+        agency_filters
+            .into_iter()
+            .filter(|(k, _)| !filters.contains(k))
+            .collect(),
+    )
+    .build();
+
+    listen_menu(
+        menu,
+        "There are no agencies left to filter out",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub async fn remove_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let filters = settings_list(&ses, id, |s| {
+        s.get_filters()
+            .clone()
+    })
+    .await;
+    let catalog = session_catalog(&ses).await;
+    let agency_filters = catalog.agency_filters();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        let known = catalog
+            .agency(&choice)
+            .is_some();
+        Box::pin(async move {
+            if known {
+                remove_filter(&wait_ses.clone(), id, choice, "filters").await;
+            } else {
+                eprintln!("select menu returned unknown choice")
+            }
+            disallow_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the name of the agency you want to receive reminders for again")
+    .set_custom_id(&format!("{user_id}-remove-filter"))
+    .set_user(user_id)
+    .set_options(
+        agency_filters
+            .into_iter()
+            .filter(|(k, _)| filters.contains(k))
+            .collect(),
+    )
+    .build();
+
+    listen_menu(
+        menu,
+        "There are no agency filters to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub fn allow_filters_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
 ) -> futures::future::BoxFuture<'static, ()> {
@@ -554,6 +785,7 @@ fn allow_filters_page(
             return;
         };
 
+        let catalog = session_catalog(&ses).await;
         let (description, allow_filters) = match id {
             ID::Channel(channel_id) => {
                 let settings_res = get_guild_settings(
@@ -575,9 +807,9 @@ fn allow_filters_page(
                             write!(
                                 text,
                                 "\n`{}`",
-                                LAUNCH_AGENCIES
-                                    .get(filter.as_str())
-                                    .unwrap_or(&"unknown agency")
+                                catalog
+                                    .agency_name(filter)
+                                    .unwrap_or_else(|| "unknown agency".to_owned())
                             )
                             .expect("write to String: can't fail");
                         }
@@ -610,9 +842,9 @@ fn allow_filters_page(
                             write!(
                                 text,
                                 "\n`{}`",
-                                LAUNCH_AGENCIES
-                                    .get(filter.as_str())
-                                    .unwrap_or(&"unknown agency")
+                                catalog
+                                    .agency_name(filter)
+                                    .unwrap_or_else(|| "unknown agency".to_owned())
                             )
                             .expect("write to String: can't fail");
                         }
@@ -644,145 +876,33 @@ fn allow_filters_page(
                 .description(description),
         );
 
-        let add_ses = ses.clone();
-        let allow_filters_clone = allow_filters.clone();
-        em.add_option(
+        em.add_route(
             &ButtonType {
-                style: ButtonStyle::Primary,
                 label: "Add allow filter".to_owned(),
+                style: ButtonStyle::Primary,
                 emoji: Some(PROGRADE.clone()),
             },
-            move |button_click| {
-                let add_ses = add_ses.clone();
-                let allow_filters_clone = allow_filters_clone.clone();
-                Box::pin(async move {
-                    let inner_ses = add_ses.clone();
-                    let wait_ses = add_ses.clone();
-
-                    let (user_id, http, data) = {
-                        let s = inner_ses
-                            .read()
-                            .await;
-                        (
-                            s.author,
-                            s.http
-                                .clone(),
-                            s.data
-                                .clone(),
-                        )
-                    };
-
-                    SelectMenu::builder(move |(choice, _)| {
-                        let wait_ses = wait_ses.clone();
-                        Box::pin(async move {
-                            if LAUNCH_AGENCIES.contains_key(choice.as_str()) {
-                                add_filter(
-                                    &wait_ses.clone(),
-                                    id,
-                                    choice,
-                                    "allow_filters",
-                                )
-                                .await;
-                            } else {
-                                eprintln!("select menu returned unknown choice")
-                            }
-                            allow_filters_page(wait_ses.clone(), id).await
-                        })
-                    })
-                    .set_description(
-                        "Select the name of the agency you specifically want to get reminders for",
-                    )
-                    .set_custom_id(&format!("{user_id}-add-allow-filter"))
-                    .set_user(user_id)
-                    .make_ephemeral()
-                    .set_options(
-                        LAUNCH_AGENCIES
-                            .iter()
-                            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
-                            .filter(|(k, _)| !allow_filters_clone.contains(k))
-                            .collect(),
-                    )
-                    .build()
-                    .unwrap()
-                    .listen(
-                        http,
-                        &Interaction::Component(button_click),
-                        data,
-                    )
-                    .await;
-                })
-            },
+            route("add-allow-filter", id),
         );
 
         if !allow_filters.is_empty() {
-            let remove_ses = ses.clone();
-            let allow_filters_clone = allow_filters.clone();
-            em.add_option(
+            em.add_route(
                 &ButtonType {
                     label: "Remove allow filter".to_owned(),
                     style: ButtonStyle::Primary,
-                    emoji: Some(RETROGRADE.clone())
-                },
-                move |button_click| {
-                    let remove_ses = remove_ses.clone();
-                    let allow_filters_clone = allow_filters_clone.clone();
-                    Box::pin(async move {
-                        let inner_ses = remove_ses.clone();
-                        let wait_ses = remove_ses.clone();
-
-                        let (user_id, http, data) = {
-                            let s = inner_ses
-                                    .read()
-                                    .await;
-                            (
-                                s.author,
-                                s.http
-                                    .clone(),
-                                s.data
-                                    .clone(),
-                            )
-                        };
-
-                        SelectMenu::builder(move |(choice, _)| {
-                                let wait_ses = wait_ses.clone();
-                                Box::pin(async move {
-                                    if LAUNCH_AGENCIES.contains_key(choice.as_str()) {
-                                        remove_filter(&wait_ses.clone(), id, choice, "allow_filters").await;
-                                    } else {
-                                        eprintln!("select menu returned unknown choice")
-                                    }
-                                    allow_filters_page(wait_ses.clone(), id).await
-                                })
-                        })
-                            .set_description("Select the name of the agency you do not want to receive reminders for again")
-                            .set_custom_id(&format!("{user_id}-remove-allow-filter"))
-                        .set_user(user_id)
-                            .make_ephemeral()
-                            .set_options(
-                                LAUNCH_AGENCIES
-                                    .iter()
-                                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
-                                    .filter(|(k, _)| allow_filters_clone.contains(k))
-                                    .collect()
-                            )
-                            .build()
-                            .unwrap()
-                            .listen(http, &Interaction::Component(button_click), data).await;
-                    })
+                    emoji: Some(RETROGRADE.clone()),
                 },
+                route("remove-allow-filter", id),
             );
         }
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Back to the filters page".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { filters_page(ses.clone(), id).await })
-            },
+            route("filters", id),
         );
 
         let result = em
@@ -794,7 +914,121 @@ fn allow_filters_page(
     })
 }
 
-fn payload_filters_page(
+pub async fn add_allow_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let filters = settings_list(&ses, id, |s| {
+        s.get_allow_filters()
+            .clone()
+    })
+    .await;
+    let catalog = session_catalog(&ses).await;
+    let agency_filters = catalog.agency_filters();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        let known = catalog
+            .agency(&choice)
+            .is_some();
+        Box::pin(async move {
+            if known {
+                add_filter(
+                    &wait_ses.clone(),
+                    id,
+                    choice,
+                    "allow_filters",
+                )
+                .await;
+            } else {
+                eprintln!("select menu returned unknown choice")
+            }
+            allow_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the name of the agency you specifically want to get reminders for")
+    .set_custom_id(&format!("{user_id}-add-allow-filter"))
+    .set_user(user_id)
+    .make_ephemeral()
+    .set_options(
+        agency_filters
+            .into_iter()
+            .filter(|(k, _)| !filters.contains(k))
+            .collect(),
+    )
+    .build();
+
+    listen_menu(
+        menu,
+        "There are no agencies left to allow",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub async fn remove_allow_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let filters = settings_list(&ses, id, |s| {
+        s.get_allow_filters()
+            .clone()
+    })
+    .await;
+    let catalog = session_catalog(&ses).await;
+    let agency_filters = catalog.agency_filters();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        let known = catalog
+            .agency(&choice)
+            .is_some();
+        Box::pin(async move {
+            if known {
+                remove_filter(
+                    &wait_ses.clone(),
+                    id,
+                    choice,
+                    "allow_filters",
+                )
+                .await;
+            } else {
+                eprintln!("select menu returned unknown choice")
+            }
+            allow_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the name of the agency you do not want to receive reminders for again")
+    .set_custom_id(&format!(
+        "{user_id}-remove-allow-filter"
+    ))
+    .set_user(user_id)
+    .make_ephemeral()
+    .set_options(
+        agency_filters
+            .into_iter()
+            .filter(|(k, _)| filters.contains(k))
+            .collect(),
+    )
+    .build();
+
+    listen_menu(
+        menu,
+        "There are no agency allow filters to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub fn payload_filters_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
 ) -> futures::future::BoxFuture<'static, ()> {
@@ -887,162 +1121,33 @@ fn payload_filters_page(
                 .description(description),
         );
 
-        let add_ses = ses.clone();
-        em.add_non_update_option(
+        em.add_route(
             &ButtonType {
-                style: ButtonStyle::Primary,
                 label: "Add payload filter".to_owned(),
+                style: ButtonStyle::Primary,
                 emoji: Some(PROGRADE.clone()),
             },
-            move |button_click| {
-                let add_ses = add_ses.clone();
-                Box::pin(async move {
-                    let inner_ses = add_ses.clone();
-                    let wait_ses = add_ses.clone();
-
-                    let (user_id, http, data) = {
-                        let s = inner_ses
-                            .read()
-                            .await;
-                        (
-                            s.author,
-                            s.http
-                                .clone(),
-                            s.data
-                                .clone(),
-                        )
-                    };
-
-                    Modal::builder(move |inputs| {
-                        let wait_ses = wait_ses.clone();
-                        Box::pin(async move {
-                            if !inputs.is_empty() {
-                                add_filter(
-                                    &wait_ses.clone(),
-                                    id,
-                                    filter_from_string_input(
-                                        inputs
-                                            .first()
-                                            .expect("modal did not return an input value")
-                                            .clone()
-                                            .1,
-                                    ),
-                                    "payload_filters",
-                                )
-                                .await;
-                            }
-                            payload_filters_page(wait_ses.clone(), id).await
-                        })
-                    })
-                    .set_title("Payload filter modal")
-                    .set_custom_id(&format!("{user_id}-add-payload-filter",))
-                    .set_user(user_id)
-                    .add_field(
-                        Field::new(
-                            InputTextStyle::Short,
-                            "added_payload_filter",
-                            "New payload filter",
-                        )
-                        .set_max_length(20)
-                        .set_min_length(3)
-                        .set_placeholder("Put in a word or regex to filter out payloads")
-                        .set_required(),
-                    )
-                    .build()
-                    .unwrap()
-                    .listen(
-                        http,
-                        &Interaction::Component(button_click),
-                        data,
-                    )
-                    .await;
-                })
-            },
+            route("add-payload-filter", id),
         );
 
         if !payload_filters.is_empty() {
-            let remove_ses = ses.clone();
-            let payload_filters_clone = payload_filters.clone();
-            em.add_option(
+            em.add_route(
                 &ButtonType {
                     label: "Remove payload filter".to_owned(),
                     style: ButtonStyle::Primary,
                     emoji: Some(RETROGRADE.clone()),
                 },
-                move |button_click| {
-                    let remove_ses = remove_ses.clone();
-                    let payload_filters_clone = payload_filters_clone.clone();
-                    Box::pin(async move {
-                        let inner_ses = remove_ses.clone();
-                        let wait_ses = remove_ses.clone();
-
-                        let (user_id, http, data) = {
-                            let s = inner_ses
-                                .read()
-                                .await;
-                            (
-                                s.author,
-                                s.http
-                                    .clone(),
-                                s.data
-                                    .clone(),
-                            )
-                        };
-
-                        SelectMenu::builder(move |(choice, _)| {
-                            let wait_ses = wait_ses.clone();
-                            Box::pin(async move {
-                                remove_filter(
-                                    &wait_ses.clone(),
-                                    id,
-                                    choice,
-                                    "payload_filters",
-                                )
-                                .await;
-                                payload_filters_page(wait_ses.clone(), id).await
-                            })
-                        })
-                        .set_description("Select payload filter you want to remove")
-                        .set_custom_id(&format!(
-                            "{user_id}-remove-payload-filter",
-                        ))
-                        .set_user(user_id)
-                        .make_ephemeral()
-                        .set_options(
-                            payload_filters_clone
-                                .iter()
-                                .map(|r| {
-                                    (
-                                        r.as_str()
-                                            .to_owned(),
-                                        regex_filter_to_string(r),
-                                    )
-                                })
-                                .collect(),
-                        )
-                        .build()
-                        .unwrap()
-                        .listen(
-                            http,
-                            &Interaction::Component(button_click),
-                            data,
-                        )
-                        .await;
-                    })
-                },
+                route("remove-payload-filter", id),
             );
         }
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Back to the filters page".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { filters_page(ses.clone(), id).await })
-            },
+            route("filters", id),
         );
 
         let result = em
@@ -1054,56 +1159,175 @@ fn payload_filters_page(
     })
 }
 
-pub fn mentions_page(
+pub async fn add_payload_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    Modal::builder(move |modal_submit, inputs| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            // the button click was answered with the modal, so only the submit can edit the
+            // menu
+            wait_ses
+                .write()
+                .await
+                .set_interaction(SessionInteraction::Modal(modal_submit));
+
+            if !inputs.is_empty() {
+                add_filter(
+                    &wait_ses.clone(),
+                    id,
+                    filter_from_string_input(
+                        inputs
+                            .first()
+                            .expect("modal did not return an input value")
+                            .clone()
+                            .1,
+                    ),
+                    "payload_filters",
+                )
+                .await;
+            }
+            payload_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_title("Payload filter modal")
+    .set_custom_id(&format!("{user_id}-add-payload-filter",))
+    .set_user(user_id)
+    .add_field(
+        Field::new(
+            InputTextStyle::Short,
+            "added_payload_filter",
+            "New payload filter",
+        )
+        .set_max_length(20)
+        .set_min_length(3)
+        .set_placeholder("Put in a word or regex to filter out payloads")
+        .set_required()
+        .add_validator(Validator::Regex),
+    )
+    .build()
+    .unwrap()
+    .listen(
+        http,
+        &Interaction::Component(button_click),
+        data,
+    )
+    .await;
+}
+
+pub async fn remove_payload_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let payload_filters: Vec<Regex> = settings_list(&ses, id, |s| {
+        s.get_payload_filters()
+            .clone()
+    })
+    .await;
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            remove_filter(
+                &wait_ses.clone(),
+                id,
+                choice,
+                "payload_filters",
+            )
+            .await;
+            payload_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select payload filter you want to remove")
+    .set_custom_id(&format!(
+        "{user_id}-remove-payload-filter",
+    ))
+    .set_user(user_id)
+    .make_ephemeral()
+    .set_options(
+        payload_filters
+            .iter()
+            .map(|r| {
+                (
+                    r.as_str()
+                        .to_owned(),
+                    regex_filter_to_string(r),
+                )
+            })
+            .collect(),
+    )
+    .build();
+    listen_menu(
+        menu,
+        "There are no payload filters to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+/// The types of the upcoming space events that reminders could be sent for.
+async fn event_types(ses: &Arc<RwLock<EmbedSession>>) -> Vec<String> {
+    let data = ses
+        .read()
+        .await
+        .data
+        .clone();
+    let Some(cache) = data
+        .read()
+        .await
+        .get::<EventsCacheKey>()
+        .cloned()
+    else {
+        return Vec::new();
+    };
+
+    let types = cache
+        .read()
+        .await
+        .iter()
+        .map(|e| {
+            e.event_type
+                .clone()
+        })
+        .sorted()
+        .dedup()
+        .collect();
+    types
+}
+
+pub fn event_filters_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
 ) -> futures::future::BoxFuture<'static, ()> {
     Box::pin(async move {
-        let Some(db) = get_db(&ses).await else {
-            return;
-        };
-
-        let (description, mentions) = match id {
-            ID::Channel((_, guild_id)) => {
-                let settings_res = get_guild_settings(&db, guild_id.into()).await;
-                match settings_res {
-                    Ok(settings)
-                        if !settings
-                            .mentions
-                            .is_empty() =>
-                    {
-                        let mut text =
-                            "The following roles have been set to be mentioned:".to_owned();
-                        for role_id in &settings.mentions {
-                            let role_opt = role_id.to_role_cached(
-                                ses.read()
-                                    .await
-                                    .cache
-                                    .clone(),
-                            );
-                            if let Some(role) = role_opt {
-                                write!(text, "\n`{}`", role.name)
-                                    .expect("write to String: can't fail");
-                            } else {
-                                remove_mention(&ses, id, *role_id).await
-                            }
-                        }
-                        (
-                            text,
-                            settings
-                                .mentions
-                                .clone(),
-                        )
-                    },
-                    _ => {
-                        (
-                            "No role mentions have been set yet".to_owned(),
-                            Vec::new(),
-                        )
-                    },
-                }
-            },
-            ID::User(_) => return,
+        let event_filters: Vec<String> = settings_list(&ses, id, |s| {
+            s.get_event_filters()
+                .clone()
+        })
+        .await;
+        let addable = event_types(&ses)
+            .await
+            .into_iter()
+            .filter(|t| !event_filters.contains(t))
+            .count();
+
+        let description = if event_filters.is_empty() {
+            "No event types have been filtered out yet, so reminders are sent for all space events"
+                .to_owned()
+        } else {
+            let mut text = "No reminders are sent for the following types of events:".to_owned();
+            for filter in &event_filters {
+                write!(text, "\n`{filter}`").expect("write to String: can't fail");
+            }
+            text
         };
 
         let mut em = StatefulEmbed::new_with_embed(
@@ -1111,112 +1335,39 @@ pub fn mentions_page(
             CreateEmbed::new()
                 .color(DEFAULT_COLOR)
                 .timestamp(Utc::now())
-                .author(CreateEmbedAuthor::new("Role Mentions").icon_url(DEFAULT_ICON))
+                .author(CreateEmbedAuthor::new("Event Type Filters").icon_url(DEFAULT_ICON))
                 .description(description),
         );
 
-        let add_ses = ses.clone();
-        let mentions_clone = mentions.clone();
-        em.add_option(
-            &ButtonType {
-                label: "Add mention".to_owned(),
-                style: ButtonStyle::Primary,
-                emoji: Some(PROGRADE.clone()),
-            },
-            move |button_click| {
-                let add_ses = add_ses.clone();
-                let mentions_clone = mentions_clone.clone();
-                Box::pin(async move {
-                    let (user_id, http, data) = {
-                        let s = add_ses
-                            .read()
-                            .await;
-                        (
-                            s.author,
-                            s.http
-                                .clone(),
-                            s.data
-                                .clone(),
-                        )
-                    };
-
-                    role_select_menu(
-                        http,
-                        user_id,
-                        &Interaction::Component(button_click),
-                        data,
-                        Some(mentions_clone),
-                        None,
-                        move |role_id| {
-                            let wait_ses = add_ses.clone();
-                            Box::pin(async move {
-                                add_mention(&wait_ses.clone(), id, role_id).await;
-                                mentions_page(wait_ses.clone(), id).await;
-                            })
-                        },
-                    )
-                    .await;
-                })
-            },
-        );
+        if addable > 0 {
+            em.add_route(
+                &ButtonType {
+                    label: "Add event filter".to_owned(),
+                    style: ButtonStyle::Primary,
+                    emoji: Some(PROGRADE.clone()),
+                },
+                route("add-event-filter", id),
+            );
+        }
 
-        if !mentions.is_empty() {
-            let remove_ses = ses.clone();
-            let mentions_clone = mentions.clone();
-            em.add_option(
+        if !event_filters.is_empty() {
+            em.add_route(
                 &ButtonType {
-                    label: "Remove mention".to_owned(),
+                    label: "Remove event filter".to_owned(),
                     style: ButtonStyle::Primary,
                     emoji: Some(RETROGRADE.clone()),
                 },
-                move |button_click| {
-                    let remove_ses = remove_ses.clone();
-                    let mentions_clone = mentions_clone.clone();
-                    Box::pin(async move {
-                        let (user_id, http, data) = {
-                            let s = remove_ses
-                                .read()
-                                .await;
-                            (
-                                s.author,
-                                s.http
-                                    .clone(),
-                                s.data
-                                    .clone(),
-                            )
-                        };
-
-                        role_select_menu(
-                            http,
-                            user_id,
-                            &Interaction::Component(button_click),
-                            data,
-                            None,
-                            Some(mentions_clone),
-                            move |role_id| {
-                                let wait_ses = remove_ses.clone();
-                                Box::pin(async move {
-                                    remove_mention(&wait_ses.clone(), id, role_id).await;
-                                    mentions_page(wait_ses.clone(), id).await;
-                                })
-                            },
-                        )
-                        .await;
-                    })
-                },
+                route("remove-event-filter", id),
             );
         }
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
-                label: "Back to main menu".to_owned(),
+                label: "Back to the filters page".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { main_menu(ses.clone(), id).await })
-            },
+            route("filters", id),
         );
 
         let result = em
@@ -1228,25 +1379,1467 @@ pub fn mentions_page(
     })
 }
 
-pub fn other_page(
+pub async fn add_event_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let event_filters = settings_list(&ses, id, |s| {
+        s.get_event_filters()
+            .clone()
+    })
+    .await;
+    let types = event_types(&ses).await;
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            add_filter(
+                &wait_ses.clone(),
+                id,
+                choice,
+                "event_filters",
+            )
+            .await;
+            event_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the type of event you do not want to receive reminders for")
+    .set_custom_id(&format!("{user_id}-add-event-filter"))
+    .set_user(user_id)
+    .set_options(
+        types
+            .into_iter()
+            .filter(|t| !event_filters.contains(t))
+            .map(|t| (t.clone(), t))
+            .collect(),
+    )
+    .build();
+    listen_menu(
+        menu,
+        "There are no event types to filter out yet",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub async fn remove_event_filter_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let event_filters = settings_list(&ses, id, |s| {
+        s.get_event_filters()
+            .clone()
+    })
+    .await;
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            remove_filter(
+                &wait_ses.clone(),
+                id,
+                choice,
+                "event_filters",
+            )
+            .await;
+            event_filters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the type of event you want to receive reminders for again")
+    .set_custom_id(&format!(
+        "{user_id}-remove-event-filter"
+    ))
+    .set_user(user_id)
+    .set_options(
+        event_filters
+            .into_iter()
+            .map(|t| (t.clone(), t))
+            .collect(),
+    )
+    .build();
+    listen_menu(
+        menu,
+        "There are no event filters to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub fn boosters_page(
     ses: Arc<RwLock<EmbedSession>>,
     id: ID,
 ) -> futures::future::BoxFuture<'static, ()> {
     Box::pin(async move {
-        let Some(db) = get_db(&ses).await else {
-            return;
+        let boosters: Vec<String> = settings_list(&ses, id, |s| {
+            s.get_booster_subscriptions()
+                .clone()
+        })
+        .await;
+
+        let description = if boosters.is_empty() {
+            "No boosters are being followed yet, follow one by its serial to get reminders for every launch it gets assigned to, even if the launch is filtered out, and to hear how its landing went".to_owned()
+        } else {
+            let mut text = "The following boosters are being followed:".to_owned();
+            for serial in &boosters {
+                write!(text, "\n`{serial}`").expect("write to String: can't fail");
+            }
+            text
         };
 
-        let mut scrub_notifications = State::Off;
-        let mut outcome_notifications = State::Off;
-        let mut mentions = State::Off;
-        let mut description = String::new();
+        let mut em = StatefulEmbed::new_with_embed(
+            ses.clone(),
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .timestamp(Utc::now())
+                .author(CreateEmbedAuthor::new("Followed Boosters").icon_url(DEFAULT_ICON))
+                .description(description),
+        );
 
-        match id {
-            ID::Channel((_, guild_id)) => {
-                let settings_res = get_guild_settings(&db, guild_id.into()).await;
-                if let Ok(settings) = settings_res {
-                    if settings.scrub_notifications {
+        em.add_route(
+            &ButtonType {
+                label: "Follow booster".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some(PROGRADE.clone()),
+            },
+            route("add-booster", id),
+        );
+
+        if !boosters.is_empty() {
+            em.add_route(
+                &ButtonType {
+                    label: "Unfollow booster".to_owned(),
+                    style: ButtonStyle::Primary,
+                    emoji: Some(RETROGRADE.clone()),
+                },
+                route("remove-booster", id),
+            );
+        }
+
+        em.add_route(
+            &ButtonType {
+                label: "Back to main menu".to_owned(),
+                style: ButtonStyle::Danger,
+                emoji: Some(ReactionType::from(BACK_EMOJI)),
+            },
+            route("main", id),
+        );
+
+        let result = em
+            .show()
+            .await;
+        if let Err(err) = result {
+            dbg!(err);
+        }
+    })
+}
+
+pub async fn add_booster_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    Modal::builder(move |modal_submit, inputs| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            // the button click was answered with the modal, so only the submit can edit the
+            // menu
+            wait_ses
+                .write()
+                .await
+                .set_interaction(SessionInteraction::Modal(modal_submit));
+
+            if let Some((_, serial)) = inputs.first() {
+                add_filter(
+                    &wait_ses.clone(),
+                    id,
+                    serial
+                        .trim()
+                        .to_uppercase(),
+                    "booster_subscriptions",
+                )
+                .await;
+            }
+            boosters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_title("Follow a booster")
+    .set_custom_id(&format!("{user_id}-add-booster"))
+    .set_user(user_id)
+    .add_field(
+        Field::new(
+            InputTextStyle::Short,
+            "booster_serial",
+            "Booster serial",
+        )
+        .set_max_length(20)
+        .set_min_length(2)
+        .set_placeholder("For example B1058")
+        .set_required()
+        .add_validator(Validator::custom(check_booster_serial)),
+    )
+    .build()
+    .unwrap()
+    .listen(
+        http,
+        &Interaction::Component(button_click),
+        data,
+    )
+    .await;
+}
+
+/// Check that a booster serial looks like the ones launch library uses.
+fn check_booster_serial(input: &str) -> Result<(), String> {
+    let serial = input.trim();
+
+    if serial.is_empty()
+        || !serial
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+    {
+        return Err(
+            "a booster serial can only have letters, numbers, dashes and dots, like B1058"
+                .to_owned(),
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn remove_booster_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let boosters: Vec<String> = settings_list(&ses, id, |s| {
+        s.get_booster_subscriptions()
+            .clone()
+    })
+    .await;
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            remove_filter(
+                &wait_ses.clone(),
+                id,
+                choice,
+                "booster_subscriptions",
+            )
+            .await;
+            boosters_page(wait_ses.clone(), id).await
+        })
+    })
+    .set_description("Select the booster you want to unfollow")
+    .set_custom_id(&format!("{user_id}-remove-booster"))
+    .set_user(user_id)
+    .make_ephemeral()
+    .set_options(
+        boosters
+            .into_iter()
+            .map(|serial| (serial.clone(), serial))
+            .collect(),
+    )
+    .build();
+    listen_menu(
+        menu,
+        "You don't follow any boosters",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+/// What the roles of a mention rule get mentioned for.
+fn describe_mention_rule(rule: &MentionRule) -> String {
+    match (rule.kind, rule.minutes) {
+        (MentionKind::Reminder, Some(minutes)) => {
+            format!(
+                "the {} reminder",
+                format_duration(Duration::minutes(minutes), false)
+            )
+        },
+        (MentionKind::Reminder, None) => "every reminder".to_owned(),
+        (MentionKind::Scrub, _) => "scrubs".to_owned(),
+        (MentionKind::Outcome, _) => "outcomes".to_owned(),
+    }
+}
+
+/// The agency or vehicle the filter of a launch mention is for.
+fn describe_launch_mention(catalog: &Catalog, kind: LaunchMentionKind, filter: &str) -> String {
+    match kind {
+        LaunchMentionKind::Agency => {
+            catalog
+                .agency_name(filter)
+                .unwrap_or_else(|| filter.to_owned())
+        },
+        LaunchMentionKind::Vehicle => filter.to_owned(),
+    }
+}
+
+pub fn mentions_page(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+) -> futures::future::BoxFuture<'static, ()> {
+    Box::pin(async move {
+        let Some(db) = get_db(&ses).await else {
+            return;
+        };
+
+        let ID::Channel((_, guild_id)) = id else {
+            return;
+        };
+
+        let (mentions, rules, launch_mentions, launch_mentions_only) =
+            get_guild_settings(&db, guild_id.into())
+                .await
+                .map(|settings| {
+                    (
+                        settings.mentions,
+                        settings.mention_rules,
+                        settings.launch_mentions,
+                        settings.launch_mentions_only,
+                    )
+                })
+                .unwrap_or_default();
+        let cache = ses
+            .read()
+            .await
+            .cache
+            .clone();
+
+        let mut description = if mentions.is_empty() {
+            "No roles have been set to be mentioned for every reminder".to_owned()
+        } else {
+            "The following roles have been set to be mentioned for every reminder:".to_owned()
+        };
+        for role_id in &mentions {
+            if let Some(role) = role_id.to_role_cached(&cache) {
+                write!(description, "\n`{}`", role.name).expect("write to String: can't fail");
+            } else {
+                remove_mention(&ses, id, *role_id).await
+            }
+        }
+
+        if !rules.is_empty() {
+            description.push_str("\n\nThese roles only get mentioned for some messages:");
+        }
+        for rule in &rules {
+            if let Some(role) = rule
+                .role
+                .to_role_cached(&cache)
+            {
+                write!(
+                    description,
+                    "\n`{}` for {}",
+                    role.name,
+                    describe_mention_rule(rule)
+                )
+                .expect("write to String: can't fail");
+            } else {
+                remove_mention_rule(&ses, id, rule).await
+            }
+        }
+
+        let catalog = session_catalog(&ses).await;
+        if !launch_mentions.is_empty() {
+            description
+                .push_str("\n\nThese roles get mentioned for the reminders of some launches:");
+        }
+        for mention in &launch_mentions {
+            if let Some(role) = mention
+                .role
+                .to_role_cached(&cache)
+            {
+                write!(
+                    description,
+                    "\n`{}` for {} launches",
+                    role.name,
+                    describe_launch_mention(&catalog, mention.kind, &mention.filter)
+                )
+                .expect("write to String: can't fail");
+            } else {
+                remove_launch_mention(&ses, id, mention).await
+            }
+        }
+
+        let mut em = StatefulEmbed::new_with_embed(
+            ses.clone(),
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .timestamp(Utc::now())
+                .author(CreateEmbedAuthor::new("Role Mentions").icon_url(DEFAULT_ICON))
+                .description(description),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Add mention".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some(PROGRADE.clone()),
+            },
+            route("add-mention", id),
+        );
+
+        if !mentions.is_empty() {
+            em.add_route(
+                &ButtonType {
+                    label: "Remove mention".to_owned(),
+                    style: ButtonStyle::Primary,
+                    emoji: Some(RETROGRADE.clone()),
+                },
+                route("remove-mention", id),
+            );
+        }
+
+        em.add_route(
+            &ButtonType {
+                label: "Mention for specific messages".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some('🎯'.into()),
+            },
+            route("mention-rules", id),
+        );
+
+        if !rules.is_empty() {
+            em.add_route(
+                &ButtonType {
+                    label: "Remove mention rule".to_owned(),
+                    style: ButtonStyle::Primary,
+                    emoji: Some(RETROGRADE.clone()),
+                },
+                route("remove-mention-rule", id),
+            );
+        }
+
+        em.add_route(
+            &ButtonType {
+                label: "Mention for an agency".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some('🏢'.into()),
+            },
+            route("launch-mention", id).arg(LaunchMentionKind::Agency.as_str()),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Mention for a vehicle".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some('🚀'.into()),
+            },
+            route("launch-mention", id).arg(LaunchMentionKind::Vehicle.as_str()),
+        );
+
+        if !launch_mentions.is_empty() {
+            em.add_route(
+                &ButtonType {
+                    label: "Remove launch mention".to_owned(),
+                    style: ButtonStyle::Primary,
+                    emoji: Some(RETROGRADE.clone()),
+                },
+                route("remove-launch-mention", id),
+            );
+
+            em.add_route(
+                &ButtonType {
+                    label: if launch_mentions_only {
+                        "Also mention the other roles".to_owned()
+                    } else {
+                        "Only mention the launch roles".to_owned()
+                    },
+                    style: ButtonStyle::Secondary,
+                    emoji: Some('🔔'.into()),
+                },
+                route("toggle", id)
+                    .arg("launch_mentions_only")
+                    .arg(!launch_mentions_only),
+            );
+        }
+
+        em.add_route(
+            &ButtonType {
+                label: "Back to main menu".to_owned(),
+                style: ButtonStyle::Danger,
+                emoji: Some(ReactionType::from(BACK_EMOJI)),
+            },
+            route("main", id),
+        );
+
+        let result = em
+            .show()
+            .await;
+        if let Err(err) = result {
+            dbg!(err);
+        }
+    })
+}
+
+/// Pick which messages new mention rules are for, every reminder that has
+/// been set gets a button of its own.
+pub fn mention_rules_page(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+) -> futures::future::BoxFuture<'static, ()> {
+    Box::pin(async move {
+        let reminders = get_reminders(&ses, id)
+            .await
+            .unwrap_or_default();
+
+        let mut em = StatefulEmbed::new_with_embed(
+            ses.clone(),
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .timestamp(Utc::now())
+                .author(CreateEmbedAuthor::new("Mention Rules").icon_url(DEFAULT_ICON))
+                .description(
+                    "Select the messages to mention roles for, after that you can pick the roles",
+                ),
+        );
+
+        // discord allows 25 buttons, the other ones take up 4 of those
+        for reminder in reminders
+            .iter()
+            .take(20)
+        {
+            em.add_route(
+                &ButtonType {
+                    label: format!(
+                        "{} reminder",
+                        format_duration(reminder.get_duration(), false)
+                    ),
+                    style: ButtonStyle::Secondary,
+                    emoji: None,
+                },
+                route("add-mention-rule", id)
+                    .arg(MentionKind::Reminder.as_str())
+                    .arg(reminder.minutes),
+            );
+        }
+
+        for (kind, label) in [
+            (MentionKind::Reminder, "Every reminder"),
+            (MentionKind::Scrub, "Scrubs"),
+            (MentionKind::Outcome, "Outcomes"),
+        ] {
+            em.add_route(
+                &ButtonType {
+                    label: label.to_owned(),
+                    style: ButtonStyle::Primary,
+                    emoji: None,
+                },
+                route("add-mention-rule", id)
+                    .arg(kind.as_str())
+                    .arg("all"),
+            );
+        }
+
+        em.add_route(
+            &ButtonType {
+                label: "Back to mentions".to_owned(),
+                style: ButtonStyle::Danger,
+                emoji: Some(ReactionType::from(BACK_EMOJI)),
+            },
+            route("mentions", id),
+        );
+
+        let result = em
+            .show()
+            .await;
+        if let Err(err) = result {
+            dbg!(err);
+        }
+    })
+}
+
+pub async fn add_mention_rule_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+    kind: MentionKind,
+    minutes: Option<i64>,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    role_select_menu(
+        http,
+        user_id,
+        &Interaction::Component(button_click),
+        data,
+        25,
+        move |role_ids| {
+            let wait_ses = ses.clone();
+            Box::pin(async move {
+                for role in role_ids {
+                    let rule = MentionRule {
+                        role,
+                        kind,
+                        minutes,
+                    };
+                    add_mention_rule(&wait_ses, id, &rule).await;
+                }
+                mentions_page(wait_ses.clone(), id).await;
+            })
+        },
+    )
+    .await;
+}
+
+pub async fn remove_mention_rule_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+    let Some(db) = get_db(&ses).await else {
+        return;
+    };
+
+    let rules = get_guild_settings(&db, guild_id.into())
+        .await
+        .map(|settings| settings.mention_rules)
+        .unwrap_or_default();
+    let cache = ses
+        .read()
+        .await
+        .cache
+        .clone();
+    let options = rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            let role = rule
+                .role
+                .to_role_cached(&cache)
+                .map_or_else(
+                    || {
+                        rule.role
+                            .to_string()
+                    },
+                    |role| role.name,
+                );
+            (
+                i.to_string(),
+                format!(
+                    "{role} for {}",
+                    describe_mention_rule(rule)
+                ),
+            )
+        })
+        .collect();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        let rule = choice
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| rules.get(i))
+            .cloned();
+        Box::pin(async move {
+            if let Some(rule) = rule {
+                remove_mention_rule(&wait_ses, id, &rule).await;
+            }
+            mentions_page(wait_ses.clone(), id).await;
+        })
+    })
+    .set_description("Select the mention rule you want to remove")
+    .set_custom_id(&format!(
+        "{user_id}-remove-mention-rule"
+    ))
+    .set_user(user_id)
+    .set_options(options)
+    .build();
+    listen_menu(
+        menu,
+        "There are no mention rules to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+pub async fn launch_mention_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+    kind: LaunchMentionKind,
+) {
+    let catalog = session_catalog(&ses).await;
+    let (description, options) = match kind {
+        LaunchMentionKind::Agency => {
+            (
+                "Select the agency to mention roles for",
+                catalog.agency_filters(),
+            )
+        },
+        LaunchMentionKind::Vehicle => {
+            (
+                "Select the vehicle to mention roles for",
+                catalog
+                    .vehicle_filters()
+                    .into_iter()
+                    .map(|v| (v.clone(), v))
+                    .collect(),
+            )
+        },
+    };
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            launch_mention_roles_page(wait_ses.clone(), id, kind, choice).await;
+        })
+    })
+    .set_description(description)
+    .set_custom_id(&format!("{user_id}-launch-mention"))
+    .set_user(user_id)
+    .set_options(
+        options
+            .into_iter()
+            // the filter has to fit in the custom_id of the button to pick the roles with
+            .filter(|(filter, _)| {
+                route("add-launch-mention", id)
+                    .arg(kind.as_str())
+                    .arg(filter)
+                    .to_string()
+                    .len()
+                    <= ComponentId::MAX_LENGTH
+            })
+            .collect(),
+    )
+    .build();
+    listen_menu(
+        menu,
+        "There are no agencies or vehicles to pick from",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+/// Roles can only be picked from a click, so a chosen agency or vehicle gets
+/// a button to pick the roles with.
+pub fn launch_mention_roles_page(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    kind: LaunchMentionKind,
+    filter: String,
+) -> futures::future::BoxFuture<'static, ()> {
+    Box::pin(async move {
+        let target = describe_launch_mention(
+            &session_catalog(&ses).await,
+            kind,
+            &filter,
+        );
+
+        let mut em = StatefulEmbed::new_with_embed(
+            ses.clone(),
+            CreateEmbed::new()
+                .color(DEFAULT_COLOR)
+                .timestamp(Utc::now())
+                .author(CreateEmbedAuthor::new("Launch Mentions").icon_url(DEFAULT_ICON))
+                .description(format!(
+                    "Pick the roles to mention for {target} launches"
+                )),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Pick roles".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some(PROGRADE.clone()),
+            },
+            route("add-launch-mention", id)
+                .arg(kind.as_str())
+                .arg(&filter),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Back to mentions".to_owned(),
+                style: ButtonStyle::Danger,
+                emoji: Some(ReactionType::from(BACK_EMOJI)),
+            },
+            route("mentions", id),
+        );
+
+        let result = em
+            .show()
+            .await;
+        if let Err(err) = result {
+            dbg!(err);
+        }
+    })
+}
+
+pub async fn add_launch_mention_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+    kind: LaunchMentionKind,
+    filter: String,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    role_select_menu(
+        http,
+        user_id,
+        &Interaction::Component(button_click),
+        data,
+        25,
+        move |role_ids| {
+            let wait_ses = ses.clone();
+            let filter = filter.clone();
+            Box::pin(async move {
+                for role in role_ids {
+                    let mention = LaunchMention {
+                        role,
+                        kind,
+                        filter: filter.clone(),
+                    };
+                    add_launch_mention(&wait_ses, id, &mention).await;
+                }
+                mentions_page(wait_ses.clone(), id).await;
+            })
+        },
+    )
+    .await;
+}
+
+pub async fn remove_launch_mention_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+    let Some(db) = get_db(&ses).await else {
+        return;
+    };
+
+    let mentions = get_guild_settings(&db, guild_id.into())
+        .await
+        .map(|settings| settings.launch_mentions)
+        .unwrap_or_default();
+    let cache = ses
+        .read()
+        .await
+        .cache
+        .clone();
+    let catalog = session_catalog(&ses).await;
+    let options = mentions
+        .iter()
+        .enumerate()
+        .map(|(i, mention)| {
+            let role = mention
+                .role
+                .to_role_cached(&cache)
+                .map_or_else(
+                    || {
+                        mention
+                            .role
+                            .to_string()
+                    },
+                    |role| role.name,
+                );
+            (
+                i.to_string(),
+                format!(
+                    "{role} for {} launches",
+                    describe_launch_mention(&catalog, mention.kind, &mention.filter)
+                ),
+            )
+        })
+        .collect();
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let menu = SelectMenu::builder(move |(choice, _)| {
+        let wait_ses = ses.clone();
+        let mention = choice
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| mentions.get(i))
+            .cloned();
+        Box::pin(async move {
+            if let Some(mention) = mention {
+                remove_launch_mention(&wait_ses, id, &mention).await;
+            }
+            mentions_page(wait_ses.clone(), id).await;
+        })
+    })
+    .set_description("Select the launch mention you want to remove")
+    .set_custom_id(&format!(
+        "{user_id}-remove-launch-mention"
+    ))
+    .set_user(user_id)
+    .set_options(options)
+    .build();
+    listen_menu(
+        menu,
+        "There are no launch mentions to remove",
+        http,
+        button_click,
+        data,
+    )
+    .await;
+}
+
+async fn get_mentions(ses: &Arc<RwLock<EmbedSession>>, id: ID) -> Vec<RoleId> {
+    let ID::Channel((_, guild_id)) = id else {
+        return Vec::new();
+    };
+    let Some(db) = get_db(ses).await else {
+        return Vec::new();
+    };
+
+    get_guild_settings(&db, guild_id.into())
+        .await
+        .map(|settings| settings.mentions)
+        .unwrap_or_default()
+}
+
+pub async fn add_mention_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    role_select_menu(
+        http,
+        user_id,
+        &Interaction::Component(button_click),
+        data,
+        25,
+        move |role_ids| {
+            let wait_ses = ses.clone();
+            Box::pin(async move {
+                let mentions = get_mentions(&wait_ses, id).await;
+                for role_id in role_ids {
+                    if !mentions.contains(&role_id) {
+                        add_mention(&wait_ses.clone(), id, role_id).await;
+                    }
+                }
+                mentions_page(wait_ses.clone(), id).await;
+            })
+        },
+    )
+    .await;
+}
+
+pub async fn remove_mention_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let mentions = get_mentions(&ses, id).await;
+    let (user_id, http, data) = session_parts(&ses).await;
+    if mentions.is_empty() {
+        tell_nothing_to_pick(
+            &http,
+            &button_click,
+            "There are no roles to stop mentioning",
+        )
+        .await;
+        return;
+    }
+
+    role_choice_menu(
+        http,
+        user_id,
+        &Interaction::Component(button_click),
+        data,
+        mentions,
+        move |role_id| {
+            let wait_ses = ses.clone();
+            Box::pin(async move {
+                remove_mention(&wait_ses.clone(), id, role_id).await;
+                mentions_page(wait_ses.clone(), id).await;
+            })
+        },
+    )
+    .await;
+}
+
+/// The kinds of messages that can get a template, with how they are called in
+/// the menu.
+const TEMPLATE_KINDS: [(MentionKind, &str); 3] = [
+    (MentionKind::Reminder, "Reminders"),
+    (MentionKind::Scrub, "Scrubs"),
+    (MentionKind::Outcome, "Outcomes"),
+];
+
+/// Show a part of a template without letting a long one take over the menu.
+fn template_part(part: Option<&String>) -> String {
+    part.map_or_else(
+        || "standard".to_owned(),
+        |text| {
+            let mut short: String = text
+                .chars()
+                .take(100)
+                .collect();
+            if short.len() < text.len() {
+                short.push('…');
+            }
+            format!("`{short}`")
+        },
+    )
+}
+
+fn parse_color(input: &str) -> Result<u32, String> {
+    let hex = input
+        .trim()
+        .trim_start_matches('#');
+
+    if hex.len() != 6 {
+        return Err("colors have to be written like #1f8b4c".to_owned());
+    }
+
+    u32::from_str_radix(hex, 16).map_err(|_| format!("`{input}` is not a hex color"))
+}
+
+pub fn templates_page(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+) -> futures::future::BoxFuture<'static, ()> {
+    Box::pin(async move {
+        let Some(db) = get_db(&ses).await else {
+            return;
+        };
+
+        let ID::Channel((_, guild_id)) = id else {
+            return;
+        };
+
+        let (templates, color, hide_thumbnail) = get_guild_settings(&db, guild_id.into())
+            .await
+            .map(|settings| {
+                (
+                    settings.templates,
+                    settings.embed_color,
+                    settings.hide_thumbnail,
+                )
+            })
+            .unwrap_or_default();
+
+        let mut description = format!(
+            "The text, title and description of the messages can be changed, using the placeholders {}",
+            PLACEHOLDERS
+                .iter()
+                .map(|name| format!("`{{{name}}}`"))
+                .join(", ")
+        );
+        for (kind, name) in TEMPLATE_KINDS {
+            let template = templates
+                .iter()
+                .find(|t| t.kind == kind);
+            write!(
+                description,
+                "\n\n**{name}**\nText: {}\nTitle: {}\nDescription: {}",
+                template_part(template.and_then(|t| {
+                    t.content
+                        .as_ref()
+                })),
+                template_part(template.and_then(|t| {
+                    t.title
+                        .as_ref()
+                })),
+                template_part(template.and_then(|t| {
+                    t.description
+                        .as_ref()
+                })),
+            )
+            .expect("write to String: can't fail");
+        }
+        write!(
+            description,
+            "\n\n**Embed color:** {}\n**Rocket image:** {}",
+            color.map_or_else(
+                || "standard".to_owned(),
+                |c| format!("#{c:06x}")
+            ),
+            if hide_thumbnail { "hidden" } else { "shown" },
+        )
+        .expect("write to String: can't fail");
+
+        let mut em = StatefulEmbed::new_with_embed(
+            ses.clone(),
+            CreateEmbed::new()
+                .color(color.unwrap_or(DEFAULT_COLOR))
+                .timestamp(Utc::now())
+                .author(CreateEmbedAuthor::new("Message Style").icon_url(DEFAULT_ICON))
+                .description(description),
+        );
+
+        for (kind, name) in TEMPLATE_KINDS {
+            em.add_route(
+                &ButtonType {
+                    label: format!("Edit {}", name.to_lowercase()),
+                    style: ButtonStyle::Primary,
+                    emoji: Some('✏'.into()),
+                },
+                route("edit-template", id).arg(kind.as_str()),
+            );
+        }
+
+        em.add_route(
+            &ButtonType {
+                label: "Set color".to_owned(),
+                style: ButtonStyle::Primary,
+                emoji: Some('🎨'.into()),
+            },
+            route("template-color", id),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: if hide_thumbnail {
+                    "Show rocket image".to_owned()
+                } else {
+                    "Hide rocket image".to_owned()
+                },
+                style: ButtonStyle::Primary,
+                emoji: Some('🖼'.into()),
+            },
+            route("toggle", id)
+                .arg("hide_thumbnail")
+                .arg(!hide_thumbnail),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Preview".to_owned(),
+                style: ButtonStyle::Secondary,
+                emoji: Some('👀'.into()),
+            },
+            route("preview-templates", id),
+        );
+
+        em.add_route(
+            &ButtonType {
+                label: "Back to main menu".to_owned(),
+                style: ButtonStyle::Danger,
+                emoji: Some(ReactionType::from(BACK_EMOJI)),
+            },
+            route("main", id),
+        );
+
+        let result = em
+            .show()
+            .await;
+        if let Err(err) = result {
+            dbg!(err);
+        }
+    })
+}
+
+pub async fn edit_template_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+    kind: MentionKind,
+) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+    let Some(db) = get_db(&ses).await else {
+        return;
+    };
+    let current = get_guild_settings(&db, guild_id.into())
+        .await
+        .ok()
+        .and_then(|settings| {
+            settings
+                .templates
+                .into_iter()
+                .find(|t| t.kind == kind)
+        });
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    let field = |custom_id: &str, label: &str, max_length: u16, value: Option<&String>| {
+        let mut field = Field::new(
+            InputTextStyle::Paragraph,
+            custom_id,
+            label,
+        )
+        .set_optional()
+        .set_max_length(max_length)
+        .set_placeholder("Leave empty for the standard wording")
+        .add_validator(Validator::custom(check_template));
+        if let Some(value) = value {
+            field = field.set_value(value);
+        }
+        field
+    };
+
+    Modal::builder(move |modal_submit, inputs| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            // the button click was answered with the modal, so only the submit can edit the
+            // menu
+            wait_ses
+                .write()
+                .await
+                .set_interaction(SessionInteraction::Modal(modal_submit));
+
+            let part = |name: &str| {
+                inputs
+                    .iter()
+                    .find(|(custom_id, _)| custom_id == name)
+                    .map(|(_, value)| value.trim())
+                    .filter(|value| !value.is_empty())
+                    .map(ToOwned::to_owned)
+            };
+            let template = MessageTemplate {
+                kind,
+                content: part("content"),
+                title: part("title"),
+                description: part("description"),
+            };
+            let is_empty = template
+                .content
+                .is_none()
+                && template
+                    .title
+                    .is_none()
+                && template
+                    .description
+                    .is_none();
+
+            set_template(
+                &wait_ses,
+                id,
+                kind,
+                (!is_empty).then_some(template),
+            )
+            .await;
+            templates_page(wait_ses.clone(), id).await;
+        })
+    })
+    .set_title("Change the wording")
+    .set_custom_id(&format!("{user_id}-edit-template"))
+    .set_user(user_id)
+    .add_field(field(
+        "content",
+        "Text above the embed",
+        1000,
+        current
+            .as_ref()
+            .and_then(|t| {
+                t.content
+                    .as_ref()
+            }),
+    ))
+    .add_field(field(
+        "title",
+        "Title",
+        256,
+        current
+            .as_ref()
+            .and_then(|t| {
+                t.title
+                    .as_ref()
+            }),
+    ))
+    .add_field(field(
+        "description",
+        "Description",
+        4000,
+        current
+            .as_ref()
+            .and_then(|t| {
+                t.description
+                    .as_ref()
+            }),
+    ))
+    .build()
+    .unwrap()
+    .listen(
+        http,
+        &Interaction::Component(button_click),
+        data,
+    )
+    .await;
+}
+
+pub async fn template_color_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    Modal::builder(move |modal_submit, inputs| {
+        let wait_ses = ses.clone();
+        Box::pin(async move {
+            // the button click was answered with the modal, so only the submit can edit the
+            // menu
+            wait_ses
+                .write()
+                .await
+                .set_interaction(SessionInteraction::Modal(modal_submit));
+
+            let color = inputs
+                .first()
+                .and_then(|(_, value)| parse_color(value).ok());
+            set_embed_color(&wait_ses, id, color).await;
+            templates_page(wait_ses.clone(), id).await;
+        })
+    })
+    .set_title("Set the reminder color")
+    .set_custom_id(&format!("{user_id}-template-color"))
+    .set_user(user_id)
+    .add_field(
+        Field::new(
+            InputTextStyle::Short,
+            "color",
+            "Hex color",
+        )
+        .set_optional()
+        .set_max_length(7)
+        .set_placeholder("For example #1f8b4c, leave empty for the standard color")
+        .add_validator(Validator::custom(|input| {
+            parse_color(input).map(|_| ())
+        })),
+    )
+    .build()
+    .unwrap()
+    .listen(
+        http,
+        &Interaction::Component(button_click),
+        data,
+    )
+    .await;
+}
+
+/// Show how the messages of the guild look for the next launch, only to the
+/// one asking.
+pub async fn preview_templates_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let ID::Channel((channel_id, guild_id)) = id else {
+        return;
+    };
+    let Some(db) = get_db(&ses).await else {
+        return;
+    };
+    let (_, http, data) = session_parts(&ses).await;
+
+    let Ok(settings) = get_guild_settings(&db, guild_id.into()).await else {
+        return;
+    };
+    let launch = match data
+        .read()
+        .await
+        .get::<LaunchesCacheKey>()
+    {
+        Some(cache) => {
+            cache
+                .read()
+                .await
+                .first()
+                .cloned()
+        },
+        None => None,
+    };
+
+    let response = match launch {
+        Some(launch) if settings.plain_text_in(channel_id) => {
+            let texts = preview_templates(&settings, &launch)
+                .into_iter()
+                .zip(TEMPLATE_KINDS)
+                .map(|(notice, (_, name))| format!("__{name}__\n{}", notice.text()));
+
+            // the preview only gets a single message, the rest is left out
+            CreateInteractionResponseMessage::new().content(
+                plain_messages(texts, "")
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default(),
+            )
+        },
+        Some(launch) => {
+            let previews = preview_templates(&settings, &launch);
+            let content = previews
+                .iter()
+                .zip(TEMPLATE_KINDS)
+                .filter_map(|(notice, (_, name))| {
+                    notice
+                        .content
+                        .as_ref()
+                        .map(|c| format!("**{name}:** {c}"))
+                })
+                .join("\n");
+
+            let response = CreateInteractionResponseMessage::new().embeds(
+                previews
+                    .iter()
+                    .map(Notice::embed)
+                    .collect(),
+            );
+            if content.is_empty() {
+                response
+            } else {
+                response.content(content)
+            }
+        },
+        None => {
+            CreateInteractionResponseMessage::new()
+                .content("There is no upcoming launch to show a preview with")
+        },
+    };
+
+    if let Err(e) = button_click
+        .create_response(
+            &http,
+            CreateInteractionResponse::Message(response.ephemeral(true)),
+        )
+        .await
+    {
+        eprintln!("Can't show template preview: {e}");
+    }
+}
+
+pub fn other_page(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+) -> futures::future::BoxFuture<'static, ()> {
+    Box::pin(async move {
+        let Some(db) = get_db(&ses).await else {
+            return;
+        };
+
+        let mut scrub_notifications = State::Off;
+        let mut outcome_notifications = State::Off;
+        let mut mentions = State::Off;
+        let mut launch_threads = State::Off;
+        let mut plain_text = State::Off;
+        let mut description = String::new();
+
+        match id {
+            ID::Channel((channel_id, guild_id)) => {
+                let settings_res = get_guild_settings(&db, guild_id.into()).await;
+                if let Ok(settings) = settings_res {
+                    if settings.scrub_notifications {
                         scrub_notifications = State::On;
                     }
 
@@ -1258,6 +2851,14 @@ pub fn other_page(
                         mentions = State::On;
                     }
 
+                    if settings.launch_threads {
+                        launch_threads = State::On;
+                    }
+
+                    if settings.plain_text_in(channel_id) {
+                        plain_text = State::On;
+                    }
+
                     if let Some(chan) = settings.notifications_channel {
                         description = format!(
                             "\nScrub and outcome notifications will be posted in: <#{chan}>",
@@ -1278,6 +2879,10 @@ pub fn other_page(
                     if settings.outcome_notifications {
                         outcome_notifications = State::On;
                     }
+
+                    if settings.plain_text {
+                        plain_text = State::On;
+                    }
                 }
             },
         };
@@ -1291,8 +2896,7 @@ pub fn other_page(
                 .description(description),
         );
 
-        let scrub_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Toggle Scrub Notifications",
             &format!("Toggle scrub notifications on and off\nThese notifications notify you when a launch gets delayed.\nThis is currently **{scrub_notifications}**"),
             false,
@@ -1301,19 +2905,12 @@ pub fn other_page(
                 style: ButtonStyle::Primary,
                 label: "Toggle Scrubs".to_owned(),
             },
-            move |_| {
-                let scrub_ses = scrub_ses.clone();
-                Box::pin(async move {
-                    let scrub_ses = scrub_ses.clone();
-                    toggle_setting(&scrub_ses, id, "scrub_notifications", !scrub_notifications.as_ref())
-                        .await;
-                    other_page(scrub_ses, id).await
-                })
-            },
+            route("toggle", id)
+                .arg("scrub_notifications")
+                .arg(!scrub_notifications.as_ref()),
         );
 
-        let outcome_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Toggle Outcome Notifications",
             &format!("Toggle outcome notifications on and off\nThese notifications notify you about the outcome of a launch.\nThis is currently **{outcome_notifications}**"),
             false,
@@ -1322,19 +2919,12 @@ pub fn other_page(
                 style: ButtonStyle::Primary,
                 label: "Toggle Outcomes".to_owned(),
             },
-            move |_| {
-                let outcome_ses = outcome_ses.clone();
-                Box::pin(async move {
-                    let outcome_ses = outcome_ses.clone();
-                    toggle_setting(&outcome_ses, id, "outcome_notifications", !outcome_notifications.as_ref())
-                        .await;
-                    other_page(outcome_ses, id).await
-                })
-            },
+            route("toggle", id)
+                .arg("outcome_notifications")
+                .arg(!outcome_notifications.as_ref()),
         );
 
-        let mentions_ses = ses.clone();
-        em = em.add_field(
+        em = em.add_route_field(
             "Toggle Mentions",
             &format!(
                 "Toggle mentions for scrub and outcome notifications.\nThis is currently **{mentions}**",
@@ -1345,25 +2935,34 @@ pub fn other_page(
                 style: ButtonStyle::Primary,
                 label: "Toggle Mentions".to_owned(),
             },
-            move |_| {
-                let mentions_ses = mentions_ses.clone();
-                Box::pin(async move {
-                    let mentions_ses = mentions_ses.clone();
-                    toggle_setting(
-                        &mentions_ses,
-                        id,
-                        "mention_others",
-                        !mentions.as_ref(),
-                    )
-                    .await;
-                    other_page(mentions_ses, id).await
-                })
+            route("toggle", id)
+                .arg("mention_others")
+                .arg(!mentions.as_ref()),
+        );
+
+        let plain_text_target = if id.guild_specific() {
+            "in this channel "
+        } else {
+            ""
+        };
+        em = em.add_route_field(
+            "Toggle Plain Text",
+            &format!(
+                "Toggle sending reminders and notifications {plain_text_target}as plain text instead of embeds, for bridges and screen readers that don't handle embeds well.\nThis is currently **{plain_text}**",
+            ),
+            false,
+            &ButtonType {
+                emoji: Some('📝'.into()),
+                style: ButtonStyle::Primary,
+                label: "Toggle Plain Text".to_owned(),
             },
+            route("toggle", id)
+                .arg("plain_text")
+                .arg(!plain_text.as_ref()),
         );
 
         if id.guild_specific() {
-            let chan_ses = ses.clone();
-            em = em.add_field(
+            em = em.add_route_field(
                 "Set Notification Channel",
                 "Set the channel to receive scrub and outcome notifications in, this can only be one per server",
                 false,
@@ -1372,44 +2971,33 @@ pub fn other_page(
                     style: ButtonStyle::Primary,
                     label: "Set Notification Channel".to_owned(),
                 },
-                move |button_click| {
-                    let chan_ses = chan_ses.clone();
-                    Box::pin(async move {
-                    let (user_id, http, data) = {
-                        let s = chan_ses
-                                .read()
-                                .await;
-                        (
-                            s.author,
-                            s.http
-                                .clone(),
-                            s.data
-                                .clone(),
-                        )
-                    };
-
-                    channel_select_menu(http, user_id, &Interaction::Component(button_click), data, move |channel_id| {
-                        let wait_ses = chan_ses.clone();
-                        Box::pin(async move {
-                                    set_notification_channel(&wait_ses.clone(), id, channel_id).await;
-                                    other_page(wait_ses.clone(), id).await;
-                    })}).await;
+                route("notification-channel", id),
+            );
 
-                    })
+            em = em.add_route_field(
+                "Toggle Launch Threads",
+                &format!(
+                    "Toggle posting the reminders and notifications of a launch in a thread of its own, which gets archived once the launch is over.\nThis is currently **{launch_threads}**",
+                ),
+                false,
+                &ButtonType {
+                    emoji: Some('🧵'.into()),
+                    style: ButtonStyle::Primary,
+                    label: "Toggle Threads".to_owned(),
                 },
+                route("toggle", id)
+                    .arg("launch_threads")
+                    .arg(!launch_threads.as_ref()),
             );
         }
 
-        em.add_option(
+        em.add_route(
             &ButtonType {
                 label: "Back to main menu".to_owned(),
                 style: ButtonStyle::Danger,
                 emoji: Some(ReactionType::from(BACK_EMOJI)),
             },
-            move |_| {
-                let ses = ses.clone();
-                Box::pin(async move { main_menu(ses.clone(), id).await })
-            },
+            route("main", id),
         );
 
         let result = em
@@ -1420,3 +3008,27 @@ pub fn other_page(
         }
     })
 }
+
+pub async fn notification_channel_menu(
+    ses: Arc<RwLock<EmbedSession>>,
+    id: ID,
+    button_click: ComponentInteraction,
+) {
+    let (user_id, http, data) = session_parts(&ses).await;
+
+    channel_select_menu(
+        http,
+        user_id,
+        &Interaction::Component(button_click),
+        data,
+        vec![ChannelType::Text, ChannelType::News],
+        move |channel_id| {
+            let wait_ses = ses.clone();
+            Box::pin(async move {
+                set_notification_channel(&wait_ses.clone(), id, channel_id).await;
+                other_page(wait_ses.clone(), id).await;
+            })
+        },
+    )
+    .await;
+}
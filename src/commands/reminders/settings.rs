@@ -29,7 +29,13 @@ use serenity::{
 
 use crate::{
     events::statefulembed::EmbedSession,
-    models::reminders::Reminder,
+    models::reminders::{
+        LaunchMention,
+        MentionKind,
+        MentionRule,
+        MessageTemplate,
+        Reminder,
+    },
     utils::reminders::{
         get_db,
         ID,
@@ -307,6 +313,44 @@ pub async fn toggle_setting(ses: &Arc<RwLock<EmbedSession>>, id: ID, setting: &s
     }
 }
 
+/// Users get all their messages as plain text or none, for guilds it is up to
+/// each channel.
+pub async fn toggle_plain_text(ses: &Arc<RwLock<EmbedSession>>, id: ID, val: bool) {
+    let ID::Channel((channel_id, guild_id)) = id else {
+        toggle_setting(ses, id, "plain_text", val).await;
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let channel = channel_id.get() as i64;
+    let update = if val {
+        doc! {"$addToSet": {"plain_text_channels": channel}}
+    } else {
+        doc! {"$pull": {"plain_text_channels": channel}}
+    };
+
+    let result = db
+        .collection::<Document>("guild_settings")
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            update,
+            Some(
+                UpdateOptions::builder()
+                    .upsert(true)
+                    .build(),
+            ),
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("error while toggling plain text:");
+        dbg!(e);
+    }
+}
+
 pub async fn set_notification_channel(ses: &Arc<RwLock<EmbedSession>>, id: ID, channel: ChannelId) {
     let Some(db) = get_db(ses).await else {
         return;
@@ -399,3 +443,237 @@ pub async fn remove_mention(ses: &Arc<RwLock<EmbedSession>>, id: ID, role: RoleI
         dbg!(e);
     }
 }
+
+pub async fn add_mention_rule(ses: &Arc<RwLock<EmbedSession>>, id: ID, rule: &MentionRule) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let result = db
+        .collection::<Document>("guild_settings")
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            doc! {
+                "$addToSet": {
+                    "mention_rules": mention_rule_doc(rule)
+                }
+            },
+            Some(
+                UpdateOptions::builder()
+                    .upsert(true)
+                    .build(),
+            ),
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("error while adding mention rule:");
+        dbg!(e);
+    }
+}
+
+pub async fn remove_mention_rule(ses: &Arc<RwLock<EmbedSession>>, id: ID, rule: &MentionRule) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let result = db
+        .collection::<Document>("guild_settings")
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            doc! {
+                "$pull": {
+                    "mention_rules": mention_rule_doc(rule)
+                }
+            },
+            None,
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("error while removing mention rule:");
+        dbg!(e);
+    }
+}
+
+// stored with the ids as numbers like the other settings, so the same rule
+// can be found again to remove it
+fn mention_rule_doc(rule: &MentionRule) -> Document {
+    doc! {
+        "role": rule.role.get() as i64,
+        "kind": rule.kind.as_str(),
+        "minutes": rule.minutes,
+    }
+}
+
+pub async fn add_launch_mention(ses: &Arc<RwLock<EmbedSession>>, id: ID, mention: &LaunchMention) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let result = db
+        .collection::<Document>("guild_settings")
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            doc! {
+                "$addToSet": {
+                    "launch_mentions": launch_mention_doc(mention)
+                }
+            },
+            Some(
+                UpdateOptions::builder()
+                    .upsert(true)
+                    .build(),
+            ),
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("error while adding launch mention:");
+        dbg!(e);
+    }
+}
+
+pub async fn remove_launch_mention(
+    ses: &Arc<RwLock<EmbedSession>>,
+    id: ID,
+    mention: &LaunchMention,
+) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let result = db
+        .collection::<Document>("guild_settings")
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            doc! {
+                "$pull": {
+                    "launch_mentions": launch_mention_doc(mention)
+                }
+            },
+            None,
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("error while removing launch mention:");
+        dbg!(e);
+    }
+}
+
+fn launch_mention_doc(mention: &LaunchMention) -> Document {
+    doc! {
+        "role": mention.role.get() as i64,
+        "kind": mention.kind.as_str(),
+        "filter": &mention.filter,
+    }
+}
+
+/// Replace the template of a kind of message, or go back to the standard
+/// wording when there is no template.
+pub async fn set_template(
+    ses: &Arc<RwLock<EmbedSession>>,
+    id: ID,
+    kind: MentionKind,
+    template: Option<MessageTemplate>,
+) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let collection = db.collection::<Document>("guild_settings");
+    let mut result = collection
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            doc! {
+                "$pull": {
+                    "templates": {"kind": kind.as_str()}
+                }
+            },
+            None,
+        )
+        .await;
+
+    if let (Ok(_), Some(template)) = (&result, template) {
+        result = collection
+            .update_one(
+                doc! {"guild": guild_id.get() as i64},
+                doc! {
+                    "$push": {
+                        "templates": {
+                            "kind": kind.as_str(),
+                            "content": template.content,
+                            "title": template.title,
+                            "description": template.description,
+                        }
+                    }
+                },
+                Some(
+                    UpdateOptions::builder()
+                        .upsert(true)
+                        .build(),
+                ),
+            )
+            .await;
+    }
+
+    if let Err(e) = result {
+        eprintln!("error while setting template:");
+        dbg!(e);
+    }
+}
+
+pub async fn set_embed_color(ses: &Arc<RwLock<EmbedSession>>, id: ID, color: Option<u32>) {
+    let ID::Channel((_, guild_id)) = id else {
+        return;
+    };
+
+    let Some(db) = get_db(ses).await else {
+        return;
+    };
+
+    let update = if let Some(color) = color {
+        doc! {"$set": {"embed_color": color}}
+    } else {
+        doc! {"$unset": {"embed_color": ""}}
+    };
+
+    let result = db
+        .collection::<Document>("guild_settings")
+        .update_one(
+            doc! {"guild": guild_id.get() as i64},
+            update,
+            Some(
+                UpdateOptions::builder()
+                    .upsert(true)
+                    .build(),
+            ),
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("error while setting embed color:");
+        dbg!(e);
+    }
+}
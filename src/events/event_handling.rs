@@ -191,11 +191,19 @@ impl EventHandler for Handler {
                 &ctx.http,
                 format!(
                     "An error happened in {}:\n```{:?}```",
-                    interaction
-                        .as_command()
-                        .expect("not a command")
-                        .data
-                        .name,
+                    match &interaction {
+                        Interaction::Command(command) =>
+                            command
+                                .data
+                                .name
+                                .as_str(),
+                        Interaction::Component(component) =>
+                            component
+                                .data
+                                .custom_id
+                                .as_str(),
+                        _ => "an unknown interaction",
+                    },
                     e
                 ),
             )
@@ -1,4 +1,10 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
 
 use futures::{
     future::BoxFuture,
@@ -22,15 +28,40 @@ use serenity::{
             UserId,
         },
     },
+    prelude::{
+        RwLock,
+        TypeMap,
+    },
     Error,
     Result,
 };
 
+use super::statefulembed::expire_sessions;
 use crate::{
     models::caches::InteractionKey,
-    utils::interaction_builder::InteractionResponseBuilder,
+    utils::{
+        constants::{
+            SELECT_MENU_TIMEOUT,
+            SESSION_SWEEP_INTERVAL,
+        },
+        interaction_builder::InteractionResponseBuilder,
+    },
 };
 
+/// The `custom_id`s a handler can have to get an interaction with the given
+/// `custom_id`: the full id and every part of it that ends right before a '-'.
+pub fn custom_id_prefixes(custom_id: &str) -> impl Iterator<Item = &str> {
+    custom_id
+        .match_indices('-')
+        .map(|(i, _)| &custom_id[..i])
+        .chain(std::iter::once(custom_id))
+}
+
+/// Whether a handler for `handler_id` gets an interaction with `custom_id`.
+fn custom_id_matches(handler_id: &str, custom_id: &str) -> bool {
+    custom_id_prefixes(custom_id).any(|prefix| prefix == handler_id)
+}
+
 type Handler = Arc<Box<dyn Fn(Interaction) -> BoxFuture<'static, ()> + Send + Sync>>;
 
 type Filter = Arc<Box<dyn Fn(Interaction) -> BoxFuture<'static, bool> + Send + Sync>>;
@@ -44,6 +75,7 @@ pub struct InteractionHandler {
     component_type: Option<ComponentType>,
     handler: Handler,
     filter: Option<Filter>,
+    expires_at: Instant,
 }
 
 impl InteractionHandler {
@@ -54,6 +86,21 @@ impl InteractionHandler {
         InteractionHandlerBuilder::new(handler)
     }
 
+    pub fn custom_id(&self) -> Option<&str> {
+        self.custom_id
+            .as_deref()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Instant::now()
+    }
+
+    /// Whether this is the very same handler, not just one waiting for the
+    /// same interaction.
+    pub fn is_same(&self, other: &Self) -> bool {
+        self == other && Arc::ptr_eq(&self.handler, &other.handler)
+    }
+
     async fn handle(&self, interaction: &Interaction) -> bool {
         if self
             .interaction_type
@@ -96,11 +143,12 @@ impl InteractionHandler {
                 }
 
                 if let Some(custom_id) = &self.custom_id {
-                    if !component
-                        .data
-                        .custom_id
-                        .starts_with(custom_id)
-                    {
+                    if !custom_id_matches(
+                        custom_id,
+                        &component
+                            .data
+                            .custom_id,
+                    ) {
                         return false;
                     }
                 }
@@ -121,6 +169,17 @@ impl InteractionHandler {
                         return false;
                     }
                 }
+
+                if let Some(custom_id) = &self.custom_id {
+                    if !custom_id_matches(
+                        custom_id,
+                        &modal
+                            .data
+                            .custom_id,
+                    ) {
+                        return false;
+                    }
+                }
             },
             _ => return false,
         }
@@ -150,6 +209,7 @@ impl PartialEq for InteractionHandler {
 #[derive(Clone)]
 pub struct InteractionHandlerBuilder {
     inner: InteractionHandler,
+    timeout: Duration,
 }
 
 impl InteractionHandlerBuilder {
@@ -166,7 +226,9 @@ impl InteractionHandlerBuilder {
                 interaction_type: None,
                 component_type: None,
                 filter: None,
+                expires_at: Instant::now(),
             },
+            timeout: SELECT_MENU_TIMEOUT,
         }
     }
 
@@ -212,6 +274,13 @@ impl InteractionHandlerBuilder {
         self
     }
 
+    /// Set how long to keep waiting for the interaction before dropping the
+    /// handler.
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     #[allow(dead_code)]
     pub fn set_filter(mut self, filter: Filter) -> Self {
         self.inner
@@ -219,7 +288,7 @@ impl InteractionHandlerBuilder {
         self
     }
 
-    pub fn build(self) -> Result<InteractionHandler> {
+    pub fn build(mut self) -> Result<InteractionHandler> {
         if self
             .inner
             .component_type
@@ -235,28 +304,45 @@ impl InteractionHandlerBuilder {
             }
         }
 
+        self.inner
+            .expires_at = Instant::now() + self.timeout;
+
         Ok(self.inner)
     }
 }
 
 pub async fn handle_interaction(ctx: &Context, interaction: &Interaction) {
-    let all = {
+    let custom_id = match interaction {
+        Interaction::Component(component) => {
+            component
+                .data
+                .custom_id
+                .as_str()
+        },
+        Interaction::Modal(modal) => {
+            modal
+                .data
+                .custom_id
+                .as_str()
+        },
+        _ => return,
+    };
+
+    let candidates = {
         if let Some(waiting) = ctx
             .data
             .read()
             .await
             .get::<InteractionKey>()
         {
-            waiting
-                .0
-                .clone()
+            waiting.candidates(custom_id)
         } else {
             eprintln!("No waiting interaction cache");
             return;
         }
     };
 
-    let handled = stream::iter(all.iter())
+    let handled = stream::iter(candidates.iter())
         .filter(|h| {
             async {
                 h.handle(interaction)
@@ -269,18 +355,35 @@ pub async fn handle_interaction(ctx: &Context, interaction: &Interaction) {
         .cloned()
         .collect::<Vec<InteractionHandler>>();
 
+    if handled.is_empty() {
+        return;
+    }
+
     if let Some(waiting) = ctx
         .data
         .write()
         .await
         .get_mut::<InteractionKey>()
     {
-        waiting.0 = waiting
-            .0
-            .iter()
-            .filter(|h| !handled.contains(h))
-            .cloned()
-            .collect::<Vec<InteractionHandler>>();
+        waiting.remove(&handled);
+    }
+}
+
+/// Periodically drop the embed sessions and interaction handlers that have
+/// not been used within their timeout.
+pub async fn session_sweeper(data: Arc<RwLock<TypeMap>>) {
+    loop {
+        tokio::time::sleep(SESSION_SWEEP_INTERVAL).await;
+
+        expire_sessions(&data).await;
+
+        if let Some(waiting) = data
+            .write()
+            .await
+            .get_mut::<InteractionKey>()
+        {
+            waiting.remove_expired();
+        }
     }
 }
 
@@ -360,3 +463,32 @@ fn component_type_equals_kind(
         ComponentInteractionDataKind::Unknown(_) => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_ids_match_at_dashes() {
+        assert!(custom_id_matches(
+            "123-remove",
+            "123-remove"
+        ));
+        assert!(custom_id_matches(
+            "123-remove",
+            "123-remove-x"
+        ));
+        assert!(!custom_id_matches(
+            "123-remove",
+            "123-removebooster"
+        ));
+        assert!(!custom_id_matches(
+            "123-remove-x",
+            "123-remove"
+        ));
+        assert_eq!(
+            custom_id_prefixes("123-remove-x").collect::<Vec<_>>(),
+            vec!["123", "123-remove", "123-remove-x"]
+        );
+    }
+}
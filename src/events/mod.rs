@@ -1,6 +1,7 @@
 mod event_handling;
 pub mod interaction_handler;
 pub mod modal;
+pub mod paginator;
 pub mod select_menu;
 pub mod statefulembed;
 pub mod time_embed;
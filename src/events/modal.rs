@@ -1,19 +1,31 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::Arc,
+    time::Duration,
+};
 
 use futures::future::BoxFuture;
+use regex::Regex;
 use serenity::{
     builder::{
         CreateActionRow,
+        CreateButton,
         CreateInputText,
         CreateInteractionResponse,
+        CreateInteractionResponseFollowup,
+        EditInteractionResponse,
     },
     http::Http,
     model::{
         application::{
             ActionRowComponent,
+            ButtonStyle,
+            ComponentType,
             InputTextStyle,
             Interaction,
             InteractionType,
+            ModalInteraction,
         },
         id::UserId,
     },
@@ -30,13 +42,20 @@ use super::interaction_handler::{
 };
 use crate::{
     models::caches::InteractionKey,
-    utils::interaction_builder::{
-        InteractionBuilderKind,
-        InteractionResponseBuilder,
+    utils::{
+        catalog::Catalog,
+        constants::MODAL_TIMEOUT,
+        interaction_builder::{
+            InteractionBuilderKind,
+            InteractionResponseBuilder,
+        },
+        parse_duration,
     },
 };
 
-type Handler = Arc<Box<dyn Fn(Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync>>;
+type Handler = Arc<
+    Box<dyn Fn(ModalInteraction, Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync>,
+>;
 
 #[derive(Clone)]
 pub struct Modal {
@@ -45,6 +64,7 @@ pub struct Modal {
     fields: Vec<Field>,
     custom_id: Option<String>,
     handler: Handler,
+    timeout: Duration,
 }
 
 impl Modal {
@@ -54,14 +74,46 @@ impl Modal {
         interaction: &Interaction,
         data: Arc<RwLock<TypeMap>>,
     ) {
-        self.send(&http, interaction)
+        self.listen_with_origin(http, interaction, data, None)
             .await;
+    }
+
+    /// Send the modal and wait for it to be submitted. If the input doesn't
+    /// pass the validators of the fields the user gets told what is wrong, with
+    /// a button to reopen the modal with their input filled in.
+    ///
+    /// The handler always gets the first submit of the modal, so it can edit
+    /// the message the modal was opened from even after a retry.
+    fn listen_with_origin(
+        self,
+        http: Arc<Http>,
+        interaction: &Interaction,
+        data: Arc<RwLock<TypeMap>>,
+        origin: Option<ModalInteraction>,
+    ) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.send(&http, interaction)
+                .await;
+
+            let interaction_handler = self.submit_handler(http, data.clone(), origin);
 
-        let handler = self
-            .handler
-            .clone();
+            if let Some(waiting) = data
+                .write()
+                .await
+                .get_mut::<InteractionKey>()
+            {
+                waiting.push(interaction_handler);
+            }
+        })
+    }
 
-        let http_clone = http.clone();
+    fn submit_handler(
+        &self,
+        http: Arc<Http>,
+        type_map: Arc<RwLock<TypeMap>>,
+        origin: Option<ModalInteraction>,
+    ) -> InteractionHandler {
+        let modal = self.clone();
         let mut interaction_handler = InteractionHandler::builder(move |interaction| {
             let data = interaction
                 .modal_submit()
@@ -86,41 +138,188 @@ impl Modal {
                 })
                 .collect::<Vec<_>>();
 
-            let http_clone = http_clone.clone();
-            let handler_clone = handler.clone();
+            let http = http.clone();
+            let type_map = type_map.clone();
+            let origin = origin.clone();
+            let modal = modal.clone();
             Box::pin(async move {
                 let _ = data
                     .create_response(
-                        http_clone,
+                        &http,
                         CreateInteractionResponse::Acknowledge,
                     )
                     .await;
 
-                handler_clone(values).await
+                let errors = modal.validate(&values);
+
+                // a retry gets submitted from the error message, which then gets updated in
+                // place
+                match (origin, errors) {
+                    (None, None) => {
+                        (modal.handler)(data, values).await;
+                    },
+                    (Some(origin), None) => {
+                        let _ = data
+                            .edit_response(
+                                &http,
+                                EditInteractionResponse::new()
+                                    .content("Thanks, that worked!")
+                                    .components(Vec::new()),
+                            )
+                            .await;
+                        (modal.handler)(origin, values).await;
+                    },
+                    (None, Some(errors)) => {
+                        let _ = data
+                            .create_followup(
+                                &http,
+                                CreateInteractionResponseFollowup::new()
+                                    .content(errors)
+                                    .components(vec![modal.retry_row()])
+                                    .ephemeral(true),
+                            )
+                            .await;
+                        modal
+                            .wait_for_retry(http, type_map, data, values)
+                            .await;
+                    },
+                    (Some(origin), Some(errors)) => {
+                        let _ = data
+                            .edit_response(
+                                &http,
+                                EditInteractionResponse::new()
+                                    .content(errors)
+                                    .components(vec![modal.retry_row()]),
+                            )
+                            .await;
+                        modal
+                            .wait_for_retry(http, type_map, origin, values)
+                            .await;
+                    },
+                }
             })
         })
-        .set_interaction_type(InteractionType::Modal);
+        .set_interaction_type(InteractionType::Modal)
+        .set_timeout(self.timeout);
 
         if let Some(user_id) = self.user_id {
             interaction_handler = interaction_handler.set_user(user_id);
         }
 
-        if let Some(custom_id) = self.custom_id {
+        if let Some(custom_id) = self
+            .custom_id
+            .clone()
+        {
             interaction_handler = interaction_handler.set_custom_id(custom_id);
         }
 
-        if let Some(waiting) = data
+        interaction_handler
+            .build()
+            .unwrap()
+    }
+
+    /// Check all inputs against the validators of their field, giving the
+    /// message to show to the user if any of them failed.
+    fn validate(&self, values: &[(String, String)]) -> Option<String> {
+        let mut errors = String::new();
+
+        for (custom_id, value) in values {
+            let Some(field) = self
+                .fields
+                .iter()
+                .find(|f| &f.custom_id == custom_id)
+            else {
+                continue;
+            };
+
+            if let Err(err) = field.validate(value) {
+                writeln!(errors, "• **{}**: {err}", field.label)
+                    .expect("write to String: can't fail");
+            }
+        }
+
+        if errors.is_empty() {
+            None
+        } else {
+            Some(format!(
+                "Some of your input isn't valid:\n{errors}"
+            ))
+        }
+    }
+
+    fn retry_id(&self) -> String {
+        format!(
+            "{}-retry",
+            self.custom_id
+                .as_deref()
+                .unwrap_or("modal")
+        )
+    }
+
+    fn retry_row(&self) -> CreateActionRow {
+        CreateActionRow::Buttons(vec![CreateButton::new(self.retry_id())
+            .label("Try again")
+            .style(ButtonStyle::Primary)
+            .emoji('🔁')])
+    }
+
+    /// Wait for the "try again" button to be clicked and reopen the modal with
+    /// the previous input filled in.
+    async fn wait_for_retry(
+        &self,
+        http: Arc<Http>,
+        type_map: Arc<RwLock<TypeMap>>,
+        origin: ModalInteraction,
+        values: Vec<(String, String)>,
+    ) {
+        let previous = values
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let mut modal = self.clone();
+        for field in &mut modal.fields {
+            if let Some(value) = previous.get(&field.custom_id) {
+                field.inner = field
+                    .inner
+                    .clone()
+                    .value(value);
+            }
+        }
+
+        let type_map_clone = type_map.clone();
+        let mut retry_handler = InteractionHandler::builder(move |interaction| {
+            let modal = modal.clone();
+            let http = http.clone();
+            let type_map = type_map_clone.clone();
+            let origin = origin.clone();
+            Box::pin(async move {
+                modal
+                    .listen_with_origin(
+                        http,
+                        &interaction,
+                        type_map,
+                        Some(origin),
+                    )
+                    .await;
+            })
+        })
+        .set_component_type(ComponentType::Button)
+        .set_custom_id(self.retry_id())
+        .set_timeout(self.timeout);
+
+        if let Some(user_id) = self.user_id {
+            retry_handler = retry_handler.set_user(user_id);
+        }
+
+        if let Some(waiting) = type_map
             .write()
             .await
             .get_mut::<InteractionKey>()
         {
-            waiting
-                .0
-                .push(
-                    interaction_handler
-                        .build()
-                        .unwrap(),
-                );
+            waiting.push(
+                retry_handler
+                    .build()
+                    .unwrap(),
+            );
         }
     }
 
@@ -157,7 +356,10 @@ impl Modal {
 
     pub fn builder<F>(handler: F) -> ModalBuilder
     where
-        F: Fn(Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+        F: Fn(ModalInteraction, Vec<(String, String)>) -> BoxFuture<'static, ()>
+            + Send
+            + Sync
+            + 'static,
     {
         ModalBuilder::new(handler)
     }
@@ -171,7 +373,10 @@ pub struct ModalBuilder {
 impl ModalBuilder {
     pub fn new<F>(handler: F) -> Self
     where
-        F: Fn(Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+        F: Fn(ModalInteraction, Vec<(String, String)>) -> BoxFuture<'static, ()>
+            + Send
+            + Sync
+            + 'static,
     {
         Self {
             inner: Modal {
@@ -180,6 +385,7 @@ impl ModalBuilder {
                 custom_id: None,
                 user_id: None,
                 fields: Vec::new(),
+                timeout: MODAL_TIMEOUT,
             },
         }
     }
@@ -208,6 +414,13 @@ impl ModalBuilder {
         self
     }
 
+    #[allow(dead_code)]
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.inner
+            .timeout = timeout;
+        self
+    }
+
     pub fn set_user(mut self, user_id: UserId) -> Self {
         self.inner
             .user_id = Some(user_id);
@@ -222,9 +435,78 @@ impl ModalBuilder {
     }
 }
 
+type Check = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// A check the input of a [`Field`] has to pass before the modal handler gets
+/// called.
+#[derive(Clone)]
+pub enum Validator {
+    /// The input has to be a valid regex.
+    Regex,
+    /// The input has to be a duration that [`parse_duration`] understands.
+    Duration,
+    /// The input has to be a whole number within the range, inclusive.
+    Range(i64, i64),
+    /// The input has to be one of the agency filters in the catalog.
+    Agency(Arc<Catalog>),
+    /// Any other check, giving the message to show to the user on failure.
+    Custom(Check),
+}
+
+impl Validator {
+    pub fn validate(&self, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        match self {
+            Self::Regex => {
+                Regex::new(input)
+                    .map(|_| ())
+                    .map_err(|_| format!("`{input}` is not a valid regex"))
+            },
+            Self::Duration => {
+                parse_duration(input)
+                    .map(|_| ())
+                    .map_err(|err| format!("{err}, try something like `2h30m` or `1 day`"))
+            },
+            Self::Range(min, max) => {
+                match input.parse::<i64>() {
+                    Ok(num) if (*min..=*max).contains(&num) => Ok(()),
+                    _ => {
+                        Err(format!(
+                            "has to be a number from {min} to {max}"
+                        ))
+                    },
+                }
+            },
+            Self::Agency(catalog) => {
+                if catalog
+                    .agency(&input.to_lowercase())
+                    .is_some()
+                {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "`{input}` is not a known agency, use `/filtersinfo` to see them all"
+                    ))
+                }
+            },
+            Self::Custom(check) => check(input),
+        }
+    }
+
+    pub fn custom<F>(check: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(check))
+    }
+}
+
 #[derive(Clone)]
 pub struct Field {
     inner: CreateInputText,
+    custom_id: String,
+    label: String,
+    validators: Vec<Validator>,
 }
 
 impl Field {
@@ -235,9 +517,33 @@ impl Field {
                 label.to_string(),
                 custom_id.to_string(),
             ),
+            custom_id: custom_id.to_string(),
+            label: label.to_string(),
+            validators: Vec::new(),
         }
     }
 
+    /// Check the input against the validators, empty input of optional fields
+    /// is always valid.
+    fn validate(&self, input: &str) -> Result<(), String> {
+        if input
+            .trim()
+            .is_empty()
+        {
+            return Ok(());
+        }
+
+        self.validators
+            .iter()
+            .try_for_each(|v| v.validate(input))
+    }
+
+    pub fn add_validator(mut self, validator: Validator) -> Self {
+        self.validators
+            .push(validator);
+        self
+    }
+
     pub fn set_required(mut self) -> Self {
         self.inner = self
             .inner
@@ -245,6 +551,22 @@ impl Field {
         self
     }
 
+    /// Let the field be left empty, discord requires every field by default.
+    pub fn set_optional(mut self) -> Self {
+        self.inner = self
+            .inner
+            .required(false);
+        self
+    }
+
+    /// Fill the field in beforehand, like with the current value of a setting.
+    pub fn set_value<T: ToString + ?Sized>(mut self, value: &T) -> Self {
+        self.inner = self
+            .inner
+            .value(value.to_string());
+        self
+    }
+
     pub fn set_min_length(mut self, min_length: u16) -> Self {
         self.inner = self
             .inner
@@ -266,3 +588,50 @@ impl Field {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validators() {
+        assert!(Validator::Regex
+            .validate("star(link|ship)")
+            .is_ok());
+        assert!(Validator::Regex
+            .validate("star(link")
+            .is_err());
+        assert!(Validator::Duration
+            .validate("2 hours")
+            .is_ok());
+        assert!(Validator::Duration
+            .validate("soon")
+            .is_err());
+        assert!(Validator::Range(1, 5)
+            .validate(" 5 ")
+            .is_ok());
+        assert!(Validator::Range(1, 5)
+            .validate("6")
+            .is_err());
+
+        let catalog = Arc::new(Catalog::default());
+        assert!(Validator::Agency(catalog.clone())
+            .validate("SpaceX")
+            .is_ok());
+        assert!(Validator::Agency(catalog)
+            .validate("acme")
+            .is_err());
+    }
+
+    #[test]
+    fn empty_optional_input_is_valid() {
+        let field = Field::new(InputTextStyle::Short, "num", "Number")
+            .add_validator(Validator::Range(1, 5));
+        assert!(field
+            .validate("")
+            .is_ok());
+        assert!(field
+            .validate("9")
+            .is_err());
+    }
+}
@@ -0,0 +1,455 @@
+use std::{
+    fmt,
+    ops::Range,
+    sync::Arc,
+};
+
+use futures::future::BoxFuture;
+use okto_framework::structs::ComponentId;
+use serenity::{
+    builder::{
+        CreateEmbed,
+        CreateEmbedFooter,
+    },
+    model::application::{
+        ButtonStyle,
+        ComponentInteraction,
+        InputTextStyle,
+        Interaction,
+    },
+    prelude::RwLock,
+};
+
+use super::{
+    modal::{
+        Field,
+        Modal,
+        Validator,
+    },
+    statefulembed::{
+        ButtonType,
+        EmbedSession,
+        SessionInteraction,
+        StatefulEmbed,
+        StatefulOption,
+    },
+};
+use crate::utils::StandardButton;
+
+type Fetcher<T> = Arc<dyn Fn(Range<usize>) -> BoxFuture<'static, Vec<T>> + Send + Sync>;
+
+type Renderer<T> = Arc<dyn Fn(&[T], PageInfo) -> CreateEmbed + Send + Sync>;
+
+type Handler = Arc<dyn Fn(ComponentInteraction) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Where the items shown by a [`Paginator`] come from.
+#[derive(Clone)]
+pub enum PageSource<T> {
+    /// All items are known up front.
+    List(Vec<T>),
+    /// Only the amount of items is known up front, the items themselves get
+    /// fetched once the page they are on gets shown.
+    Fetch { count: usize, fetch: Fetcher<T> },
+}
+
+impl<T: Clone> PageSource<T> {
+    // for lists too big to get up front, none of the commands need it yet
+    #[allow(dead_code)]
+    pub fn fetch<F>(count: usize, fetch: F) -> Self
+    where
+        F: Fn(Range<usize>) -> BoxFuture<'static, Vec<T>> + Send + Sync + 'static,
+    {
+        Self::Fetch {
+            count,
+            fetch: Arc::new(fetch),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::List(items) => items.len(),
+            Self::Fetch {
+                count,
+                ..
+            } => *count,
+        }
+    }
+
+    async fn get(&self, range: Range<usize>) -> Vec<T> {
+        match self {
+            Self::List(items) => items[range].to_vec(),
+            Self::Fetch {
+                fetch,
+                ..
+            } => fetch(range).await,
+        }
+    }
+}
+
+/// The page that is being rendered, zero-indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageInfo {
+    pub page: usize,
+    pub pages: usize,
+}
+
+impl fmt::Display for PageInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Page {} of {}",
+            self.page + 1,
+            self.pages
+        )
+    }
+}
+
+#[derive(Clone)]
+enum PageButton {
+    Route(ButtonType, ComponentId),
+    Handler(ButtonType, Handler),
+}
+
+/// An embed that shows a list of items a page at a time, with buttons to go
+/// through the pages and to jump to a specific page.
+///
+/// Without a route the buttons are handled by the [`EmbedSession`], with one
+/// they get routed to the [`Component`] of the route with the page to show as
+/// the last argument, which can then be passed through [`parse_page`].
+///
+/// [`Component`]: okto_framework::structs::Component
+#[derive(Clone)]
+pub struct Paginator<T> {
+    session: Arc<RwLock<EmbedSession>>,
+    source: PageSource<T>,
+    per_page: usize,
+    render: Renderer<T>,
+    footer: Option<String>,
+    route: Option<ComponentId>,
+    buttons: Vec<PageButton>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Paginator<T> {
+    pub fn new<F>(
+        session: Arc<RwLock<EmbedSession>>,
+        source: PageSource<T>,
+        per_page: usize,
+        render: F,
+    ) -> Self
+    where
+        F: Fn(&[T], PageInfo) -> CreateEmbed + Send + Sync + 'static,
+    {
+        Self {
+            session,
+            source,
+            per_page: per_page.max(1),
+            render: Arc::new(render),
+            footer: None,
+            route: None,
+            buttons: Vec::new(),
+        }
+    }
+
+    /// Text to put in the footer after the page number.
+    pub fn set_footer<S: ToString + ?Sized>(mut self, footer: &S) -> Self {
+        self.footer = Some(footer.to_string());
+        self
+    }
+
+    pub fn set_route(mut self, route: ComponentId) -> Self {
+        self.route = Some(route);
+        self
+    }
+
+    /// Let everyone go through the pages instead of only the one that ran the
+    /// command, only applies to paginators without a route.
+    pub async fn set_open(self, open: bool) -> Self {
+        self.session
+            .write()
+            .await
+            .set_open(open);
+        self
+    }
+
+    pub fn add_route(mut self, button: ButtonType, route: ComponentId) -> Self {
+        self.buttons
+            .push(PageButton::Route(button, route));
+        self
+    }
+
+    pub fn add_option<F>(mut self, button: ButtonType, handler: F) -> Self
+    where
+        F: Fn(ComponentInteraction) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        self.buttons
+            .push(PageButton::Handler(
+                button,
+                Arc::new(handler),
+            ));
+        self
+    }
+
+    pub fn pages(&self) -> usize {
+        self.source
+            .len()
+            .div_ceil(self.per_page)
+            .max(1)
+    }
+
+    /// Show the given page, or the last one if there aren't that many pages.
+    pub fn show_page(self, page: usize) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            let info = PageInfo {
+                page: page.min(self.pages() - 1),
+                pages: self.pages(),
+            };
+
+            let start = info.page * self.per_page;
+            let end = (start + self.per_page).min(
+                self.source
+                    .len(),
+            );
+            let items = self
+                .source
+                .get(start..end)
+                .await;
+
+            let footer = match &self.footer {
+                Some(text) => format!("{info} • {text}"),
+                None => info.to_string(),
+            };
+            let mut em = StatefulEmbed::new_with_embed(
+                self.session
+                    .clone(),
+                (self.render)(&items, info).footer(CreateEmbedFooter::new(footer)),
+            );
+
+            if info.page > 0 {
+                self.add_page_button(
+                    &mut em,
+                    StandardButton::First,
+                    "first",
+                    0,
+                );
+                self.add_page_button(
+                    &mut em,
+                    StandardButton::Back,
+                    &(info.page - 1).to_string(),
+                    info.page - 1,
+                );
+            }
+
+            if info.page + 1 < info.pages {
+                self.add_page_button(
+                    &mut em,
+                    StandardButton::Forward,
+                    &(info.page + 1).to_string(),
+                    info.page + 1,
+                );
+                self.add_page_button(
+                    &mut em,
+                    StandardButton::Last,
+                    "last",
+                    info.pages - 1,
+                );
+            }
+
+            if info.pages > 2 {
+                let jump = ButtonType {
+                    label: "Go to page".to_owned(),
+                    style: ButtonStyle::Secondary,
+                    emoji: Some('🔢'.into()),
+                };
+                if let Some(route) = &self.route {
+                    em.add_route(
+                        &jump,
+                        route
+                            .clone()
+                            .arg("jump"),
+                    );
+                } else {
+                    let paginator = self.clone();
+                    // the modal has to be the response to the click, so the session
+                    // must not acknowledge it first
+                    em.options
+                        .push(StatefulOption {
+                            button: jump,
+                            handler: Some(Arc::new(move |click| {
+                                let paginator = paginator.clone();
+                                Box::pin(async move {
+                                    paginator
+                                        .ask_page(click)
+                                        .await
+                                })
+                            })),
+                            route: None,
+                            is_update: false,
+                        });
+                }
+            }
+
+            for button in &self.buttons {
+                match button {
+                    PageButton::Route(button, route) => {
+                        em.add_route(button, route.clone());
+                    },
+                    PageButton::Handler(button, handler) => {
+                        let handler = handler.clone();
+                        em.add_option(button, move |click| handler(click));
+                    },
+                }
+            }
+
+            let res = em
+                .show()
+                .await;
+            if let Err(err) = res {
+                dbg!(err);
+            }
+        })
+    }
+
+    fn add_page_button(
+        &self,
+        em: &mut StatefulEmbed,
+        button: StandardButton,
+        arg: &str,
+        page: usize,
+    ) {
+        if let Some(route) = &self.route {
+            em.add_route(
+                &button.to_button(),
+                route
+                    .clone()
+                    .arg(arg),
+            );
+        } else {
+            let paginator = self.clone();
+            em.add_option(&button.to_button(), move |_| {
+                paginator
+                    .clone()
+                    .show_page(page)
+            });
+        }
+    }
+
+    /// Open a modal in response to the click asking which page to go to.
+    pub async fn ask_page(self, click: ComponentInteraction) {
+        let (http, data) = {
+            let s = self
+                .session
+                .read()
+                .await;
+            (
+                s.http
+                    .clone(),
+                s.data
+                    .clone(),
+            )
+        };
+        let user_id = click
+            .user
+            .id;
+        let pages = self.pages();
+
+        Modal::builder(move |modal_submit, inputs| {
+            let paginator = self.clone();
+            Box::pin(async move {
+                // the click was answered with the modal, so the submit has to edit the message
+                paginator
+                    .session
+                    .write()
+                    .await
+                    .set_interaction(SessionInteraction::Modal(modal_submit));
+
+                let page = inputs
+                    .first()
+                    .and_then(|(_, value)| {
+                        value
+                            .trim()
+                            .parse::<usize>()
+                            .ok()
+                    })
+                    .unwrap_or(1);
+                paginator
+                    .show_page(page.saturating_sub(1))
+                    .await;
+            })
+        })
+        .set_title("Go to page")
+        .set_custom_id(&format!("{user_id}-jump-page"))
+        .set_user(user_id)
+        .add_field(
+            Field::new(
+                InputTextStyle::Short,
+                "page",
+                format!("Page number (1-{pages})").as_str(),
+            )
+            .set_max_length(4)
+            .set_min_length(1)
+            .set_required()
+            .add_validator(Validator::Range(1, pages as i64)),
+        )
+        .build()
+        .unwrap()
+        .listen(
+            http,
+            &Interaction::Component(click),
+            data,
+        )
+        .await;
+    }
+}
+
+/// Parse the page argument of a routed paginator button, "last" is given as
+/// `usize::MAX` which [`Paginator::show_page`] turns into the last page.
+pub fn parse_page(arg: &str) -> Option<usize> {
+    match arg {
+        "first" => Some(0),
+        "last" => Some(usize::MAX),
+        num => {
+            num.parse()
+                .ok()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_info_display() {
+        let info = PageInfo {
+            page: 0,
+            pages: 3,
+        };
+        assert_eq!(info.to_string(), "Page 1 of 3");
+    }
+
+    #[test]
+    fn parse_page_arguments() {
+        assert_eq!(parse_page("first"), Some(0));
+        assert_eq!(parse_page("last"), Some(usize::MAX));
+        assert_eq!(parse_page("4"), Some(4));
+        assert_eq!(parse_page("jump"), None);
+    }
+
+    #[tokio::test]
+    async fn sources_give_the_items_of_a_page() {
+        let list = PageSource::List((0..10).collect::<Vec<usize>>());
+        let fetched = PageSource::fetch(10, |range| {
+            Box::pin(async move { range.collect() })
+        });
+
+        for source in [list, fetched] {
+            assert_eq!(source.len(), 10);
+            assert_eq!(
+                source
+                    .get(4..7)
+                    .await,
+                vec![4, 5, 6]
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::{
     collections::HashMap,
+    fmt::Write,
     sync::Arc,
+    time::Duration,
 };
 
 use futures::future::BoxFuture;
@@ -9,6 +11,7 @@ use serenity::{
     all::ComponentInteractionDataKind,
     builder::{
         CreateActionRow,
+        CreateButton,
         CreateInteractionResponse,
         CreateSelectMenu,
         CreateSelectMenuKind,
@@ -17,9 +20,11 @@ use serenity::{
     http::Http,
     model::{
         application::{
+            ButtonStyle,
             ComponentType,
             Interaction,
         },
+        channel::ChannelType,
         id::UserId,
     },
     prelude::{
@@ -35,22 +40,102 @@ use super::interaction_handler::{
 };
 use crate::{
     models::caches::InteractionKey,
-    utils::interaction_builder::InteractionResponseBuilder,
+    utils::{
+        constants::SELECT_MENU_TIMEOUT,
+        interaction_builder::InteractionResponseBuilder,
+    },
 };
 
-type Handler = Arc<Box<dyn Fn((String, String)) -> BoxFuture<'static, ()> + Send + Sync>>;
+type Handler = Arc<Box<dyn Fn(Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync>>;
+
+/// The most options that fit in the five select menus of a single message.
+const MAX_OPTIONS: usize = 125;
+
+/// Options per page when there are too many for a single message, one row of
+/// the message is taken by the page buttons then.
+const PAGE_SIZE: usize = 100;
+
+/// The kind of select menu to show, the native discord kinds let the user pick
+/// from all roles, channels or users instead of from a list of options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectMenuKind {
+    String,
+    Role,
+    /// Only channels of the given types can be picked, or all channels if
+    /// empty.
+    Channel(Vec<ChannelType>),
+    #[allow(dead_code)]
+    User,
+    /// Both users and roles can be picked.
+    #[allow(dead_code)]
+    Mentionable,
+}
+
+impl SelectMenuKind {
+    fn component_type(&self) -> ComponentType {
+        match self {
+            Self::String => ComponentType::StringSelect,
+            Self::Role => ComponentType::RoleSelect,
+            Self::Channel(_) => ComponentType::ChannelSelect,
+            Self::User => ComponentType::UserSelect,
+            Self::Mentionable => ComponentType::MentionableSelect,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct SelectMenu {
     description: Option<String>,
     user_id: Option<UserId>,
     options: HashMap<String, String>,
+    kind: SelectMenuKind,
+    min_values: Option<u8>,
+    max_values: Option<u8>,
     ephemeral: bool,
     custom_id: Option<String>,
     handler: Handler,
+    timeout: Duration,
+    page: usize,
 }
 
 impl SelectMenu {
+    /// The amount of pages the options are spread over.
+    fn page_count(&self) -> usize {
+        if self
+            .options
+            .len()
+            <= MAX_OPTIONS
+        {
+            1
+        } else {
+            self.options
+                .len()
+                .div_ceil(PAGE_SIZE)
+        }
+    }
+
+    /// The options on the page that is shown, sorted on their label.
+    fn page_options(&self) -> Vec<(&String, &String)> {
+        let size = if self.page_count() == 1 {
+            MAX_OPTIONS
+        } else {
+            PAGE_SIZE
+        };
+
+        self.options
+            .iter()
+            .sorted_by_key(|t| t.1)
+            .skip(self.page * size)
+            .take(size)
+            .collect()
+    }
+
+    fn custom_id(&self) -> &str {
+        self.custom_id
+            .as_ref()
+            .map_or("select-row", |s| s.as_str())
+    }
+
     pub async fn listen(
         self,
         http: Arc<Http>,
@@ -60,6 +145,11 @@ impl SelectMenu {
         self.send(&http, interaction)
             .await;
 
+        if self.page_count() > 1 {
+            self.listen_pages(http.clone(), data.clone())
+                .await;
+        }
+
         let handler = self
             .handler
             .clone();
@@ -72,26 +162,43 @@ impl SelectMenu {
                 .message_component()
                 .expect("Didn't get a message component in select menu");
 
-            let key = if let ComponentInteractionDataKind::StringSelect {
-                values,
-            } = data
+            // native select menus give ids, which are used as both the key and the value
+            let chosen = match data
                 .data
                 .kind
                 .clone()
             {
-                values
-                    .first()
-                    .cloned()
-                    .expect("No values returned from select menu")
-            } else {
-                panic!("Got a non-string value from the select menu")
+                ComponentInteractionDataKind::StringSelect {
+                    values,
+                } => {
+                    values
+                        .into_iter()
+                        .filter_map(|key| {
+                            let value = options
+                                .get(&key)
+                                .cloned();
+                            if value.is_none() {
+                                eprintln!("Not a valid choice in select menu: {key}");
+                            }
+                            value.map(|value| (key, value))
+                        })
+                        .collect()
+                },
+                ComponentInteractionDataKind::RoleSelect {
+                    values,
+                } => id_pairs(values),
+                ComponentInteractionDataKind::ChannelSelect {
+                    values,
+                } => id_pairs(values),
+                ComponentInteractionDataKind::UserSelect {
+                    values,
+                } => id_pairs(values),
+                ComponentInteractionDataKind::MentionableSelect {
+                    values,
+                } => id_pairs(values),
+                _ => panic!("Got a non-select value from the select menu"),
             };
 
-            let chosen = options
-                .get(&key)
-                .cloned()
-                .expect("Not a valid choice in select menu");
-
             let http_clone = http_clone.clone();
             let handler_clone = handler.clone();
             Box::pin(async move {
@@ -102,10 +209,14 @@ impl SelectMenu {
                     )
                     .await;
 
-                handler_clone((key, chosen.clone())).await
+                handler_clone(chosen).await
             })
         })
-        .set_component_type(ComponentType::StringSelect);
+        .set_component_type(
+            self.kind
+                .component_type(),
+        )
+        .set_timeout(self.timeout);
 
         if let Some(user_id) = self.user_id {
             interaction_handler = interaction_handler.set_user(user_id);
@@ -120,49 +231,201 @@ impl SelectMenu {
             .await
             .get_mut::<InteractionKey>()
         {
-            waiting
-                .0
-                .push(
-                    interaction_handler
-                        .build()
-                        .unwrap(),
-                );
+            waiting.push(
+                interaction_handler
+                    .build()
+                    .unwrap(),
+            );
         }
     }
 
-    async fn send(&self, http: impl AsRef<Http>, interaction: &Interaction) {
-        let mut resp = InteractionResponseBuilder::default()
-            .content(
-                self.description
-                    .clone()
-                    .unwrap_or_else(|| "Select an option".to_owned()),
+    /// Show another page of the menu when one of the page buttons gets
+    /// clicked.
+    async fn listen_pages(&self, http: Arc<Http>, data: Arc<RwLock<TypeMap>>) {
+        let menu = self.clone();
+        let data_clone = data.clone();
+        let mut page_handler = InteractionHandler::builder(move |interaction| {
+            let menu = menu.clone();
+            let http = http.clone();
+            let data = data_clone.clone();
+            Box::pin(async move {
+                let Interaction::Component(click) = &interaction else {
+                    return;
+                };
+                let Some(page) = click
+                    .data
+                    .custom_id
+                    .rsplit('-')
+                    .next()
+                    .and_then(|p| {
+                        p.parse::<usize>()
+                            .ok()
+                    })
+                else {
+                    return;
+                };
+
+                let _ = click
+                    .create_response(
+                        &http,
+                        CreateInteractionResponse::Acknowledge,
+                    )
+                    .await;
+
+                show_page(
+                    SelectMenu {
+                        page,
+                        ..menu
+                    },
+                    http,
+                    interaction,
+                    data,
+                )
+                .await;
+            })
+        })
+        .set_component_type(ComponentType::Button)
+        .set_custom_id(format!("{}-page", self.custom_id()))
+        .set_timeout(self.timeout);
+
+        if let Some(user_id) = self.user_id {
+            page_handler = page_handler.set_user(user_id);
+        }
+
+        if let Some(waiting) = data
+            .write()
+            .await
+            .get_mut::<InteractionKey>()
+        {
+            waiting.push(
+                page_handler
+                    .build()
+                    .unwrap(),
+            );
+        }
+    }
+
+    fn page_buttons(&self) -> CreateActionRow {
+        let pages = self.page_count();
+        let button = |page: usize, label: &str| {
+            CreateButton::new(format!(
+                "{}-page-{page}",
+                self.custom_id()
+            ))
+            .label(label)
+            .style(ButtonStyle::Secondary)
+        };
+
+        CreateActionRow::Buttons(vec![
+            button(
+                self.page
+                    .saturating_sub(1),
+                "Previous",
             )
-            .components(
-                self.options
-                    .iter()
-                    .sorted_by_key(|t| t.1)
+            .disabled(self.page == 0),
+            button(self.page + 1, "Next").disabled(self.page + 1 >= pages),
+        ])
+    }
+
+    fn create_menu(&self, row: usize, kind: CreateSelectMenuKind) -> CreateActionRow {
+        let mut menu = CreateSelectMenu::new(
+            format!("{}-{}", self.custom_id(), row),
+            kind,
+        );
+
+        if let Some(min) = self.min_values {
+            menu = menu.min_values(min);
+        }
+
+        if let Some(max) = self.max_values {
+            menu = menu.max_values(max);
+        }
+
+        CreateActionRow::SelectMenu(menu)
+    }
+
+    fn components(&self) -> Vec<CreateActionRow> {
+        match &self.kind {
+            SelectMenuKind::String => {
+                let mut rows: Vec<CreateActionRow> = self
+                    .page_options()
+                    .into_iter()
                     .chunks(25)
                     .into_iter()
-                    .take(5)
                     .enumerate()
                     .map(|(i, chunk)| {
-                        CreateActionRow::SelectMenu(CreateSelectMenu::new(
-                            format!(
-                                "{}-{}",
-                                self.custom_id
-                                    .as_ref()
-                                    .map_or("select-row", |s| s.as_str()),
-                                i
-                            ),
+                        self.create_menu(
+                            i,
                             CreateSelectMenuKind::String {
                                 options: chunk
                                     .map(|(key, value)| CreateSelectMenuOption::new(value, key))
                                     .collect(),
                             },
-                        ))
+                        )
                     })
-                    .collect(),
-            );
+                    .collect();
+                if self.page_count() > 1 {
+                    rows.push(self.page_buttons());
+                }
+                rows
+            },
+            SelectMenuKind::Role => {
+                vec![self.create_menu(
+                    0,
+                    CreateSelectMenuKind::Role {
+                        default_roles: None,
+                    },
+                )]
+            },
+            SelectMenuKind::Channel(channel_types) => {
+                vec![self.create_menu(
+                    0,
+                    CreateSelectMenuKind::Channel {
+                        channel_types: Some(channel_types.clone()).filter(|t| !t.is_empty()),
+                        default_channels: None,
+                    },
+                )]
+            },
+            SelectMenuKind::User => {
+                vec![self.create_menu(
+                    0,
+                    CreateSelectMenuKind::User {
+                        default_users: None,
+                    },
+                )]
+            },
+            SelectMenuKind::Mentionable => {
+                vec![self.create_menu(
+                    0,
+                    CreateSelectMenuKind::Mentionable {
+                        default_users: None,
+                        default_roles: None,
+                    },
+                )]
+            },
+        }
+    }
+
+    async fn send(&self, http: impl AsRef<Http>, interaction: &Interaction) {
+        let components = self.components();
+
+        let mut description = self
+            .description
+            .clone()
+            .unwrap_or_else(|| "Select an option".to_owned());
+        if self.page_count() > 1 {
+            write!(
+                description,
+                "\n-# page {} of {}",
+                self.page + 1,
+                self.page_count()
+            )
+            .expect("write to String: can't fail");
+        }
+
+        let mut resp = InteractionResponseBuilder::default()
+            .content(description)
+            .components(components);
 
         if self.ephemeral {
             resp = resp.make_ephemeral();
@@ -178,14 +441,55 @@ impl SelectMenu {
         respond_to_interaction(http, interaction, resp, true).await;
     }
 
+    /// A select menu where a single option gets picked.
     pub fn builder<F>(handler: F) -> SelectMenuBuilder
     where
         F: Fn((String, String)) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        SelectMenuBuilder::new(move |chosen: Vec<(String, String)>| {
+            match chosen
+                .into_iter()
+                .next()
+            {
+                Some(choice) => handler(choice),
+                None => Box::pin(async {}),
+            }
+        })
+    }
+
+    /// A select menu where multiple options can be picked at once, set the
+    /// bounds with `set_min_values` and `set_max_values`.
+    pub fn multi_builder<F>(handler: F) -> SelectMenuBuilder
+    where
+        F: Fn(Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
     {
         SelectMenuBuilder::new(handler)
     }
 }
 
+/// Show a page of a menu again, boxed so the menu can listen for its page
+/// buttons from within a handler.
+fn show_page(
+    menu: SelectMenu,
+    http: Arc<Http>,
+    interaction: Interaction,
+    data: Arc<RwLock<TypeMap>>,
+) -> BoxFuture<'static, ()> {
+    Box::pin(async move {
+        menu.listen(http, &interaction, data)
+            .await;
+    })
+}
+
+fn id_pairs<T: ToString>(ids: Vec<T>) -> Vec<(String, String)> {
+    ids.into_iter()
+        .map(|id| {
+            let id = id.to_string();
+            (id.clone(), id)
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct SelectMenuBuilder {
     inner: SelectMenu,
@@ -194,7 +498,7 @@ pub struct SelectMenuBuilder {
 impl SelectMenuBuilder {
     pub fn new<F>(handler: F) -> Self
     where
-        F: Fn((String, String)) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+        F: Fn(Vec<(String, String)>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
     {
         Self {
             inner: SelectMenu {
@@ -204,23 +508,47 @@ impl SelectMenuBuilder {
                 custom_id: None,
                 user_id: None,
                 options: HashMap::new(),
+                kind: SelectMenuKind::String,
+                min_values: None,
+                max_values: None,
+                timeout: SELECT_MENU_TIMEOUT,
+                page: 0,
             },
         }
     }
 
     pub fn build(self) -> Result<SelectMenu, Error> {
-        if self
+        let option_count = self
             .inner
             .options
-            .is_empty()
-            || self
-                .inner
-                .options
-                .len()
-                > 125
+            .len();
+        if self
+            .inner
+            .kind
+            == SelectMenuKind::String
         {
+            if option_count == 0 {
+                return Err(Error::Other(
+                    "A select menu needs at least one option",
+                ));
+            }
+        } else if option_count > 0 {
+            return Err(Error::Other(
+                "Only string select menus can have options",
+            ));
+        }
+
+        let min = self
+            .inner
+            .min_values
+            .unwrap_or(1);
+        let max = self
+            .inner
+            .max_values
+            .unwrap_or(1);
+        if min > max || max == 0 || max > 25 {
             return Err(Error::Other(
-                "Unsupported amount of options in a select menu",
+                "The maximum amount of values to select has to be between 1 and 25, and at least the minimum",
             ));
         }
 
@@ -247,6 +575,13 @@ impl SelectMenuBuilder {
         self
     }
 
+    #[allow(dead_code)]
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.inner
+            .timeout = timeout;
+        self
+    }
+
     pub fn set_user(mut self, user_id: UserId) -> Self {
         self.inner
             .user_id = Some(user_id);
@@ -259,9 +594,144 @@ impl SelectMenuBuilder {
         self
     }
 
+    pub fn set_kind(mut self, kind: SelectMenuKind) -> Self {
+        self.inner
+            .kind = kind;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn set_min_values(mut self, min: u8) -> Self {
+        self.inner
+            .min_values = Some(min);
+        self
+    }
+
+    pub fn set_max_values(mut self, max: u8) -> Self {
+        self.inner
+            .max_values = Some(max);
+        self
+    }
+
     pub fn make_ephemeral(mut self) -> Self {
         self.inner
             .ephemeral = true;
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu(kind: SelectMenuKind) -> SelectMenuBuilder {
+        SelectMenu::builder(|_| Box::pin(async {}))
+            .set_custom_id("test")
+            .set_kind(kind)
+    }
+
+    #[test]
+    fn native_menus_need_no_options() {
+        assert!(menu(SelectMenuKind::Role)
+            .build()
+            .is_ok());
+        assert!(menu(SelectMenuKind::String)
+            .build()
+            .is_err());
+        assert!(menu(SelectMenuKind::Channel(vec![
+            ChannelType::Text
+        ]))
+        .set_options(HashMap::from([(
+            "a".to_owned(),
+            "b".to_owned()
+        )]))
+        .build()
+        .is_err());
+    }
+
+    #[test]
+    fn options_past_a_message_get_pages() {
+        let options = |count: usize| {
+            (0..count)
+                .map(|i| {
+                    (
+                        format!("{i:03}"),
+                        format!("option {i:03}"),
+                    )
+                })
+                .collect::<HashMap<String, String>>()
+        };
+
+        let small = menu(SelectMenuKind::String)
+            .set_options(options(125))
+            .build()
+            .unwrap();
+        assert_eq!(small.page_count(), 1);
+        assert_eq!(
+            small
+                .page_options()
+                .len(),
+            125
+        );
+
+        let mut large = menu(SelectMenuKind::String)
+            .set_options(options(260))
+            .build()
+            .unwrap();
+        assert_eq!(large.page_count(), 3);
+        large.page = 2;
+        let last = large.page_options();
+        assert_eq!(last.len(), 60);
+        assert_eq!(last[0].0, "200");
+    }
+
+    #[test]
+    fn value_bounds() {
+        assert!(menu(SelectMenuKind::Role)
+            .set_max_values(25)
+            .build()
+            .is_ok());
+        assert!(menu(SelectMenuKind::Role)
+            .set_max_values(26)
+            .build()
+            .is_err());
+        assert!(menu(SelectMenuKind::Role)
+            .set_min_values(0)
+            .set_max_values(0)
+            .build()
+            .is_err());
+        assert!(menu(SelectMenuKind::Role)
+            .set_min_values(3)
+            .set_max_values(2)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn user_and_mentionable_menus() {
+        for (kind, component_type) in [
+            (SelectMenuKind::User, 5),
+            (SelectMenuKind::Mentionable, 7),
+        ] {
+            let built = menu(kind)
+                .set_max_values(10)
+                .build()
+                .unwrap();
+            let rows = serde_json::to_value(built.components()).unwrap();
+            let select = &rows[0]["components"][0];
+
+            assert_eq!(
+                rows.as_array()
+                    .unwrap()
+                    .len(),
+                1
+            );
+            assert_eq!(select["type"], component_type);
+            assert_eq!(select["custom_id"], "test-0");
+            assert_eq!(select["max_values"], 10);
+            assert!(select
+                .get("options")
+                .is_none());
+        }
+    }
+}
@@ -1,7 +1,14 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
 
 use futures::future::BoxFuture;
 use itertools::Itertools;
+use okto_framework::structs::ComponentId;
 use serenity::{
     builder::{
         CreateActionRow,
@@ -12,15 +19,22 @@ use serenity::{
         EditInteractionResponse,
     },
     cache::Cache,
-    http::Http,
+    http::{
+        CacheHttp,
+        Http,
+    },
     model::{
         application::{
             ButtonStyle,
             CommandInteraction,
             ComponentInteraction,
             Interaction,
+            ModalInteraction,
+        },
+        channel::{
+            Message,
+            ReactionType,
         },
-        channel::ReactionType,
         id::{
             MessageId,
             UserId,
@@ -36,7 +50,13 @@ use serenity::{
 
 use crate::{
     models::caches::EmbedSessionsKey,
-    utils::error_log,
+    utils::{
+        constants::{
+            EMBED_SESSION_TIMEOUT,
+            INTERACTION_TOKEN_LIFETIME,
+        },
+        error_log,
+    },
 };
 
 type Handler = dyn Fn(ComponentInteraction) -> BoxFuture<'static, ()> + Send + Sync;
@@ -51,10 +71,26 @@ pub struct ButtonType {
 #[derive(Clone)]
 pub struct StatefulOption {
     pub button: ButtonType,
-    pub handler: Arc<Handler>,
+    pub handler: Option<Arc<Handler>>,
+    pub route: Option<ComponentId>,
     pub is_update: bool,
 }
 
+impl StatefulOption {
+    fn custom_id(&self) -> String {
+        self.route
+            .as_ref()
+            .map_or_else(
+                || {
+                    self.button
+                        .label
+                        .clone()
+                },
+                ToString::to_string,
+            )
+    }
+}
+
 #[derive(Clone)]
 pub struct StatefulEmbed {
     pub inner: CreateEmbed,
@@ -91,24 +127,46 @@ impl StatefulEmbed {
     where
         F: Fn(ComponentInteraction) -> BoxFuture<'static, ()> + Send + Sync + 'static,
     {
-        let full_name = if let Some(e) = &button.emoji {
-            format!("{e} {name}")
-        } else {
-            name.to_owned()
-        };
-        self.inner = self
-            .inner
-            .field(full_name, value, inline);
+        self.push_field(name, value, inline, button);
         self.options
             .push(StatefulOption {
                 button: button.clone(),
-                handler: Arc::new(Box::new(handler)),
+                handler: Some(Arc::new(Box::new(handler))),
+                route: None,
                 is_update: true,
             });
 
         self
     }
 
+    /// Adds a field with a button that gets handled by the [`Component`]
+    /// registered for the prefix of the route, this keeps working after a
+    /// restart.
+    ///
+    /// [`Component`]: okto_framework::structs::Component
+    pub fn add_route_field(
+        mut self,
+        name: &str,
+        value: &str,
+        inline: bool,
+        button: &ButtonType,
+        route: ComponentId,
+    ) -> Self {
+        self.push_field(name, value, inline, button);
+        self.add_route(button, route);
+
+        self
+    }
+
+    fn push_field(&mut self, name: &str, value: &str, inline: bool, button: &ButtonType) {
+        let full_name = if let Some(e) = &button.emoji {
+            format!("{e} {name}")
+        } else {
+            name.to_owned()
+        };
+        self.inner = std::mem::take(&mut self.inner).field(full_name, value, inline);
+    }
+
     pub fn add_option<F>(&mut self, button: &ButtonType, handler: F) -> &mut Self
     where
         F: Fn(ComponentInteraction) -> BoxFuture<'static, ()> + Send + Sync + 'static,
@@ -116,28 +174,43 @@ impl StatefulEmbed {
         self.options
             .push(StatefulOption {
                 button: button.clone(),
-                handler: Arc::new(Box::new(handler)),
+                handler: Some(Arc::new(Box::new(handler))),
+                route: None,
                 is_update: true,
             });
 
         self
     }
 
-    pub fn add_non_update_option<F>(&mut self, button: &ButtonType, handler: F) -> &mut Self
-    where
-        F: Fn(ComponentInteraction) -> BoxFuture<'static, ()> + Send + Sync + 'static,
-    {
+    /// Adds a button that gets handled by the [`Component`] registered for the
+    /// prefix of the route instead of by a handler kept in memory.
+    ///
+    /// [`Component`]: okto_framework::structs::Component
+    pub fn add_route(&mut self, button: &ButtonType, route: ComponentId) -> &mut Self {
+        debug_assert!(
+            route
+                .to_string()
+                .len()
+                <= ComponentId::MAX_LENGTH,
+            "custom_id of route is too long: {route}"
+        );
+
         self.options
             .push(StatefulOption {
                 button: button.clone(),
-                handler: Arc::new(Box::new(handler)),
-                is_update: false,
+                handler: None,
+                route: Some(route),
+                is_update: true,
             });
 
         self
     }
 
     fn get_components(&self) -> Vec<CreateActionRow> {
+        self.build_components(false)
+    }
+
+    fn build_components(&self, disabled: bool) -> Vec<CreateActionRow> {
         let mut components = Vec::new();
 
         for option_batch in &self
@@ -147,23 +220,19 @@ impl StatefulEmbed {
         {
             let mut row = Vec::new();
             for option in option_batch {
-                let mut button = CreateButton::new(
-                    option
-                        .button
-                        .label
-                        .to_string(),
-                )
-                .style(
-                    option
-                        .button
-                        .style,
-                )
-                .label(
-                    option
-                        .button
-                        .label
-                        .to_string(),
-                );
+                let mut button = CreateButton::new(option.custom_id())
+                    .style(
+                        option
+                            .button
+                            .style,
+                    )
+                    .label(
+                        option
+                            .button
+                            .label
+                            .to_string(),
+                    )
+                    .disabled(disabled);
 
                 if let Some(e) = &option
                     .button
@@ -190,6 +259,7 @@ impl StatefulEmbed {
                 .http
                 .clone();
             session.set_embed(self.clone());
+            session.touch();
 
             session
                 .interaction
@@ -228,14 +298,87 @@ impl StatefulEmbed {
     }
 }
 
+#[derive(Clone)]
+pub enum SessionInteraction {
+    Command(CommandInteraction),
+    Component(ComponentInteraction),
+    Modal(ModalInteraction),
+}
+
+impl SessionInteraction {
+    pub async fn edit_response(
+        &self,
+        http: impl CacheHttp,
+        builder: EditInteractionResponse,
+    ) -> Result<Message> {
+        match self {
+            Self::Command(cmd) => {
+                cmd.edit_response(http, builder)
+                    .await
+            },
+            Self::Component(comp) => {
+                comp.edit_response(http, builder)
+                    .await
+            },
+            Self::Modal(modal) => {
+                modal
+                    .edit_response(http, builder)
+                    .await
+            },
+        }
+    }
+
+    pub async fn get_response(&self, http: impl AsRef<Http>) -> Result<Message> {
+        match self {
+            Self::Command(cmd) => {
+                cmd.get_response(http)
+                    .await
+            },
+            Self::Component(comp) => {
+                comp.get_response(http)
+                    .await
+            },
+            Self::Modal(modal) => {
+                modal
+                    .get_response(http)
+                    .await
+            },
+        }
+    }
+
+    pub async fn delete_response(&self, http: impl AsRef<Http>) -> Result<()> {
+        match self {
+            Self::Command(cmd) => {
+                cmd.delete_response(http)
+                    .await
+            },
+            Self::Component(comp) => {
+                comp.delete_response(http)
+                    .await
+            },
+            Self::Modal(modal) => {
+                modal
+                    .delete_response(http)
+                    .await
+            },
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct EmbedSession {
     pub current_state: Option<StatefulEmbed>,
-    pub interaction: CommandInteraction,
+    pub interaction: SessionInteraction,
     pub http: Arc<Http>,
     pub data: Arc<RwLock<TypeMap>>,
     pub cache: Arc<Cache>,
     pub author: UserId,
+    open: bool,
+    timeout: Duration,
+    expires_at: Instant,
+    /// When the interaction the session edits its message through was
+    /// created, its token is only valid for a limited time.
+    interaction_at: Instant,
 }
 
 impl EmbedSession {
@@ -257,6 +400,50 @@ impl EmbedSession {
             )
             .await?;
 
+        Ok(Self::deferred(ctx, interaction))
+    }
+
+    /// Creates a session for a command that has already been deferred.
+    pub fn deferred(ctx: &Context, interaction: CommandInteraction) -> Arc<RwLock<Self>> {
+        Arc::new(RwLock::new(Self {
+            http: ctx
+                .http
+                .clone(),
+            current_state: None,
+            author: interaction
+                .user
+                .id,
+            interaction: SessionInteraction::Command(interaction),
+            data: ctx
+                .data
+                .clone(),
+            cache: ctx
+                .cache
+                .clone(),
+            open: false,
+            timeout: EMBED_SESSION_TIMEOUT,
+            expires_at: Instant::now() + EMBED_SESSION_TIMEOUT,
+            interaction_at: Instant::now(),
+        }))
+    }
+
+    /// Picks a session back up from a button click on a message of an earlier
+    /// session, for example after a restart. When `acknowledge` is false the
+    /// interaction still has to be responded to, for example with a modal.
+    pub async fn from_component(
+        ctx: &Context,
+        interaction: ComponentInteraction,
+        acknowledge: bool,
+    ) -> Result<Arc<RwLock<Self>>> {
+        if acknowledge {
+            interaction
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+        }
+
         Ok(Arc::new(RwLock::new(Self {
             http: ctx
                 .http
@@ -265,80 +452,189 @@ impl EmbedSession {
             author: interaction
                 .user
                 .id,
-            interaction,
+            interaction: SessionInteraction::Component(interaction),
             data: ctx
                 .data
                 .clone(),
             cache: ctx
                 .cache
                 .clone(),
+            open: false,
+            timeout: EMBED_SESSION_TIMEOUT,
+            expires_at: Instant::now() + EMBED_SESSION_TIMEOUT,
+            interaction_at: Instant::now(),
         })))
     }
 
     fn set_embed(&mut self, em: StatefulEmbed) {
         self.current_state = Some(em)
     }
+
+    /// Edit the message through a newer interaction from now on, for example
+    /// the submit of a modal that was opened from the menu.
+    pub fn set_interaction(&mut self, interaction: SessionInteraction) -> &mut Self {
+        self.interaction = interaction;
+        self.interaction_at = Instant::now();
+        self.touch();
+        self
+    }
+
+    /// Let everyone use the buttons of the session instead of only its author.
+    pub fn set_open(&mut self, open: bool) -> &mut Self {
+        self.open = open;
+        self
+    }
+
+    fn touch(&mut self) {
+        // past the lifetime of the token the buttons couldn't be disabled anymore
+        self.expires_at =
+            (Instant::now() + self.timeout).min(self.interaction_at + INTERACTION_TOKEN_LIFETIME);
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Instant::now()
+    }
+
+    /// Tell the user the menu has expired and disable all its buttons.
+    async fn expire(&self) -> Result<()> {
+        let components = self
+            .current_state
+            .as_ref()
+            .map(|embed| embed.build_components(true))
+            .unwrap_or_default();
+
+        self.interaction
+            .edit_response(
+                &self.http,
+                EditInteractionResponse::new()
+                    .content("This menu has expired, run the command again to get a new one")
+                    .components(components),
+            )
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Get the user that invoked the command the clicked menu belongs to, if
+/// discord still tells us.
+pub fn session_author(interaction: &ComponentInteraction) -> Option<UserId> {
+    interaction
+        .message
+        .interaction
+        .as_ref()
+        .map(|i| {
+            i.user
+                .id
+        })
+}
+
+/// Tell the user that clicked a routed button that this menu isn't theirs to
+/// use.
+pub async fn deny_route(ctx: &Context, interaction: &ComponentInteraction) -> Result<()> {
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .ephemeral(true)
+                    .content("This menu belongs to someone else, run the command yourself to get your own"),
+            ),
+        )
+        .await
+}
+
+/// Tell the user that clicked a button of an expired menu to get a new one.
+async fn deny_expired(ctx: &Context, interaction: &ComponentInteraction) -> Result<()> {
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .ephemeral(true)
+                    .content("This menu has expired, run the command again to get a new one"),
+            ),
+        )
+        .await
 }
 
 pub async fn on_button_click(ctx: &Context, full_interaction: &Interaction) {
-    if let Interaction::Component(interaction) = full_interaction {
-        let handler = {
-            let embed_session = ctx
-                .data
-                .read()
-                .await;
+    let Interaction::Component(interaction) = full_interaction else {
+        return;
+    };
 
-            if let Some(cache) = embed_session.get::<EmbedSessionsKey>() {
-                if let Some(session_lock) = cache.get(
+    let session_lock = ctx
+        .data
+        .read()
+        .await
+        .get::<EmbedSessionsKey>()
+        .and_then(|cache| {
+            cache
+                .get(
                     &interaction
                         .message
                         .id,
-                ) {
-                    let session = session_lock
-                        .read()
-                        .await;
-                    if session.author
-                        != interaction
-                            .user
-                            .id
-                    {
-                        return;
-                    }
-
-                    session
-                        .current_state
-                        .as_ref()
-                        .and_then(|embed| {
-                            let mut handler: Option<(Arc<Handler>, bool)> = None;
-
-                            for opt in &embed.options {
-                                if opt
-                                    .button
-                                    .label
-                                    == interaction
-                                        .data
-                                        .custom_id
-                                {
-                                    handler = Some((
-                                        opt.handler
-                                            .clone(),
-                                        opt.is_update,
-                                    ));
-                                    break;
-                                }
-                            }
-
-                            handler
-                        })
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
+                )
+                .cloned()
+        });
+    let Some(session_lock) = session_lock else {
+        return;
+    };
+
+    let (handler, is_update, denied) = {
+        let session = session_lock
+            .read()
+            .await;
+
+        // routed buttons are handled by their component, even for expired sessions
+        let Some((handler, is_update)) = session
+            .current_state
+            .as_ref()
+            .and_then(|embed| {
+                embed
+                    .options
+                    .iter()
+                    .find(|opt| {
+                        opt.custom_id()
+                            == interaction
+                                .data
+                                .custom_id
+                    })
+                    .and_then(|opt| {
+                        opt.handler
+                            .clone()
+                            .map(|handler| (handler, opt.is_update))
+                    })
+            })
+        else {
+            return;
+        };
+
+        let denied = if !session.open
+            && session.author
+                != interaction
+                    .user
+                    .id
+        {
+            Some(deny_route(ctx, interaction).await)
+        } else if session.is_expired() {
+            Some(deny_expired(ctx, interaction).await)
+        } else {
+            None
         };
 
-        if let Some((handler, true)) = handler {
+        (handler, is_update, denied)
+    };
+
+    match denied {
+        Some(Err(e)) => {
+            error_log(
+                &ctx.http,
+                format!("Got error when responding to interaction: {e}",),
+            )
+            .await;
+        },
+        Some(Ok(())) => {},
+        None if is_update => {
             let r = interaction
                 .create_response(
                     &ctx.http,
@@ -353,11 +649,17 @@ pub async fn on_button_click(ctx: &Context, full_interaction: &Interaction) {
                 )
                 .await;
             } else {
+                // the token of the click outlives the one the session had so far
+                session_lock
+                    .write()
+                    .await
+                    .set_interaction(SessionInteraction::Component(
+                        interaction.clone(),
+                    ));
                 handler(interaction.clone()).await;
             }
-        } else if let Some((handler, false)) = handler {
-            handler(interaction.clone()).await;
-        }
+        },
+        None => handler(interaction.clone()).await,
     }
 }
 
@@ -371,3 +673,66 @@ pub async fn on_message_delete(ctx: &Context, message_id: MessageId) {
         cache.remove(&message_id);
     }
 }
+
+/// Remove all sessions that haven't been used within their timeout, disabling
+/// the buttons on their messages.
+pub async fn expire_sessions(data: &Arc<RwLock<TypeMap>>) {
+    // take a snapshot first, as `show` locks the session before the type map
+    let sessions = if let Some(cache) = data
+        .read()
+        .await
+        .get::<EmbedSessionsKey>()
+    {
+        cache
+            .iter()
+            .map(|(message_id, session)| (*message_id, session.clone()))
+            .collect::<Vec<_>>()
+    } else {
+        return;
+    };
+
+    let mut expired = Vec::new();
+    for (message_id, session) in sessions {
+        if session
+            .read()
+            .await
+            .is_expired()
+        {
+            expired.push((message_id, session));
+        }
+    }
+
+    if expired.is_empty() {
+        return;
+    }
+
+    if let Some(cache) = data
+        .write()
+        .await
+        .get_mut::<EmbedSessionsKey>()
+    {
+        expired.retain(|(message_id, session)| {
+            // the message might have gotten a new session in the meantime
+            if cache
+                .get(message_id)
+                .is_some_and(|current| Arc::ptr_eq(current, session))
+            {
+                cache.remove(message_id);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    for (_, session) in expired {
+        if let Err(e) = session
+            .read()
+            .await
+            .expire()
+            .await
+        {
+            eprintln!("Couldn't disable the buttons of an expired menu: {e}");
+        }
+    }
+}
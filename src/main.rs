@@ -22,6 +22,7 @@ use std::{
 };
 
 use commands::{
+    events::*,
     general::*,
     help::*,
     launches::*,
@@ -29,9 +30,11 @@ use commands::{
     reminders::*,
 };
 use models::caches::{
+    CatalogKey,
     CommandListKey,
     DatabaseKey,
     EmbedSessionsKey,
+    EventsCacheKey,
     InteractionKey,
     LaunchesCacheKey,
     PictureCacheKey,
@@ -49,6 +52,10 @@ use serenity::{
     },
 };
 use utils::{
+    catalog::{
+        load_catalog,
+        Catalog,
+    },
     error_log,
     preloading::preload_data,
 };
@@ -94,7 +101,7 @@ async fn main() {
         })
     });
 
-    let slash_framework = okto_framework::create_framework!(
+    let mut slash_framework = okto_framework::create_framework!(
         &token,
         application_id,
         // the commands:
@@ -103,6 +110,7 @@ async fn main() {
         info,
         websites,
         peopleinspace,
+        station,
         iss,
         exoplanet,
         earthpic,
@@ -112,21 +120,41 @@ async fn main() {
         curiosity,
         perseverance,
         nextlaunch,
+        countdown,
         listlaunches,
         launchinfo,
+        pastlaunches,
+        launchstats,
+        vehicle,
+        pad,
+        events,
         filtersinfo,
         notifychannel,
         notifyme
     );
+    slash_framework
+        .add_component(&LAUNCHES_COMPONENT)
+        .expect("Can't add launches component");
+    slash_framework
+        .add_component(&REMINDERS_COMPONENT)
+        .expect("Can't add reminders component");
+    slash_framework
+        .add_component(&reminders::LAUNCH_PINGS_COMPONENT)
+        .expect("Can't add launch pings component");
+
+    let events_cache = Arc::new(RwLock::new(Vec::new()));
+    let catalog = Arc::new(RwLock::new(Catalog::default()));
 
     let data_map = {
         println!("Preparing caches");
         let mut data = TypeMap::new();
         data.insert::<CommandListKey>(slash_framework.get_command_list());
         data.insert::<EmbedSessionsKey>(HashMap::new());
-        data.insert::<InteractionKey>(models::caches::InteractionHandlerHolder(Vec::new()));
+        data.insert::<InteractionKey>(models::caches::InteractionHandlerHolder::default());
         data.insert::<PictureCacheKey>(preload_data().await);
         data.insert::<LaunchesCacheKey>(Arc::new(RwLock::new(Vec::new())));
+        data.insert::<EventsCacheKey>(events_cache.clone());
+        data.insert::<CatalogKey>(catalog.clone());
         data.insert::<DatabaseKey>(
             MongoClient::with_uri_str(&mongo_uri)
                 .await
@@ -178,11 +206,26 @@ async fn main() {
     } else {
         panic!("No launches cache key")
     };
+    load_catalog(&db_clone, &catalog).await;
+    tokio::spawn(reminders::countdown_tracking(
+        http_clone.clone(),
+        launches_cache_clone.clone(),
+        db_clone.clone(),
+    ));
     tokio::spawn(reminders::reminder_tracking(
         http_clone,
         launches_cache_clone,
+        events_cache,
+        catalog,
         db_clone,
     ));
+    tokio::spawn(
+        events::interaction_handler::session_sweeper(
+            client
+                .data
+                .clone(),
+        ),
+    );
 
     println!("Starting the bot");
     if let Err(why) = client
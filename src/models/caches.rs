@@ -14,12 +14,19 @@ use serenity::{
 };
 
 use super::{
+    events::EventData,
     launches::LaunchData,
     pictures::MarsRoverPicture,
 };
-use crate::events::{
-    interaction_handler::InteractionHandler,
-    statefulembed::EmbedSession,
+use crate::{
+    events::{
+        interaction_handler::{
+            custom_id_prefixes,
+            InteractionHandler,
+        },
+        statefulembed::EmbedSession,
+    },
+    utils::catalog::Catalog,
 };
 
 #[derive(Debug, Clone)]
@@ -41,6 +48,18 @@ impl TypeMapKey for LaunchesCacheKey {
     type Value = Arc<RwLock<Vec<LaunchData>>>;
 }
 
+pub struct EventsCacheKey;
+
+impl TypeMapKey for EventsCacheKey {
+    type Value = Arc<RwLock<Vec<EventData>>>;
+}
+
+pub struct CatalogKey;
+
+impl TypeMapKey for CatalogKey {
+    type Value = Arc<RwLock<Catalog>>;
+}
+
 pub struct EmbedSessionsKey;
 
 impl TypeMapKey for EmbedSessionsKey {
@@ -53,7 +72,79 @@ impl TypeMapKey for InteractionKey {
     type Value = InteractionHandlerHolder;
 }
 
-pub struct InteractionHandlerHolder(pub Vec<InteractionHandler>);
+/// The handlers waiting for a select menu or modal to be answered, indexed by
+/// their `custom_id` so an interaction only has to be checked against the
+/// handlers that could match it.
+#[derive(Default)]
+pub struct InteractionHandlerHolder {
+    by_custom_id: HashMap<String, Vec<InteractionHandler>>,
+    other: Vec<InteractionHandler>,
+}
+
+impl InteractionHandlerHolder {
+    pub fn push(&mut self, handler: InteractionHandler) {
+        let handlers = if let Some(custom_id) = handler.custom_id() {
+            self.by_custom_id
+                .entry(custom_id.to_owned())
+                .or_default()
+        } else {
+            &mut self.other
+        };
+
+        // opening the same menu again replaces the one that never got answered
+        handlers.retain(|h| *h != handler);
+        handlers.push(handler);
+    }
+
+    /// Get the unexpired handlers that could match an interaction with the
+    /// given `custom_id`, a handler matches on the full id or on any part of
+    /// it that ends right before a '-'.
+    pub fn candidates(&self, custom_id: &str) -> Vec<InteractionHandler> {
+        let mut found = self
+            .other
+            .clone();
+
+        for prefix in custom_id_prefixes(custom_id) {
+            if let Some(handlers) = self
+                .by_custom_id
+                .get(prefix)
+            {
+                found.extend(
+                    handlers
+                        .iter()
+                        .cloned(),
+                );
+            }
+        }
+
+        found.retain(|h| !h.is_expired());
+        found
+    }
+
+    /// Drop the handlers that handled an interaction, the ones that replaced
+    /// them while handling it are kept.
+    pub fn remove(&mut self, handled: &[InteractionHandler]) {
+        self.retain(|h| {
+            !handled
+                .iter()
+                .any(|done| done.is_same(h))
+        });
+    }
+
+    pub fn remove_expired(&mut self) {
+        self.retain(|h| !h.is_expired());
+    }
+
+    fn retain(&mut self, f: impl Fn(&InteractionHandler) -> bool) {
+        self.other
+            .retain(&f);
+        self.by_custom_id
+            .retain(|_, handlers| {
+                handlers.retain(&f);
+                !handlers.is_empty()
+            });
+    }
+}
 
 pub struct DatabaseKey;
 
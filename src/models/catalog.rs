@@ -0,0 +1,23 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// A launch provider that has been seen in launch library data.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Agency {
+    pub ll_id: i32,
+    pub name: String,
+    /// The filter name for the agency, made from its name.
+    pub key: String,
+}
+
+/// A rocket configuration that has been seen in launch library data.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Vehicle {
+    pub ll_id: i32,
+    pub name: String,
+    pub family: String,
+    /// The filter name for the whole family, made from the family name.
+    pub key: String,
+}
@@ -0,0 +1,25 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serenity::model::id::{
+    ChannelId,
+    MessageId,
+};
+
+/// A message counting down to a launch, which keeps getting edited until the
+/// launch is over.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Countdown {
+    pub channel: ChannelId,
+    pub message: MessageId,
+    /// The `ll_id` of the launch.
+    pub launch: String,
+    /// The NET of the launch the last time it was upcoming, as a timestamp.
+    #[serde(default)]
+    pub net: Option<i64>,
+    /// How many launch fetches in a row the launch wasn't upcoming in, it can
+    /// drop out of them for a while without being over.
+    #[serde(default)]
+    pub missed_fetches: i32,
+}
@@ -0,0 +1,136 @@
+use chrono::NaiveDateTime;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::utils::serde::string_option;
+
+#[derive(Deserialize)]
+pub struct AstronautContainer {
+    pub results: Vec<AstronautInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AstronautInfo {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub nationality: String,
+    pub agency: Option<AgencyRef>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub last_flight: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AgencyRef {
+    pub name: String,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub abbrev: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StationContainer {
+    pub results: Vec<StationInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StationInfo {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub orbit: String,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub founded: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub owners: Vec<AgencyRef>,
+    #[serde(default)]
+    pub active_expeditions: Vec<ExpeditionInfo>,
+    #[serde(default)]
+    pub docking_location: Vec<DockingLocationInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExpeditionInfo {
+    pub name: String,
+    #[serde(default)]
+    pub crew: Vec<CrewInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CrewInfo {
+    pub role: Option<CrewRole>,
+    pub astronaut: AstronautInfo,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CrewRole {
+    pub role: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DockingLocationInfo {
+    pub name: String,
+    pub docked: Option<DockingEventInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct DockingEventContainer {
+    pub results: Vec<DockingEventInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DockingEventInfo {
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub docking: Option<String>,
+    pub flight_vehicle: Option<FlightVehicleInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FlightVehicleInfo {
+    pub spacecraft: Option<SpacecraftInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpacecraftInfo {
+    pub name: String,
+}
+
+/// A person that is in space right now.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Astronaut {
+    pub name: String,
+    pub agency: Option<String>,
+    pub nationality: String,
+    /// When the flight they are on now launched.
+    pub launched: Option<NaiveDateTime>,
+    pub station: Option<String>,
+    pub expedition: Option<String>,
+    pub role: Option<String>,
+}
+
+/// A crewed space station with who is on board and what is docked to it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Station {
+    pub name: String,
+    pub orbit: String,
+    pub founded: Option<String>,
+    pub description: String,
+    pub image: Option<String>,
+    pub owners: Vec<String>,
+    pub expeditions: Vec<String>,
+    pub crew: Vec<Astronaut>,
+    /// The vehicles that are docked, with the port they are docked at.
+    pub docked: Vec<(String, String)>,
+    /// The vehicles that will visit, with when they will dock.
+    pub visits: Vec<(String, Option<NaiveDateTime>)>,
+}
@@ -0,0 +1,60 @@
+use chrono::NaiveDateTime;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::utils::serde::{
+    datetime_formatting,
+    string_option,
+};
+
+#[derive(Deserialize)]
+pub struct EventContainer {
+    pub results: Vec<EventInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EventInfo {
+    pub id: i32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub event_type: EventTypeInfo,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub location: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub news_url: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub video_url: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub feature_image: Option<String>,
+    #[serde(with = "datetime_formatting")]
+    pub date: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EventTypeInfo {
+    pub id: i32,
+    pub name: String,
+}
+
+/// A space event that isn't a launch, like a spacewalk, docking or static fire.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EventData {
+    pub ll_id: i32,
+    pub name: String,
+    pub event_type: String,
+    pub description: String,
+    pub location: Option<String>,
+    pub news_url: Option<String>,
+    pub video_url: Option<String>,
+    pub image: Option<String>,
+    #[serde(with = "datetime_formatting")]
+    pub date: NaiveDateTime,
+}
@@ -20,6 +20,8 @@ use crate::utils::serde::{
 #[derive(Deserialize)]
 pub struct LaunchContainer {
     pub results: Vec<LaunchInfo>,
+    #[serde(default)]
+    pub next: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -31,7 +33,14 @@ pub struct LaunchData {
     pub payload: String,
     pub vid_urls: Vec<VidURL>,
     pub vehicle: String,
+    #[serde(default)]
+    pub vehicle_family: String,
+    /// The launch library id of the rocket configuration.
+    #[serde(default)]
+    pub vehicle_id: Option<i32>,
     pub location: String,
+    #[serde(default)]
+    pub pad: Option<PadData>,
     pub rocket_img: Option<String>,
     #[serde(with = "datetime_formatting")]
     pub net: NaiveDateTime,
@@ -40,6 +49,65 @@ pub struct LaunchData {
     pub mission_type: String,
     pub mission_description: String,
     pub lsp: String,
+    /// The launch library id of the launch provider.
+    #[serde(default)]
+    pub lsp_id: Option<i32>,
+    #[serde(default)]
+    pub failreason: Option<String>,
+    #[serde(default)]
+    pub holdreason: Option<String>,
+    #[serde(default)]
+    pub probability: Option<i32>,
+    #[serde(default)]
+    pub weather_concerns: Option<String>,
+    /// How precise the NET is, like "Hour" or "Month".
+    #[serde(default)]
+    pub net_precision: Option<String>,
+    #[serde(default)]
+    pub boosters: Vec<BoosterData>,
+}
+
+/// An empty launch for tests to fill in the parts they look at.
+#[cfg(test)]
+impl Default for LaunchData {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            ll_id: String::new(),
+            launch_name: String::new(),
+            status: LaunchStatus::Go,
+            payload: String::new(),
+            vid_urls: Vec::new(),
+            vehicle: String::new(),
+            vehicle_family: String::new(),
+            vehicle_id: None,
+            location: String::new(),
+            pad: None,
+            rocket_img: None,
+            net: NaiveDateTime::default(),
+            launch_window: Duration::zero(),
+            mission_type: String::new(),
+            mission_description: String::new(),
+            lsp: String::new(),
+            lsp_id: None,
+            failreason: None,
+            holdreason: None,
+            probability: None,
+            weather_concerns: None,
+            net_precision: None,
+            boosters: Vec::new(),
+        }
+    }
+}
+
+/// A finished launch as it is kept in the launch history.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LaunchRecord {
+    #[serde(flatten)]
+    pub launch: LaunchData,
+    /// How far the NET moved from when the launch was first seen, in seconds.
+    #[serde(default)]
+    pub net_slip: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -61,12 +129,63 @@ pub struct LaunchInfo {
     pub vid_urls: Option<Vec<VidURL>>,
     #[serde(with = "string_option")]
     pub image: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub failreason: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub holdreason: Option<String>,
+    /// The chance of the weather allowing the launch, in percent.
+    #[serde(default)]
+    pub probability: Option<i32>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub weather_concerns: Option<String>,
+    #[serde(default)]
+    pub net_precision: Option<NetPrecision>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NetPrecision {
+    pub id: i32,
+    pub name: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct RocketInfo {
     pub id: i32,
     pub configuration: RocketConfig,
+    #[serde(default)]
+    pub launcher_stage: Vec<LauncherStage>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LauncherStage {
+    pub id: i32,
+    #[serde(rename = "type")]
+    pub stage_type: String,
+    pub reused: Option<bool>,
+    pub launcher_flight_number: Option<i32>,
+    pub launcher: LauncherDetail,
+    pub landing: Option<LandingInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LandingInfo {
+    pub id: i32,
+    pub attempt: Option<bool>,
+    pub success: Option<bool>,
+    #[serde(default)]
+    pub description: String,
+    pub location: Option<LandingLocation>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LandingLocation {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub abbrev: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -110,11 +229,35 @@ pub struct PadInfo {
     pub location: LocationInfo,
     #[serde(with = "string_option")]
     pub map_url: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub map_image: Option<String>,
     #[serde(with = "string_option")]
     pub info_url: Option<String>,
     pub total_launch_count: i32,
 }
 
+#[derive(Deserialize)]
+pub struct PadContainer {
+    pub results: Vec<PadInfo>,
+}
+
+/// The pad of a launch with the statistics of it and the site it is on.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PadData {
+    pub id: i32,
+    pub name: String,
+    pub location: String,
+    pub country_code: String,
+    pub latitude: Option<String>,
+    pub longitude: Option<String>,
+    pub map_url: Option<String>,
+    pub map_image: Option<String>,
+    pub launch_count: i32,
+    pub location_launch_count: i32,
+    pub location_landing_count: i32,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct MissionInfo {
     pub id: i32,
@@ -129,17 +272,27 @@ pub struct LauncherDetail {
     pub id: i32,
     #[serde(with = "string_option")]
     pub serial_number: Option<String>,
+    #[serde(default)]
     pub status: String,
+    #[serde(default)]
     pub details: String,
     pub flight_proven: bool,
+    #[serde(default)]
     #[serde(with = "string_option")]
     pub image_url: Option<String>,
+    #[serde(default)]
     pub successful_landings: i32,
+    #[serde(default)]
     pub attempted_landings: i32,
-    pub flights: String,
-    pub last_launch_date: String,
-    pub first_launch_date: String,
-    pub launcher_config: LauncherConfigDetail,
+    pub flights: Option<i32>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub last_launch_date: Option<String>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub first_launch_date: Option<String>,
+    /// Only given when getting the launcher itself, not as part of a launch.
+    pub launcher_config: Option<LauncherConfigDetail>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -148,18 +301,47 @@ pub struct LauncherConfigDetail {
     pub name: String,
     pub family: String,
     pub full_name: String,
+    #[serde(default)]
     #[serde(with = "string_option")]
     pub image_url: Option<String>,
+    #[serde(default)]
     pub description: String,
     pub variant: String,
-    pub length: i32,
+    pub length: Option<f64>,
     pub max_stage: Option<i32>,
     pub min_stage: Option<i32>,
-    pub diameter: i32,
+    pub diameter: Option<f64>,
+    #[serde(default)]
     #[serde(with = "string_option")]
     pub info_url: Option<String>,
+    #[serde(default)]
     #[serde(with = "string_option")]
     pub wiki_url: Option<String>,
+    pub manufacturer: Option<AgencyInfo>,
+    #[serde(default)]
+    #[serde(with = "string_option")]
+    pub maiden_flight: Option<String>,
+    pub total_launch_count: Option<i32>,
+    pub successful_launches: Option<i32>,
+    pub failed_launches: Option<i32>,
+    pub pending_launches: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct LauncherConfigContainer {
+    pub results: Vec<LauncherConfigDetail>,
+}
+
+/// A booster of a launch, as shown with the launch information.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BoosterData {
+    pub stage_type: String,
+    pub serial: Option<String>,
+    pub flight_number: Option<i32>,
+    pub flight_proven: bool,
+    pub landing_attempt: Option<bool>,
+    pub landing_success: Option<bool>,
+    pub landing_location: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -194,6 +376,14 @@ impl LaunchStatus {
             LaunchStatus::PartialFailure => "Partial Failure",
         }
     }
+
+    /// Whether the launch has happened and its outcome is known.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            LaunchStatus::Success | LaunchStatus::Failure | LaunchStatus::PartialFailure
+        )
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -1,4 +1,8 @@
 pub mod caches;
+pub mod catalog;
+pub mod countdowns;
+pub mod crew;
+pub mod events;
 pub mod launches;
 pub mod pictures;
 pub mod reminders;
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use chrono::Duration;
 use regex::Regex;
 use serde::{
@@ -28,6 +30,7 @@ pub struct ChannelReminder {
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // every toggle of the settings menu is a bool
 pub struct GuildSettings {
     pub guild: GuildId,
     #[serde(default)]
@@ -37,8 +40,25 @@ pub struct GuildSettings {
     #[serde(default)]
     #[serde(with = "serde_regex")]
     pub payload_filters: Vec<Regex>,
+    /// Serials of boosters to always get reminders and landing outcomes for.
+    #[serde(default)]
+    pub booster_subscriptions: Vec<String>,
+    /// Types of space events to not get reminders for.
+    #[serde(default)]
+    pub event_filters: Vec<String>,
     #[serde(default)]
     pub mentions: Vec<RoleId>,
+    /// Roles to mention for only some of the messages.
+    #[serde(default)]
+    pub mention_rules: Vec<MentionRule>,
+    /// Roles to mention in the reminders for launches of an agency or with a
+    /// vehicle.
+    #[serde(default)]
+    pub launch_mentions: Vec<LaunchMention>,
+    /// Whether the roles of the launch mentions replace the other roles for
+    /// the reminders of launches they are for.
+    #[serde(default)]
+    pub launch_mentions_only: bool,
     #[serde(default)]
     pub scrub_notifications: bool,
     #[serde(default)]
@@ -47,6 +67,193 @@ pub struct GuildSettings {
     pub mention_others: bool,
     #[serde(default)]
     pub notifications_channel: Option<ChannelId>,
+    /// Whether the messages about a launch go in a thread of their own.
+    #[serde(default)]
+    pub launch_threads: bool,
+    /// Custom wording for the reminders, scrubs and outcomes.
+    #[serde(default)]
+    pub templates: Vec<MessageTemplate>,
+    /// The color of the embeds of the reminders and notifications, the
+    /// standard color when not set.
+    #[serde(default)]
+    pub embed_color: Option<u32>,
+    /// Whether to leave the image of the rocket out of the reminders and
+    /// notifications.
+    #[serde(default)]
+    pub hide_thumbnail: bool,
+    /// The channels that get their messages as text instead of embeds, like
+    /// the ones that are bridged to IRC or Matrix.
+    #[serde(default)]
+    pub plain_text_channels: Vec<ChannelId>,
+}
+
+impl GuildSettings {
+    pub fn plain_text_in(&self, channel: ChannelId) -> bool {
+        self.plain_text_channels
+            .contains(&channel)
+    }
+}
+
+/// The settings of a guild that hasn't changed any, for tests to fill in the
+/// parts they look at.
+#[cfg(test)]
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            guild: GuildId::new(429307670730637312),
+            filters: Vec::new(),
+            allow_filters: Vec::new(),
+            payload_filters: Vec::new(),
+            booster_subscriptions: Vec::new(),
+            event_filters: Vec::new(),
+            mentions: Vec::new(),
+            mention_rules: Vec::new(),
+            launch_mentions: Vec::new(),
+            launch_mentions_only: false,
+            scrub_notifications: false,
+            outcome_notifications: false,
+            mention_others: false,
+            notifications_channel: None,
+            launch_threads: false,
+            templates: Vec::new(),
+            embed_color: None,
+            hide_thumbnail: false,
+            plain_text_channels: Vec::new(),
+        }
+    }
+}
+
+/// The kinds of messages a mention rule or template can be for.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionKind {
+    Reminder,
+    /// Delays of a launch and boosters getting assigned to it.
+    Scrub,
+    /// How a launch and the landings of its boosters went.
+    Outcome,
+}
+
+impl MentionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reminder => "reminder",
+            Self::Scrub => "scrub",
+            Self::Outcome => "outcome",
+        }
+    }
+}
+
+impl FromStr for MentionKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reminder" => Ok(Self::Reminder),
+            "scrub" => Ok(Self::Scrub),
+            "outcome" => Ok(Self::Outcome),
+            _ => Err("invalid mention kind"),
+        }
+    }
+}
+
+/// A role that only gets mentioned for one kind of message, unlike the roles
+/// in `mentions` which get mentioned for every reminder.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MentionRule {
+    pub role: RoleId,
+    pub kind: MentionKind,
+    /// The reminder this is for in minutes before launch, every reminder when
+    /// not set.
+    #[serde(default)]
+    pub minutes: Option<i64>,
+}
+
+impl MentionRule {
+    /// Whether the role gets mentioned for a message of the given kind,
+    /// `minutes` being the offset of a reminder.
+    pub fn applies(&self, kind: MentionKind, minutes: Option<i64>) -> bool {
+        self.kind == kind
+            && self
+                .minutes
+                .is_none_or(|m| minutes == Some(m))
+    }
+}
+
+/// The wording a guild wants for one kind of message, the parts can use
+/// placeholders like `{payload}` and the parts that aren't set keep the
+/// standard wording.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MessageTemplate {
+    pub kind: MentionKind,
+    /// A line of text above the embed.
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// What the filter of a launch mention is for.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMentionKind {
+    Agency,
+    Vehicle,
+}
+
+impl LaunchMentionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Agency => "agency",
+            Self::Vehicle => "vehicle",
+        }
+    }
+}
+
+impl FromStr for LaunchMentionKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "agency" => Ok(Self::Agency),
+            "vehicle" => Ok(Self::Vehicle),
+            _ => Err("invalid launch mention kind"),
+        }
+    }
+}
+
+/// A role that gets mentioned in the reminders for the launches of an agency
+/// or with a vehicle.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LaunchMention {
+    pub role: RoleId,
+    pub kind: LaunchMentionKind,
+    /// An agency or vehicle filter, like the ones in `/filtersinfo`.
+    pub filter: String,
+}
+
+/// The thread a channel gets the messages about a launch in, started from the
+/// first reminder for the launch in that channel.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LaunchThread {
+    pub channel: ChannelId,
+    /// The `ll_id` of the launch.
+    pub launch: String,
+    /// Not set yet while the first reminder is starting the thread.
+    #[serde(default)]
+    pub thread: Option<ChannelId>,
+}
+
+/// The users that want a ping in the reminders for a launch in a channel.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LaunchPings {
+    pub channel: ChannelId,
+    /// The `ll_id` of the launch.
+    pub launch: String,
+    #[serde(default)]
+    pub users: Vec<UserId>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -59,10 +266,19 @@ pub struct UserSettings {
     #[serde(default)]
     #[serde(with = "serde_regex")]
     pub payload_filters: Vec<Regex>,
+    /// Serials of boosters to always get reminders and landing outcomes for.
+    #[serde(default)]
+    pub booster_subscriptions: Vec<String>,
+    /// Types of space events to not get reminders for.
+    #[serde(default)]
+    pub event_filters: Vec<String>,
     #[serde(default)]
     pub scrub_notifications: bool,
     #[serde(default)]
     pub outcome_notifications: bool,
+    /// Whether to send the messages as text instead of embeds.
+    #[serde(default)]
+    pub plain_text: bool,
 }
 
 impl Reminder {
@@ -78,6 +294,10 @@ pub trait ReminderSettings {
 
     fn get_payload_filters(&self) -> &Vec<Regex>;
 
+    fn get_booster_subscriptions(&self) -> &Vec<String>;
+
+    fn get_event_filters(&self) -> &Vec<String>;
+
     fn notify_scrub(&self) -> bool;
 
     fn notify_outcome(&self) -> bool;
@@ -96,6 +316,14 @@ impl ReminderSettings for GuildSettings {
         &self.payload_filters
     }
 
+    fn get_booster_subscriptions(&self) -> &Vec<String> {
+        &self.booster_subscriptions
+    }
+
+    fn get_event_filters(&self) -> &Vec<String> {
+        &self.event_filters
+    }
+
     fn notify_scrub(&self) -> bool {
         self.scrub_notifications
     }
@@ -118,6 +346,14 @@ impl ReminderSettings for UserSettings {
         &self.payload_filters
     }
 
+    fn get_booster_subscriptions(&self) -> &Vec<String> {
+        &self.booster_subscriptions
+    }
+
+    fn get_event_filters(&self) -> &Vec<String> {
+        &self.event_filters
+    }
+
     fn notify_scrub(&self) -> bool {
         self.scrub_notifications
     }
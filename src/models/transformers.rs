@@ -1,8 +1,18 @@
 use std::str::FromStr;
 
-use super::launches::{
-    LaunchData,
-    LaunchInfo,
+use super::{
+    events::{
+        EventData,
+        EventInfo,
+    },
+    launches::{
+        BoosterData,
+        LaunchData,
+        LaunchInfo,
+        LauncherStage,
+        PadData,
+        PadInfo,
+    },
 };
 
 impl From<LaunchInfo> for LaunchData {
@@ -24,6 +34,13 @@ impl From<LaunchInfo> for LaunchData {
             });
         };
 
+        let boosters = info
+            .rocket
+            .launcher_stage
+            .iter()
+            .map(BoosterData::from)
+            .collect();
+
         LaunchData {
             id: 0,
             ll_id: info.id,
@@ -45,9 +62,20 @@ impl From<LaunchInfo> for LaunchData {
                 .rocket
                 .configuration
                 .full_name,
+            vehicle_id: Some(
+                info.rocket
+                    .configuration
+                    .id,
+            ),
+            vehicle_family: info
+                .rocket
+                .configuration
+                .family,
             location: info
                 .pad
-                .name,
+                .name
+                .clone(),
+            pad: Some(PadData::from(&info.pad)),
             net: info.net,
             launch_window: info.window_end - info.window_start,
             rocket_img: info.image,
@@ -65,12 +93,133 @@ impl From<LaunchInfo> for LaunchData {
                     || String::from("mission description unknown"),
                     |m| m.description,
                 ),
+            lsp_id: info
+                .launch_service_provider
+                .as_ref()
+                .map(|l| l.id),
             lsp: info
                 .launch_service_provider
                 .map_or_else(
                     || String::from("Unknown launch provider"),
                     |l| l.name,
                 ),
+            failreason: info.failreason,
+            holdreason: info.holdreason,
+            // launch library uses -1 for an unknown probability
+            probability: info
+                .probability
+                .filter(|p| *p >= 0),
+            weather_concerns: info.weather_concerns,
+            net_precision: info
+                .net_precision
+                .map(|p| p.name),
+            boosters,
+        }
+    }
+}
+
+impl From<&LauncherStage> for BoosterData {
+    fn from(stage: &LauncherStage) -> BoosterData {
+        BoosterData {
+            stage_type: stage
+                .stage_type
+                .clone(),
+            serial: stage
+                .launcher
+                .serial_number
+                .clone(),
+            flight_number: stage.launcher_flight_number,
+            flight_proven: stage
+                .launcher
+                .flight_proven,
+            landing_attempt: stage
+                .landing
+                .as_ref()
+                .and_then(|l| l.attempt),
+            landing_success: stage
+                .landing
+                .as_ref()
+                .and_then(|l| l.success),
+            landing_location: stage
+                .landing
+                .as_ref()
+                .and_then(|l| {
+                    l.location
+                        .as_ref()
+                })
+                .map(|l| {
+                    if l.abbrev
+                        .is_empty()
+                    {
+                        l.name
+                            .clone()
+                    } else {
+                        l.abbrev
+                            .clone()
+                    }
+                }),
+        }
+    }
+}
+
+impl From<&PadInfo> for PadData {
+    fn from(pad: &PadInfo) -> PadData {
+        PadData {
+            id: pad.id,
+            name: pad
+                .name
+                .clone(),
+            location: pad
+                .location
+                .name
+                .clone(),
+            country_code: pad
+                .location
+                .country_code
+                .clone(),
+            latitude: pad
+                .latitude
+                .clone(),
+            longitude: pad
+                .longitude
+                .clone(),
+            map_url: pad
+                .map_url
+                .clone(),
+            // the map of the pad itself is more detailed than the one of the site
+            map_image: pad
+                .map_image
+                .clone()
+                .or_else(|| {
+                    pad.location
+                        .map_image
+                        .clone()
+                }),
+            launch_count: pad.total_launch_count,
+            location_launch_count: pad
+                .location
+                .total_launch_count,
+            location_landing_count: pad
+                .location
+                .total_landing_count,
+        }
+    }
+}
+
+impl From<EventInfo> for EventData {
+    fn from(info: EventInfo) -> EventData {
+        EventData {
+            ll_id: info.id,
+            name: info.name,
+            event_type: info
+                .event_type
+                .name,
+            description: info.description,
+            location: info.location,
+            news_url: info.news_url,
+            video_url: info.video_url,
+            image: info.feature_image,
+            date: info.date,
         }
     }
 }
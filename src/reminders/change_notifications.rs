@@ -19,42 +19,66 @@ use mongodb::{
 };
 use serde::de::DeserializeOwned;
 use serenity::{
-    builder::{
-        CreateEmbed,
-        CreateMessage,
-    },
+    builder::CreateMessage,
     http::Http,
     model::{
-        channel::Message,
         id::ChannelId,
         Colour,
         Timestamp,
     },
-    Error as SerenityError,
 };
 
-use super::filtering::passes_filters;
+use super::{
+    filtering::passes_filters,
+    launch_pings::clear_launch_pings,
+    launch_threads::{
+        archive_launch_threads,
+        notification_channel,
+    },
+    notice::Notice,
+    plain_text::{
+        embed_messages,
+        plain_messages,
+    },
+    templates::style_message,
+};
 use crate::{
     models::{
         launches::{
+            BoosterData,
             LaunchData,
             LaunchStatus,
         },
         reminders::{
             GuildSettings,
+            MentionKind,
             UserSettings,
         },
     },
-    utils::default_embed,
+    utils::{
+        catalog::Catalog,
+        launches::launch_reasons,
+        reminders::{
+            format_mentions,
+            guild_mentions,
+        },
+    },
 };
 
 async fn get_toggled<T>(db: &Database, collection: &str, toggled: &str) -> Vec<T>
+where
+    T: DeserializeOwned,
+{
+    get_matching(db, collection, doc! {toggled: true}).await
+}
+
+async fn get_matching<T>(db: &Database, collection: &str, filter: Document) -> Vec<T>
 where
     T: DeserializeOwned,
 {
     let Ok(cursor) = db
         .collection(collection)
-        .find(doc! {toggled: true}, None)
+        .find(filter, None)
         .await
     else {
         return Vec::new();
@@ -80,25 +104,23 @@ where
     }
 }
 
-fn get_mentions(settings: &GuildSettings) -> Option<String> {
-    let mentions = settings
-        .mentions
-        .iter()
-        .fold(String::new(), |acc, mention| {
-            acc + &format!(" <@&{}>", mention.get())
-        });
-
-    (settings.mention_others && !mentions.is_empty()).then_some(mentions)
+fn get_mentions(settings: &GuildSettings, kind: MentionKind) -> String {
+    format_mentions(&guild_mentions(settings, kind, None))
 }
 
 async fn send_user_notification<'r>(
     http: &'r Arc<Http>,
+    catalog: &'r Catalog,
     all_settings: Vec<UserSettings>,
     launch: &'r LaunchData,
-    embed: &'r CreateEmbed,
+    notice: &'r Notice,
 ) {
     stream::iter(all_settings)
-        .filter(|settings| future::ready(passes_filters(settings, launch)))
+        .filter(|settings| {
+            future::ready(passes_filters(
+                settings, catalog, launch,
+            ))
+        })
         .filter_map(|settings| {
             let http = http.clone();
             async move {
@@ -107,9 +129,18 @@ async fn send_user_notification<'r>(
                     .create_dm_channel(&http)
                     .await
                     .ok()
+                    .map(|channel| (channel, settings.plain_text))
             }
         })
-        .map(|channel| send_message(http, channel.id, None, embed.clone()))
+        .map(|(channel, plain_text)| {
+            send_message(
+                http,
+                channel.id,
+                notice.clone(),
+                String::new(),
+                plain_text,
+            )
+        })
         .collect::<FuturesUnordered<_>>()
         .await
         .collect::<Vec<_>>()
@@ -118,28 +149,52 @@ async fn send_user_notification<'r>(
 
 async fn send_guild_notification<'r>(
     http: &'r Arc<Http>,
+    db: &'r Database,
+    catalog: &'r Catalog,
     all_settings: Vec<GuildSettings>,
     launch: &'r LaunchData,
-    embed: &'r CreateEmbed,
+    notice: &'r Notice,
+    kind: MentionKind,
 ) {
     stream::iter(all_settings)
-        .filter(|settings| future::ready(passes_filters(settings, launch)))
+        .filter(|settings| {
+            future::ready(passes_filters(
+                settings, catalog, launch,
+            ))
+        })
         .filter_map(|settings| {
-            future::ready(
-                settings
-                    .notifications_channel
-                    .map(|c| (c, settings)),
+            async move {
+                notification_channel(db, &settings, launch)
+                    .await
+                    .map(|c| (c, settings))
+            }
+        })
+        .map(|(channel, settings)| {
+            // a launch thread goes by the channel it is in
+            let plain_text = settings
+                .notifications_channel
+                .is_some_and(|c| settings.plain_text_in(c));
+            send_message(
+                http,
+                channel,
+                style_message(&settings, kind, launch, notice.clone()),
+                get_mentions(&settings, kind),
+                plain_text,
             )
         })
-        .map(|(c, settings)| (c, get_mentions(&settings)))
-        .map(|(channel, mentions)| send_message(http, channel, mentions, embed.clone()))
         .collect::<FuturesUnordered<_>>()
         .await
         .collect::<Vec<_>>()
         .await;
 }
 
-pub async fn notify_scrub(http: Arc<Http>, db: Database, old: LaunchData, new: LaunchData) {
+pub async fn notify_scrub(
+    http: Arc<Http>,
+    db: Database,
+    catalog: &Catalog,
+    old: LaunchData,
+    new: LaunchData,
+) {
     let user_settings: Vec<UserSettings> = get_toggled(
         &db,
         "user_settings",
@@ -154,34 +209,66 @@ pub async fn notify_scrub(http: Arc<Http>, db: Database, old: LaunchData, new: L
     )
     .await;
 
-    let embed = scrub_embed(&old, &new);
+    let notice = scrub_notice(&old, &new);
 
-    send_user_notification(&http, user_settings, &new, &embed).await;
+    send_user_notification(
+        &http,
+        catalog,
+        user_settings,
+        &new,
+        &notice,
+    )
+    .await;
 
-    send_guild_notification(&http, guild_settings, &new, &embed).await;
+    send_guild_notification(
+        &http,
+        &db,
+        catalog,
+        guild_settings,
+        &new,
+        &notice,
+        MentionKind::Scrub,
+    )
+    .await;
 }
 
 async fn send_message(
     http: &Arc<Http>,
     channel: ChannelId,
-    mentions_opt: Option<String>,
-    embed: CreateEmbed,
-) -> Result<Message, SerenityError> {
-    let mut message = CreateMessage::new().embed(embed);
+    notice: Notice,
+    mentions: String,
+    plain_text: bool,
+) {
+    let messages: Vec<CreateMessage> = if plain_text {
+        plain_messages([notice.text()], &mentions)
+            .into_iter()
+            .map(|text| CreateMessage::new().content(text))
+            .collect()
+    } else {
+        embed_messages(
+            vec![notice.embed()],
+            notice
+                .content
+                .as_deref()
+                .unwrap_or_default(),
+            &mentions,
+        )
+    };
 
-    if let Some(mentions) = mentions_opt {
-        message = message.content(mentions);
+    for message in messages {
+        if let Err(e) = channel
+            .send_message(http, message)
+            .await
+        {
+            eprintln!("Can't send notification: {e}");
+        }
     }
-
-    channel
-        .send_message(http, message)
-        .await
 }
 
-fn scrub_embed<'r>(old: &'r LaunchData, new: &'r LaunchData) -> CreateEmbed {
-    default_embed(
-        &format!(
-            "The launch of {} on a **{}** is now scheduled for <t:{}>{} instead of <t:{}>{}",
+pub(super) fn scrub_notice<'r>(old: &'r LaunchData, new: &'r LaunchData) -> Notice {
+    let notice = Notice::okto(
+        format!(
+            "The launch of {} on a **{}** is now scheduled for <t:{}>{} instead of <t:{}>{}\n{}",
             new.payload,
             new.vehicle,
             new.net
@@ -198,19 +285,30 @@ fn scrub_embed<'r>(old: &'r LaunchData, new: &'r LaunchData) -> CreateEmbed {
             } else {
                 ""
             },
+            launch_reasons(new),
         ),
         false,
-    )
-    .timestamp(
-        Timestamp::from_unix_timestamp(
+    );
+
+    Notice {
+        thumbnail: new
+            .rocket_img
+            .clone(),
+        timestamp: Timestamp::from_unix_timestamp(
             new.net
                 .timestamp(),
         )
         .expect("Invalid timestamp"),
-    )
+        ..notice
+    }
 }
 
-pub async fn notify_outcome(http: Arc<Http>, db: Database, finished: LaunchData) {
+pub async fn notify_outcome(
+    http: Arc<Http>,
+    db: Database,
+    catalog: &Catalog,
+    finished: LaunchData,
+) {
     let user_settings: Vec<UserSettings> = get_toggled(
         &db,
         "user_settings",
@@ -225,27 +323,49 @@ pub async fn notify_outcome(http: Arc<Http>, db: Database, finished: LaunchData)
     )
     .await;
 
-    let embed = outcome_embed(&finished);
+    let notice = outcome_notice(&finished);
+
+    send_user_notification(
+        &http,
+        catalog,
+        user_settings,
+        &finished,
+        &notice,
+    )
+    .await;
 
-    send_user_notification(&http, user_settings, &finished, &embed).await;
+    send_guild_notification(
+        &http,
+        &db,
+        catalog,
+        guild_settings,
+        &finished,
+        &notice,
+        MentionKind::Outcome,
+    )
+    .await;
 
-    send_guild_notification(&http, guild_settings, &finished, &embed).await;
+    archive_launch_threads(&http, &db, &finished.ll_id).await;
+
+    clear_launch_pings(&db, &finished.ll_id).await;
 }
 
-fn outcome_embed(finished: &LaunchData) -> CreateEmbed {
-    default_embed(
-        &format!(
-            "The launch of {} on a {} has completed with a status of **{}**!",
+pub(super) fn outcome_notice(finished: &LaunchData) -> Notice {
+    let notice = Notice::okto(
+        format!(
+            "The launch of {} on a {} has completed with a status of **{}**!\n{}",
             &finished.payload,
             &finished.vehicle,
             finished
                 .status
-                .as_str()
+                .as_str(),
+            launch_reasons(finished),
         ),
         true,
-    )
-    .color(
-        if matches!(finished.status, LaunchStatus::Success) {
+    );
+
+    Notice {
+        color: if matches!(finished.status, LaunchStatus::Success) {
             Colour::FOOYOO
         } else if matches!(
             finished.status,
@@ -255,5 +375,173 @@ fn outcome_embed(finished: &LaunchData) -> CreateEmbed {
         } else {
             Colour::RED
         },
+        thumbnail: finished
+            .rocket_img
+            .clone(),
+        ..notice
+    }
+}
+
+/// Let the followers of boosters know these boosters got assigned to a launch.
+pub async fn notify_booster_assigned(
+    http: Arc<Http>,
+    db: Database,
+    catalog: &Catalog,
+    launch: LaunchData,
+    serials: Vec<String>,
+) {
+    for serial in serials {
+        let filter = doc! {"booster_subscriptions": serial.to_uppercase()};
+        let user_settings: Vec<UserSettings> =
+            get_matching(&db, "user_settings", filter.clone()).await;
+        let guild_settings: Vec<GuildSettings> = get_matching(&db, "guild_settings", filter).await;
+
+        let notice = booster_assigned_notice(&launch, &serial);
+
+        send_user_notification(
+            &http,
+            catalog,
+            user_settings,
+            &launch,
+            &notice,
+        )
+        .await;
+
+        send_guild_notification(
+            &http,
+            &db,
+            catalog,
+            guild_settings,
+            &launch,
+            &notice,
+            MentionKind::Scrub,
+        )
+        .await;
+    }
+}
+
+fn booster_assigned_notice(launch: &LaunchData, serial: &str) -> Notice {
+    let flight = launch
+        .boosters
+        .iter()
+        .find(|b| {
+            b.serial
+                .as_deref()
+                == Some(serial)
+        })
+        .and_then(|b| b.flight_number)
+        .map(|n| format!(", which will be its flight number {n}"))
+        .unwrap_or_default();
+
+    let notice = Notice::okto(
+        format!(
+            "Booster **{serial}** has been assigned to the launch of {} on a **{}**, scheduled for <t:{}>{}{flight}",
+            launch.payload,
+            launch.vehicle,
+            launch
+                .net
+                .timestamp(),
+            if launch.status == LaunchStatus::Tbd {
+                " (TBD)"
+            } else {
+                ""
+            },
+        ),
+        false,
+    );
+
+    Notice {
+        timestamp: Timestamp::from_unix_timestamp(
+            launch
+                .net
+                .timestamp(),
+        )
+        .expect("Invalid timestamp"),
+        ..notice
+    }
+}
+
+/// Let the followers of a booster know how its landing went.
+pub async fn notify_landing(
+    http: Arc<Http>,
+    db: Database,
+    catalog: &Catalog,
+    finished: LaunchData,
+    booster: BoosterData,
+) {
+    let Some(serial) = booster
+        .serial
+        .as_deref()
+    else {
+        return;
+    };
+
+    let filter = doc! {"booster_subscriptions": serial.to_uppercase()};
+    let user_settings: Vec<UserSettings> = get_matching(&db, "user_settings", filter.clone()).await;
+    let guild_settings: Vec<GuildSettings> = get_matching(&db, "guild_settings", filter).await;
+
+    let notice = landing_notice(&finished, &booster, serial);
+
+    send_user_notification(
+        &http,
+        catalog,
+        user_settings,
+        &finished,
+        &notice,
+    )
+    .await;
+
+    send_guild_notification(
+        &http,
+        &db,
+        catalog,
+        guild_settings,
+        &finished,
+        &notice,
+        MentionKind::Outcome,
     )
+    .await;
+}
+
+fn landing_notice(finished: &LaunchData, booster: &BoosterData, serial: &str) -> Notice {
+    let location = booster
+        .landing_location
+        .as_deref()
+        .map(|location| format!(" on {location}"))
+        .unwrap_or_default();
+
+    let (outcome, colour) = match (
+        booster.landing_attempt,
+        booster.landing_success,
+    ) {
+        (Some(false), _) => {
+            (
+                "was expended".to_owned(),
+                Colour::ORANGE,
+            )
+        },
+        (_, Some(true)) => {
+            (
+                format!("has landed{location}"),
+                Colour::FOOYOO,
+            )
+        },
+        _ => {
+            (
+                format!("failed to land{location}"),
+                Colour::RED,
+            )
+        },
+    };
+
+    Notice {
+        color: colour,
+        ..Notice::okto(
+            format!(
+                "Booster **{serial}** {outcome} after the launch of {} on a {}",
+                &finished.payload, &finished.vehicle,
+            ),
+            true,
+        )
+    }
 }
@@ -0,0 +1,429 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
+
+use chrono::{
+    Duration,
+    NaiveDateTime,
+    Utc,
+};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::doc,
+    Database,
+};
+use serenity::{
+    builder::{
+        CreateEmbed,
+        CreateEmbedAuthor,
+        CreateEmbedFooter,
+        EditMessage,
+    },
+    http::Http,
+    model::{
+        id::MessageId,
+        Timestamp,
+    },
+    prelude::RwLock,
+    Error as SerenityError,
+};
+
+use super::change_notifications::outcome_notice;
+use crate::{
+    models::{
+        countdowns::Countdown,
+        launches::{
+            LaunchData,
+            LaunchRecord,
+            LaunchStatus,
+        },
+    },
+    utils::{
+        constants::{
+            DEFAULT_COLOR,
+            DEFAULT_ICON,
+        },
+        default_embed,
+        format_duration,
+        launches::{
+            format_links,
+            launch_conditions,
+        },
+    },
+};
+
+/// How close to the NET the countdown switches to updating every few seconds.
+const FINAL_MINUTES: i64 = 10;
+
+/// How many launch fetches in a row a launch can be missing from before its
+/// countdown stops, when its NET has passed and its outcome isn't known.
+const LOST_AFTER_FETCHES: i32 = 3;
+
+/// What a countdown message showed the last time it got edited.
+struct Shown {
+    at: NaiveDateTime,
+    net: NaiveDateTime,
+    status: LaunchStatus,
+}
+
+/// How long to wait between edits of a countdown, every minute far out and
+/// every few seconds around liftoff.
+fn update_interval(until_launch: Duration) -> Duration {
+    if until_launch > Duration::minutes(FINAL_MINUTES) || until_launch < -Duration::minutes(1) {
+        Duration::minutes(1)
+    } else {
+        Duration::seconds(5)
+    }
+}
+
+/// Keep editing the countdown messages until their launch is over. The
+/// countdowns are read from the database every time, so they pick up where
+/// they left off after a restart.
+pub async fn countdown_tracking(
+    http: Arc<Http>,
+    cache: Arc<RwLock<Vec<LaunchData>>>,
+    db: Database,
+) {
+    let mut shown: HashMap<MessageId, Shown> = HashMap::new();
+
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+        let launches = cache
+            .read()
+            .await
+            .clone();
+        // before the first launches are fetched every launch would look gone
+        if launches.is_empty() {
+            continue;
+        }
+
+        let countdowns = get_countdowns(&db).await;
+        shown.retain(|message, _| {
+            countdowns
+                .iter()
+                .any(|c| c.message == *message)
+        });
+
+        let now = Utc::now().naive_utc();
+        for countdown in countdowns {
+            let Some(launch) = launches
+                .iter()
+                .find(|l| l.ll_id == countdown.launch)
+            else {
+                // the history knows how the launch went once it is over, otherwise
+                // it can have dropped out of the upcoming launches for a while
+                let finished = finished_launch(&db, &countdown.launch).await;
+                if finished.is_some() || is_lost(&countdown, now) {
+                    finish_countdown(
+                        &http,
+                        &db,
+                        &countdown,
+                        finished.as_ref(),
+                    )
+                    .await;
+                }
+                continue;
+            };
+
+            if launch
+                .status
+                .is_finished()
+            {
+                finish_countdown(&http, &db, &countdown, Some(launch)).await;
+                continue;
+            }
+
+            let due = shown
+                .get(&countdown.message)
+                .is_none_or(|s| {
+                    s.net != launch.net
+                        || s.status != launch.status
+                        || now - s.at >= update_interval(launch.net - now)
+                });
+            if !due {
+                continue;
+            }
+
+            let res = countdown
+                .channel
+                .edit_message(
+                    &http,
+                    countdown.message,
+                    EditMessage::new().embed(countdown_embed(launch)),
+                )
+                .await;
+            match res {
+                Ok(_) => {
+                    shown.insert(
+                        countdown.message,
+                        Shown {
+                            at: now,
+                            net: launch.net,
+                            status: launch
+                                .status
+                                .clone(),
+                        },
+                    );
+                },
+                Err(e) if is_gone(&e) => remove_countdown(&db, &countdown).await,
+                Err(e) => eprintln!("Can't update countdown: {e}"),
+            }
+        }
+    }
+}
+
+/// Whether the launch of a countdown has been missing from the upcoming
+/// launches for long enough after its NET to stop waiting for it.
+fn is_lost(countdown: &Countdown, now: NaiveDateTime) -> bool {
+    countdown.missed_fetches >= LOST_AFTER_FETCHES
+        && countdown
+            .net
+            .is_none_or(|net| net < now.timestamp())
+}
+
+/// Keep track of the launches of the countdowns after every fetch, the NET
+/// while they are upcoming and how many fetches in a row they are missing
+/// from otherwise.
+pub(super) async fn track_countdown_launches(db: &Database, launches: &[LaunchData]) {
+    let collection = db.collection::<Countdown>("countdowns");
+
+    for countdown in get_countdowns(db).await {
+        let update = launches
+            .iter()
+            .find(|l| l.ll_id == countdown.launch)
+            .map_or_else(
+                || doc! {"$inc": {"missed_fetches": 1}},
+                |launch| doc! {"$set": {"net": launch.net.timestamp(), "missed_fetches": 0}},
+            );
+
+        if let Err(e) = collection
+            .update_one(
+                doc! {"message": countdown.message.get() as i64},
+                update,
+                None,
+            )
+            .await
+        {
+            eprintln!("Can't update countdown: {e}");
+        }
+    }
+}
+
+/// Whether editing failed because the message or access to it is gone.
+fn is_gone(err: &SerenityError) -> bool {
+    matches!(err, SerenityError::Http(e) if e.status_code().is_some_and(|s| s == 403 || s == 404))
+}
+
+/// The time to liftoff like it is shown on a countdown, with seconds in the
+/// final minutes.
+fn clock(until_launch: Duration) -> String {
+    let seconds = until_launch
+        .num_minutes()
+        .abs()
+        < FINAL_MINUTES;
+    // format_duration has nothing to show for less than a second
+    let time = if until_launch.num_seconds() == 0 {
+        "0 seconds".to_owned()
+    } else {
+        format_duration(until_launch.abs(), seconds)
+    };
+
+    if until_launch > Duration::zero() {
+        format!("T- {time}")
+    } else {
+        format!("T+ {time}")
+    }
+}
+
+pub fn countdown_embed(launch: &LaunchData) -> CreateEmbed {
+    let until_launch = launch.net - Utc::now().naive_utc();
+
+    let state = match launch.status {
+        LaunchStatus::Hold => format!("**Holding** at {}", clock(until_launch)),
+        _ if until_launch <= Duration::zero() => {
+            format!(
+                "**Liftoff!** {}, waiting for the outcome",
+                clock(until_launch)
+            )
+        },
+        _ => format!("**{}**", clock(until_launch)),
+    };
+
+    let mut description = format!(
+        "{state}\n\n\
+        **NET:** <t:{0}:F> (<t:{0}:R>)\n\
+        **Status:** {1}\n",
+        launch
+            .net
+            .timestamp(),
+        launch
+            .status
+            .as_str(),
+    );
+    if let Some(conditions) = launch_conditions(launch) {
+        description.push_str(&conditions);
+    }
+
+    let mut em = CreateEmbed::new()
+        .color(DEFAULT_COLOR)
+        .author(CreateEmbedAuthor::new("Countdown").icon_url(DEFAULT_ICON))
+        .title(&launch.launch_name)
+        .description(description)
+        .field(
+            "Webcast",
+            format_links(&launch.vid_urls)
+                .unwrap_or_else(|| "No webcast has been announced yet".to_owned()),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(format!(
+            "Updates every minute, and every few seconds in the last {FINAL_MINUTES} minutes"
+        )))
+        .timestamp(Timestamp::now());
+
+    if let Some(img) = &launch.rocket_img {
+        em = em.thumbnail(img);
+    }
+
+    em
+}
+
+/// Show how the launch went, or that it can't be followed anymore, and stop
+/// updating the countdown.
+async fn finish_countdown(
+    http: &Http,
+    db: &Database,
+    countdown: &Countdown,
+    finished: Option<&LaunchData>,
+) {
+    let embed = finished.map_or_else(
+        || {
+            default_embed(
+                "I lost track of this launch, so the countdown has stopped",
+                false,
+            )
+        },
+        |l| outcome_notice(l).embed(),
+    );
+
+    if let Err(e) = countdown
+        .channel
+        .edit_message(
+            http,
+            countdown.message,
+            EditMessage::new().embed(embed),
+        )
+        .await
+    {
+        eprintln!("Can't finish countdown: {e}");
+    }
+
+    remove_countdown(db, countdown).await;
+}
+
+async fn finished_launch(db: &Database, ll_id: &str) -> Option<LaunchData> {
+    match db
+        .collection::<LaunchRecord>("launch_history")
+        .find_one(doc! {"ll_id": ll_id}, None)
+        .await
+    {
+        Ok(record) => record.map(|r| r.launch),
+        Err(e) => {
+            eprintln!("Can't read launch history: {e}");
+            None
+        },
+    }
+}
+
+async fn get_countdowns(db: &Database) -> Vec<Countdown> {
+    match db
+        .collection::<Countdown>("countdowns")
+        .find(None, None)
+        .await
+    {
+        Ok(cursor) => {
+            cursor
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("Can't read countdowns: {e}");
+                    Vec::new()
+                })
+        },
+        Err(e) => {
+            eprintln!("Can't read countdowns: {e}");
+            Vec::new()
+        },
+    }
+}
+
+async fn remove_countdown(db: &Database, countdown: &Countdown) {
+    if let Err(e) = db
+        .collection::<Countdown>("countdowns")
+        .delete_one(
+            doc! {"message": countdown.message.get() as i64},
+            None,
+        )
+        .await
+    {
+        eprintln!("Can't remove countdown: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_faster_around_liftoff() {
+        assert_eq!(
+            update_interval(Duration::hours(3)),
+            Duration::minutes(1)
+        );
+        assert_eq!(
+            update_interval(Duration::minutes(5)),
+            Duration::seconds(5)
+        );
+        assert_eq!(
+            update_interval(Duration::seconds(-30)),
+            Duration::seconds(5)
+        );
+        assert_eq!(
+            update_interval(Duration::minutes(-20)),
+            Duration::minutes(1)
+        );
+    }
+
+    #[test]
+    fn waits_for_missing_launches_to_come_back() {
+        let now = Utc::now().naive_utc();
+        let mut countdown = Countdown {
+            channel: 429307774804033536.into(),
+            message: 1.into(),
+            launch: "e3df2ecd-c239-472f-95e4-2b89b4f75800".to_owned(),
+            net: Some((now - Duration::minutes(30)).timestamp()),
+            missed_fetches: 1,
+        };
+        assert!(!is_lost(&countdown, now));
+
+        countdown.missed_fetches = LOST_AFTER_FETCHES;
+        assert!(is_lost(&countdown, now));
+
+        countdown.net = Some((now + Duration::hours(2)).timestamp());
+        assert!(!is_lost(&countdown, now));
+    }
+
+    #[test]
+    fn clock_shows_seconds_at_the_end() {
+        assert_eq!(
+            clock(Duration::minutes(90)),
+            "T- 1 hour and 30 minutes"
+        );
+        assert_eq!(
+            clock(Duration::seconds(65)),
+            "T- 1 minute and 5 seconds"
+        );
+        assert_eq!(clock(Duration::zero()), "T+ 0 seconds");
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use reqwest::{
+    header::AUTHORIZATION,
+    Result,
+};
+use serenity::prelude::RwLock;
+
+use crate::{
+    models::events::{
+        EventContainer,
+        EventData,
+    },
+    utils::constants::{
+        DEFAULT_CLIENT,
+        LL_KEY,
+    },
+};
+
+pub async fn event_tracking(cache: Arc<RwLock<Vec<EventData>>>) {
+    let mut events: Vec<EventData> = match get_new_events().await {
+        Ok(container) => {
+            container
+                .results
+                .into_iter()
+                .map(EventData::from)
+                .collect()
+        },
+        Err(e) => {
+            eprintln!("Can't get upcoming events: {e}");
+            return;
+        },
+    };
+    events.sort_by_key(|e| e.date);
+
+    *cache
+        .write()
+        .await = events;
+}
+
+async fn get_new_events() -> Result<EventContainer> {
+    DEFAULT_CLIENT
+        .get("https://ll.thespacedevs.com/2.0.0/event/upcoming/")
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .query(&[("limit", "100")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
@@ -1,20 +1,83 @@
+use itertools::Itertools;
+use serenity::model::id::RoleId;
+
 use crate::{
     models::{
+        events::EventData,
         launches::LaunchData,
-        reminders::ReminderSettings,
+        reminders::{
+            GuildSettings,
+            LaunchMentionKind,
+            ReminderSettings,
+        },
+    },
+    utils::catalog::{
+        slug,
+        Catalog,
     },
-    utils::constants::LAUNCH_AGENCIES,
 };
 
-pub(super) fn passes_filters<T>(settings: &T, l: &LaunchData) -> bool
+/// Check if a launch uses one of the boosters that are followed, which gets it
+/// past all other filters.
+pub(super) fn follows_booster<T>(settings: &T, l: &LaunchData) -> bool
 where
     T: ReminderSettings,
 {
+    l.boosters
+        .iter()
+        .filter_map(|booster| {
+            booster
+                .serial
+                .as_deref()
+        })
+        .any(|serial| {
+            settings
+                .get_booster_subscriptions()
+                .iter()
+                .any(|followed| followed.eq_ignore_ascii_case(serial))
+        })
+}
+
+/// Check if a launch is from the agency of a filter. Filters the catalog
+/// doesn't know, like before it is loaded, are compared with the key made from
+/// the name of the launch provider.
+fn matches_agency(catalog: &Catalog, filter: &str, l: &LaunchData) -> bool {
+    match catalog.agency(filter) {
+        Some(agency) => agency.matches(l),
+        None => slug(&l.lsp) == filter,
+    }
+}
+
+/// Check if a launch uses the vehicle of a filter, unknown filters are
+/// compared with the key of the vehicle family like for [`matches_agency`].
+fn matches_vehicle(catalog: &Catalog, filter: &str, l: &LaunchData) -> bool {
+    if let Some(vehicle) = catalog.vehicle(filter) {
+        return vehicle.matches(l);
+    }
+
+    let family = if l
+        .vehicle_family
+        .is_empty()
+    {
+        &l.vehicle
+    } else {
+        &l.vehicle_family
+    };
+    slug(family) == filter
+}
+
+pub(super) fn passes_filters<T>(settings: &T, catalog: &Catalog, l: &LaunchData) -> bool
+where
+    T: ReminderSettings,
+{
+    if follows_booster(settings, l) {
+        return true;
+    }
+
     let passes_agencies = !settings
         .get_filters()
         .iter()
-        .filter_map(|filter| LAUNCH_AGENCIES.get(filter.as_str()))
-        .any(|agency| *agency == l.lsp);
+        .any(|filter| matches_agency(catalog, filter, l));
 
     let passes_agency_allows = settings
         .get_allow_filters()
@@ -22,8 +85,7 @@ where
         || settings
             .get_allow_filters()
             .iter()
-            .filter_map(|filter| LAUNCH_AGENCIES.get(filter.as_str()))
-            .any(|agency| *agency == l.lsp);
+            .any(|filter| matches_agency(catalog, filter, l));
 
     let passes_payloads = !settings
         .get_payload_filters()
@@ -33,6 +95,36 @@ where
     passes_agencies && passes_agency_allows && passes_payloads
 }
 
+pub(super) fn passes_event_filters<T>(settings: &T, e: &EventData) -> bool
+where
+    T: ReminderSettings,
+{
+    !settings
+        .get_event_filters()
+        .iter()
+        .any(|filter| filter.eq_ignore_ascii_case(&e.event_type))
+}
+
+/// The roles a guild wants mentioned for the agency and vehicle of a launch.
+pub(super) fn launch_mentions(
+    settings: &GuildSettings,
+    catalog: &Catalog,
+    l: &LaunchData,
+) -> Vec<RoleId> {
+    settings
+        .launch_mentions
+        .iter()
+        .filter(|mention| {
+            match mention.kind {
+                LaunchMentionKind::Agency => matches_agency(catalog, &mention.filter, l),
+                LaunchMentionKind::Vehicle => matches_vehicle(catalog, &mention.filter, l),
+            }
+        })
+        .map(|mention| mention.role)
+        .unique()
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{
@@ -42,48 +134,50 @@ mod tests {
     use regex::Regex;
     use serenity::model::id::{
         ChannelId,
-        GuildId,
+        UserId,
     };
 
     use super::*;
     use crate::models::{
-        launches::LaunchStatus,
-        reminders::GuildSettings,
+        launches::BoosterData,
+        reminders::{
+            LaunchMention,
+            UserSettings,
+        },
     };
 
     fn create_fake_launches() -> Vec<LaunchData> {
         vec![
             LaunchData {
                 id: 1,
-                ll_id: String::new(),
                 launch_name: "Falcon 9 Block 5".into(),
-                status: LaunchStatus::Go,
                 payload: "Starlink Group 6-2".into(),
-                vid_urls: vec![],
                 vehicle: "Falcon 9 Block 5".into(),
                 location: "Launch Complex 39A".into(),
-                rocket_img: None,
                 net: NaiveDateTime::from_timestamp_opt(1635409251, 0).unwrap(),
                 launch_window: Duration::seconds(60),
-                mission_type: String::new(),
-                mission_description: String::new(),
                 lsp: "SpaceX".into(),
+                boosters: vec![BoosterData {
+                    stage_type: "Core".into(),
+                    serial: Some("B1062".into()),
+                    flight_number: Some(12),
+                    flight_proven: true,
+                    landing_attempt: Some(true),
+                    landing_success: None,
+                    landing_location: Some("ASOG".into()),
+                }],
+                ..LaunchData::default()
             },
             LaunchData {
                 id: 2,
-                ll_id: String::new(),
                 launch_name: "Atlas V 551".into(),
-                status: LaunchStatus::Go,
                 payload: "STP-3".into(),
-                vid_urls: vec![],
                 vehicle: "Atlas V 551".into(),
                 location: "Space Launch Complex 41".into(),
-                rocket_img: None,
                 net: NaiveDateTime::from_timestamp_opt(1635409251, 0).unwrap(),
                 launch_window: Duration::seconds(60),
-                mission_type: String::new(),
-                mission_description: String::new(),
                 lsp: "United Launch Alliance".into(),
+                ..LaunchData::default()
             },
         ]
     }
@@ -92,75 +186,248 @@ mod tests {
     fn no_filters() {
         let launches = create_fake_launches();
         let settings = GuildSettings {
-            guild: GuildId::new(429307670730637312),
-            filters: vec![],
-            allow_filters: vec![],
-            payload_filters: vec![],
-            mentions: vec![],
             scrub_notifications: true,
             outcome_notifications: true,
             mention_others: true,
             notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
         };
 
-        assert!(passes_filters(&settings, &launches[0]));
-        assert!(passes_filters(&settings, &launches[1]));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[1]
+        ));
     }
 
     #[test]
     fn block_filters() {
         let launches = create_fake_launches();
         let settings = GuildSettings {
-            guild: GuildId::new(429307670730637312),
             filters: vec!["ula".into()],
-            allow_filters: vec![],
-            payload_filters: vec![],
-            mentions: vec![],
             scrub_notifications: true,
             outcome_notifications: true,
             mention_others: true,
             notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
         };
 
-        assert!(passes_filters(&settings, &launches[0]));
-        assert!(!passes_filters(&settings, &launches[1]));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(!passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[1]
+        ));
     }
 
     #[test]
     fn allow_filters() {
         let launches = create_fake_launches();
         let settings = GuildSettings {
-            guild: GuildId::new(429307670730637312),
-            filters: vec![],
             allow_filters: vec!["ula".into()],
-            payload_filters: vec![],
-            mentions: vec![],
             scrub_notifications: true,
             outcome_notifications: true,
             mention_others: true,
             notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
+        };
+
+        assert!(!passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[1]
+        ));
+    }
+
+    #[test]
+    fn filters_unknown_to_the_catalog() {
+        let launches = create_fake_launches();
+        // before the catalog is loaded only the curated aliases are known
+        let settings = GuildSettings {
+            filters: vec!["united-launch-alliance".into()],
+            scrub_notifications: true,
+            outcome_notifications: true,
+            mention_others: true,
+            notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
         };
 
-        assert!(!passes_filters(&settings, &launches[0]));
-        assert!(passes_filters(&settings, &launches[1]));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(!passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[1]
+        ));
+
+        let settings = GuildSettings {
+            filters: Vec::new(),
+            allow_filters: vec!["united-launch-alliance".into()],
+            launch_mentions: vec![LaunchMention {
+                role: RoleId::new(4),
+                kind: LaunchMentionKind::Vehicle,
+                filter: "atlas-v-551".into(),
+            }],
+            ..settings
+        };
+
+        assert!(!passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[1]
+        ));
+        assert_eq!(
+            launch_mentions(
+                &settings,
+                &Catalog::default(),
+                &launches[1]
+            ),
+            vec![RoleId::new(4)]
+        );
     }
 
     #[test]
     fn payload_filters() {
         let launches = create_fake_launches();
         let settings = GuildSettings {
-            guild: GuildId::new(429307670730637312),
+            payload_filters: vec![Regex::new(r"(?im)\bstarlink\b").unwrap()],
+            scrub_notifications: true,
+            outcome_notifications: true,
+            mention_others: true,
+            notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
+        };
+
+        assert!(!passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[1]
+        ));
+    }
+
+    #[test]
+    fn booster_subscriptions() {
+        let launches = create_fake_launches();
+        let settings = GuildSettings {
+            filters: vec!["spacex".into()],
+            payload_filters: vec![Regex::new(r"(?im)\bstarlink\b").unwrap()],
+            booster_subscriptions: vec!["b1062".into()],
+            scrub_notifications: true,
+            outcome_notifications: true,
+            mention_others: true,
+            notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
+        };
+
+        assert!(follows_booster(&settings, &launches[0]));
+        assert!(passes_filters(
+            &settings,
+            &Catalog::default(),
+            &launches[0]
+        ));
+        assert!(!follows_booster(
+            &settings,
+            &launches[1]
+        ));
+    }
+
+    #[test]
+    fn event_filters() {
+        let event = EventData {
+            ll_id: 1,
+            name: "US EVA 90".into(),
+            event_type: "EVA".into(),
+            description: String::new(),
+            location: None,
+            news_url: None,
+            video_url: None,
+            image: None,
+            date: NaiveDateTime::from_timestamp_opt(1635409251, 0).unwrap(),
+        };
+        let mut settings = UserSettings {
+            user: UserId::new(247745860979392512),
             filters: vec![],
             allow_filters: vec![],
-            payload_filters: vec![Regex::new(r"(?im)\bstarlink\b").unwrap()],
-            mentions: vec![],
+            payload_filters: vec![],
+            booster_subscriptions: vec![],
+            event_filters: vec!["docking".into()],
+            scrub_notifications: false,
+            outcome_notifications: false,
+            plain_text: false,
+        };
+
+        assert!(passes_event_filters(&settings, &event));
+
+        settings.event_filters = vec!["eva".into()];
+        assert!(!passes_event_filters(&settings, &event));
+    }
+
+    #[test]
+    fn launch_mentions_per_agency_and_vehicle() {
+        let launches = create_fake_launches();
+        let mention = |role: u64, kind: LaunchMentionKind, filter: &str| {
+            LaunchMention {
+                role: RoleId::new(role),
+                kind,
+                filter: filter.into(),
+            }
+        };
+        let settings = GuildSettings {
+            launch_mentions: vec![
+                mention(1, LaunchMentionKind::Agency, "spacex"),
+                mention(2, LaunchMentionKind::Vehicle, "falcon"),
+                mention(3, LaunchMentionKind::Agency, "ula"),
+                mention(1, LaunchMentionKind::Vehicle, "falcon"),
+            ],
             scrub_notifications: true,
             outcome_notifications: true,
             mention_others: true,
             notifications_channel: Some(ChannelId::new(429307774804033536)),
+            ..GuildSettings::default()
         };
 
-        assert!(!passes_filters(&settings, &launches[0]));
-        assert!(passes_filters(&settings, &launches[1]));
+        assert_eq!(
+            launch_mentions(
+                &settings,
+                &Catalog::default(),
+                &launches[0]
+            ),
+            vec![RoleId::new(1), RoleId::new(2)]
+        );
+        assert_eq!(
+            launch_mentions(
+                &settings,
+                &Catalog::default(),
+                &launches[1]
+            ),
+            vec![RoleId::new(3)]
+        );
     }
 }
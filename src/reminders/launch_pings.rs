@@ -0,0 +1,167 @@
+use mongodb::{
+    bson::{
+        doc,
+        Document,
+    },
+    options::UpdateOptions,
+    Database,
+};
+use okto_framework::structs::{
+    Component,
+    ComponentId,
+};
+use serenity::{
+    builder::{
+        CreateButton,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    framework::standard::CommandResult,
+    model::{
+        application::{
+            ButtonStyle,
+            ComponentInteraction,
+        },
+        id::{
+            ChannelId,
+            UserId,
+        },
+    },
+    prelude::Context,
+};
+
+use crate::models::{
+    caches::DatabaseKey,
+    launches::LaunchData,
+    reminders::LaunchPings,
+};
+
+pub static LAUNCH_PINGS_COMPONENT: Component = Component {
+    prefix: "launch-pings",
+    func: launch_pings_component,
+};
+
+/// The button on a channel reminder to get a ping in the next reminders for
+/// the launch in that channel.
+pub(super) fn ping_button(channel: ChannelId, launch: &LaunchData) -> CreateButton {
+    let label = format!("Ping me for {}", launch.launch_name);
+
+    CreateButton::new(
+        ComponentId::new(LAUNCH_PINGS_COMPONENT.prefix)
+            .arg(channel)
+            .arg(&launch.ll_id)
+            .to_string(),
+    )
+    .style(ButtonStyle::Secondary)
+    .emoji('🔔')
+    // discord allows at most 80 characters in a label
+    .label(
+        label
+            .chars()
+            .take(80)
+            .collect::<String>(),
+    )
+}
+
+/// The users that want a ping in the reminders for a launch in a channel.
+pub(super) async fn launch_pings(db: &Database, channel: ChannelId, ll_id: &str) -> Vec<UserId> {
+    match db
+        .collection::<LaunchPings>("launch_pings")
+        .find_one(
+            doc! {"channel": channel.get() as i64, "launch": ll_id},
+            None,
+        )
+        .await
+    {
+        Ok(pings) => pings.map_or_else(Vec::new, |p| p.users),
+        Err(e) => {
+            eprintln!("Can't get launch pings: {e}");
+            Vec::new()
+        },
+    }
+}
+
+/// Forget who wanted a ping for a launch, once it is over there are no more
+/// reminders for it.
+pub(super) async fn clear_launch_pings(db: &Database, ll_id: &str) {
+    if let Err(e) = db
+        .collection::<Document>("launch_pings")
+        .delete_many(doc! {"launch": ll_id}, None)
+        .await
+    {
+        eprintln!("Can't remove launch pings: {e}");
+    }
+}
+
+fn launch_pings_component<'fut>(
+    ctx: &'fut Context,
+    interaction: &'fut ComponentInteraction,
+    args: &'fut [String],
+) -> futures::future::BoxFuture<'fut, CommandResult> {
+    Box::pin(async move {
+        let [channel, ll_id] = args else {
+            return Err("invalid launch pings button".into());
+        };
+        let channel = channel
+            .parse::<u64>()
+            .ok()
+            .filter(|id| *id != 0)
+            .map(ChannelId::new)
+            .ok_or("invalid channel in launch pings button")?;
+
+        let db = ctx
+            .data
+            .read()
+            .await
+            .get::<DatabaseKey>()
+            .cloned()
+            .ok_or("Can't get the database")?;
+
+        let user = interaction
+            .user
+            .id;
+        let filter = doc! {"channel": channel.get() as i64, "launch": ll_id};
+        let collection = db.collection::<Document>("launch_pings");
+
+        // clicking again takes the ping back
+        let content = if launch_pings(&db, channel, ll_id)
+            .await
+            .contains(&user)
+        {
+            collection
+                .update_one(
+                    filter,
+                    doc! {"$pull": {"users": user.get() as i64}},
+                    None,
+                )
+                .await?;
+            "You won't get a ping for this launch anymore"
+        } else {
+            collection
+                .update_one(
+                    filter,
+                    doc! {"$addToSet": {"users": user.get() as i64}},
+                    Some(
+                        UpdateOptions::builder()
+                            .upsert(true)
+                            .build(),
+                    ),
+                )
+                .await?;
+            "You'll get a ping in the next reminders for this launch in this channel, click again to take it back"
+        };
+
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .ephemeral(true)
+                        .content(content),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    })
+}
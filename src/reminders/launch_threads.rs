@@ -0,0 +1,322 @@
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{
+        doc,
+        Document,
+    },
+    options::UpdateOptions,
+    Database,
+};
+use serenity::{
+    builder::{
+        CreateMessage,
+        CreateThread,
+        EditThread,
+    },
+    http::Http,
+    model::{
+        channel::{
+            AutoArchiveDuration,
+            Message,
+        },
+        id::ChannelId,
+    },
+    Error as SerenityError,
+};
+
+use crate::models::{
+    launches::LaunchData,
+    reminders::{
+        GuildSettings,
+        LaunchThread,
+    },
+};
+
+fn thread_filter(channel: ChannelId, ll_id: &str) -> Document {
+    doc! {"channel": channel.get() as i64, "launch": ll_id}
+}
+
+/// The thread of a channel for a launch, if it has been started.
+async fn find_thread(db: &Database, channel: ChannelId, ll_id: &str) -> Option<LaunchThread> {
+    match db
+        .collection::<LaunchThread>("launch_threads")
+        .find_one(thread_filter(channel, ll_id), None)
+        .await
+    {
+        Ok(thread) => thread,
+        Err(e) => {
+            eprintln!("Can't get launch thread: {e}");
+            None
+        },
+    }
+}
+
+/// Where a message for a launch goes when the thread for it is known.
+#[derive(Debug, PartialEq, Eq)]
+enum Claim {
+    /// The thread has been started, the message goes in it.
+    Thread(ChannelId),
+    /// Another message is starting the thread right now, so this one goes to
+    /// the channel.
+    Pending,
+    /// This message gets to start the thread.
+    Ours,
+}
+
+impl Claim {
+    /// The claim of a message on the thread, `upserted` being whether the
+    /// message made the entry for the thread.
+    fn new(upserted: bool, existing: Option<LaunchThread>) -> Self {
+        if upserted {
+            return Self::Ours;
+        }
+
+        existing
+            .and_then(|t| t.thread)
+            .map_or(Self::Pending, Self::Thread)
+    }
+}
+
+/// Make the entry for the thread of a launch if there isn't one yet, only the
+/// message that made it starts the thread so there is never more than one.
+async fn claim_thread(db: &Database, channel: ChannelId, ll_id: &str) -> Claim {
+    let upserted = match db
+        .collection::<Document>("launch_threads")
+        .update_one(
+            thread_filter(channel, ll_id),
+            doc! {"$setOnInsert": {"channel": channel.get() as i64, "launch": ll_id}},
+            UpdateOptions::builder()
+                .upsert(true)
+                .build(),
+        )
+        .await
+    {
+        Ok(result) => {
+            result
+                .upserted_id
+                .is_some()
+        },
+        Err(e) => {
+            eprintln!("Can't claim launch thread: {e}");
+            return Claim::Pending;
+        },
+    };
+
+    let existing = if upserted {
+        None
+    } else {
+        find_thread(db, channel, ll_id).await
+    };
+
+    Claim::new(upserted, existing)
+}
+
+/// Let go of the entry for a thread that couldn't be started, so a later
+/// message can try again.
+async fn release_thread(db: &Database, channel: ChannelId, ll_id: &str) {
+    if let Err(e) = db
+        .collection::<Document>("launch_threads")
+        .delete_one(thread_filter(channel, ll_id), None)
+        .await
+    {
+        eprintln!("Can't remove launch thread: {e}");
+    }
+}
+
+/// The name of the thread for a launch, discord allows at most 100
+/// characters.
+fn thread_name(launch: &LaunchData) -> String {
+    launch
+        .launch_name
+        .chars()
+        .take(100)
+        .collect()
+}
+
+/// Send a reminder for a launch in the thread of the launch, the first
+/// reminder is sent in the channel and starts the thread.
+pub(super) async fn send_in_launch_thread(
+    http: &Http,
+    db: &Database,
+    channel: ChannelId,
+    launch: &LaunchData,
+    message: CreateMessage,
+) -> Result<Message, SerenityError> {
+    match claim_thread(db, channel, &launch.ll_id).await {
+        Claim::Thread(thread) => {
+            return thread
+                .send_message(http, message)
+                .await
+        },
+        Claim::Pending => {
+            return channel
+                .send_message(http, message)
+                .await
+        },
+        Claim::Ours => {},
+    }
+
+    let sent = match channel
+        .send_message(http, message)
+        .await
+    {
+        Ok(sent) => sent,
+        Err(e) => {
+            release_thread(db, channel, &launch.ll_id).await;
+            return Err(e);
+        },
+    };
+
+    // without a thread the messages just keep going to the channel
+    let thread = match channel
+        .create_thread_from_message(
+            http,
+            sent.id,
+            CreateThread::new(thread_name(launch))
+                .auto_archive_duration(AutoArchiveDuration::OneWeek),
+        )
+        .await
+    {
+        Ok(thread) => thread,
+        Err(e) => {
+            eprintln!("Can't start launch thread: {e}");
+            release_thread(db, channel, &launch.ll_id).await;
+            return Ok(sent);
+        },
+    };
+
+    if let Err(e) = db
+        .collection::<Document>("launch_threads")
+        .update_one(
+            thread_filter(channel, &launch.ll_id),
+            doc! {"$set": {"thread": thread.id.get() as i64}},
+            None,
+        )
+        .await
+    {
+        eprintln!("Can't store launch thread: {e}");
+    }
+
+    Ok(sent)
+}
+
+/// Where a notification about a launch goes for a guild, the thread of the
+/// launch in the notifications channel when the guild uses those and it has
+/// been started.
+pub(super) async fn notification_channel(
+    db: &Database,
+    settings: &GuildSettings,
+    launch: &LaunchData,
+) -> Option<ChannelId> {
+    let channel = settings.notifications_channel?;
+
+    if settings.launch_threads {
+        if let Some(thread) = find_thread(db, channel, &launch.ll_id)
+            .await
+            .and_then(|t| t.thread)
+        {
+            return Some(thread);
+        }
+    }
+
+    Some(channel)
+}
+
+/// Archive the threads of a launch that is over, they won't get any more
+/// messages.
+pub(super) async fn archive_launch_threads(http: &Http, db: &Database, ll_id: &str) {
+    let collection = db.collection::<LaunchThread>("launch_threads");
+
+    let threads: Vec<LaunchThread> = match collection
+        .find(doc! {"launch": ll_id}, None)
+        .await
+    {
+        Ok(cursor) => {
+            cursor
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("Can't read launch threads: {e}");
+                    Vec::new()
+                })
+        },
+        Err(e) => {
+            eprintln!("Can't read launch threads: {e}");
+            Vec::new()
+        },
+    };
+
+    for thread in threads
+        .into_iter()
+        .filter_map(|t| t.thread)
+    {
+        if let Err(e) = thread
+            .edit_thread(http, EditThread::new().archived(true))
+            .await
+        {
+            eprintln!("Can't archive launch thread: {e}");
+        }
+    }
+
+    if let Err(e) = collection
+        .delete_many(doc! {"launch": ll_id}, None)
+        .await
+    {
+        eprintln!("Can't remove launch threads: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson;
+
+    use super::*;
+
+    #[test]
+    fn threads_are_per_channel() {
+        let filter = thread_filter(ChannelId::new(10), "a1");
+        assert_eq!(filter.get_i64("channel"), Ok(10));
+        assert_eq!(filter.get_str("launch"), Ok("a1"));
+        assert_eq!(filter.len(), 2);
+
+        let thread = |thread: Option<u64>| {
+            LaunchThread {
+                channel: ChannelId::new(10),
+                launch: "a1".to_owned(),
+                thread: thread.map(ChannelId::new),
+            }
+        };
+        assert_eq!(Claim::new(true, None), Claim::Ours);
+        assert_eq!(Claim::new(false, None), Claim::Pending);
+        assert_eq!(
+            Claim::new(false, Some(thread(None))),
+            Claim::Pending
+        );
+        assert_eq!(
+            Claim::new(false, Some(thread(Some(20)))),
+            Claim::Thread(ChannelId::new(20))
+        );
+    }
+
+    #[test]
+    fn claimed_threads_read_back_before_they_are_started() {
+        let claimed: LaunchThread = bson::from_document(doc! {
+            "channel": 10_i64,
+            "launch": "a1",
+        })
+        .unwrap();
+        assert!(claimed
+            .thread
+            .is_none());
+
+        let name = thread_name(&LaunchData {
+            launch_name: "x".repeat(150),
+            ..LaunchData::default()
+        });
+        assert_eq!(
+            name.chars()
+                .count(),
+            100
+        );
+    }
+}
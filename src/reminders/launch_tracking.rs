@@ -9,7 +9,17 @@ use futures::stream::{
     FuturesUnordered,
     StreamExt,
 };
-use mongodb::Database;
+use mongodb::{
+    bson::{
+        doc,
+        Document,
+    },
+    options::{
+        ReplaceOptions,
+        UpdateOptions,
+    },
+    Database,
+};
 use reqwest::{
     header::AUTHORIZATION,
     Result,
@@ -20,16 +30,25 @@ use serenity::{
 };
 
 use super::{
+    countdown_tracking::track_countdown_launches,
+    notify_booster_assigned,
+    notify_landing,
     notify_outcome,
     notify_scrub,
 };
 use crate::{
     models::launches::{
+        BoosterData,
         LaunchContainer,
         LaunchData,
+        LaunchRecord,
         LaunchStatus,
     },
     utils::{
+        catalog::{
+            update_catalog,
+            Catalog,
+        },
         constants::{
             DEFAULT_CLIENT,
             LL_KEY,
@@ -38,7 +57,12 @@ use crate::{
     },
 };
 
-pub async fn launch_tracking(http: Arc<Http>, db: Database, cache: Arc<RwLock<Vec<LaunchData>>>) {
+pub async fn launch_tracking(
+    http: Arc<Http>,
+    db: Database,
+    cache: Arc<RwLock<Vec<LaunchData>>>,
+    catalog: Arc<RwLock<Catalog>>,
+) {
     println!("getting launch information");
 
     // Get new set of launches
@@ -70,17 +94,28 @@ pub async fn launch_tracking(http: Arc<Http>, db: Database, cache: Arc<RwLock<Ve
 
     println!("got {} launches", launches.len());
 
-    let mut launch_cache = cache
-        .write()
-        .await;
+    let old_launches = cache
+        .read()
+        .await
+        .clone();
+
+    update_catalog(&db, &catalog, &launches).await;
+    store_first_nets(&db, &launches, &old_launches).await;
+    // countdowns look for launches that left the cache in the history, so the
+    // outcome has to be there before the cache gets updated
+    store_outcomes(&db, &launches, &old_launches).await;
+    track_countdown_launches(&db, &launches).await;
 
-    // Update launch cache and free the lock
-    let old_launches = launch_cache.clone();
-    *launch_cache = launches.clone();
-    std::mem::drop(launch_cache);
+    *cache
+        .write()
+        .await = launches.clone();
     let five_minutes = Duration::minutes(5);
 
     // Send out notifications
+    let catalog = catalog
+        .read()
+        .await
+        .clone();
     let notif_http = http.clone();
     let notif_res = tokio::spawn(async move {
         launches
@@ -97,7 +132,15 @@ pub async fn launch_tracking(http: Arc<Http>, db: Database, cache: Arc<RwLock<Ve
                         }
                     })
             })
-            .map(|l| notify_scrub(notif_http.clone(), db.clone(), l.0, l.1))
+            .map(|l| {
+                notify_scrub(
+                    notif_http.clone(),
+                    db.clone(),
+                    &catalog,
+                    l.0,
+                    l.1,
+                )
+            })
             .collect::<FuturesUnordered<_>>()
             .collect::<Vec<_>>()
             .await;
@@ -105,10 +148,8 @@ pub async fn launch_tracking(http: Arc<Http>, db: Database, cache: Arc<RwLock<Ve
         launches
             .iter()
             .filter(|nl| {
-                matches!(
-                    nl.status,
-                    LaunchStatus::Success | LaunchStatus::Failure | LaunchStatus::PartialFailure
-                )
+                nl.status
+                    .is_finished()
             })
             .filter(|nl| {
                 old_launches
@@ -125,7 +166,65 @@ pub async fn launch_tracking(http: Arc<Http>, db: Database, cache: Arc<RwLock<Ve
                     })
             })
             .cloned()
-            .map(|l| notify_outcome(notif_http.clone(), db.clone(), l))
+            .map(|l| {
+                notify_outcome(
+                    notif_http.clone(),
+                    db.clone(),
+                    &catalog,
+                    l,
+                )
+            })
+            .collect::<FuturesUnordered<_>>()
+            .collect::<Vec<_>>()
+            .await;
+
+        // without earlier launches every booster would look newly assigned
+        if !old_launches.is_empty() {
+            launches
+                .iter()
+                .map(|nl| {
+                    let ol = old_launches
+                        .iter()
+                        .find(|ol| nl.ll_id == ol.ll_id);
+                    (nl, assigned_boosters(ol, nl))
+                })
+                .filter(|(_, serials)| !serials.is_empty())
+                .map(|(l, serials)| {
+                    notify_booster_assigned(
+                        notif_http.clone(),
+                        db.clone(),
+                        &catalog,
+                        l.clone(),
+                        serials,
+                    )
+                })
+                .collect::<FuturesUnordered<_>>()
+                .collect::<Vec<_>>()
+                .await;
+        }
+
+        launches
+            .iter()
+            .filter_map(|nl| {
+                old_launches
+                    .iter()
+                    .find(|ol| nl.ll_id == ol.ll_id)
+                    .map(|ol| (nl, landed_boosters(ol, nl)))
+            })
+            .flat_map(|(l, boosters)| {
+                boosters
+                    .into_iter()
+                    .map(move |b| (l.clone(), b))
+            })
+            .map(|(l, b)| {
+                notify_landing(
+                    notif_http.clone(),
+                    db.clone(),
+                    &catalog,
+                    l,
+                    b,
+                )
+            })
             .collect::<FuturesUnordered<_>>()
             .collect::<Vec<_>>()
             .await;
@@ -139,13 +238,175 @@ pub async fn launch_tracking(http: Arc<Http>, db: Database, cache: Arc<RwLock<Ve
     }
 }
 
+fn booster_serials(launch: &LaunchData) -> impl Iterator<Item = &str> {
+    launch
+        .boosters
+        .iter()
+        .filter_map(|b| {
+            b.serial
+                .as_deref()
+        })
+}
+
+/// The serials of the boosters that got assigned to a launch since the last
+/// update.
+fn assigned_boosters(old: Option<&LaunchData>, new: &LaunchData) -> Vec<String> {
+    if new
+        .status
+        .is_finished()
+    {
+        return Vec::new();
+    }
+
+    booster_serials(new)
+        .filter(|serial| {
+            old.is_none_or(|ol| !booster_serials(ol).any(|old_serial| old_serial == *serial))
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+/// The boosters of a finished launch of which the landing outcome became known
+/// since the last update.
+fn landed_boosters(old: &LaunchData, new: &LaunchData) -> Vec<BoosterData> {
+    let known = |b: &BoosterData| {
+        b.landing_success
+            .is_some()
+            || b.landing_attempt == Some(false)
+    };
+
+    if !new
+        .status
+        .is_finished()
+    {
+        return Vec::new();
+    }
+
+    new.boosters
+        .iter()
+        .filter(|b| known(b))
+        .filter(|b| {
+            b.serial
+                .is_some()
+        })
+        .filter(|b| {
+            !old.status
+                .is_finished()
+                || !old
+                    .boosters
+                    .iter()
+                    .any(|ob| ob.serial == b.serial && known(ob))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Remember the NET of launches the first time they are seen, to know how much
+/// they slipped once they have launched.
+async fn store_first_nets(db: &Database, launches: &[LaunchData], old_launches: &[LaunchData]) {
+    let collection = db.collection::<Document>("launch_first_nets");
+
+    for launch in launches
+        .iter()
+        .filter(|nl| {
+            !old_launches
+                .iter()
+                .any(|ol| ol.ll_id == nl.ll_id)
+        })
+    {
+        if let Err(e) = collection
+            .update_one(
+                doc! {"ll_id": &launch.ll_id},
+                doc! {"$setOnInsert": {"net": launch.net.timestamp()}},
+                UpdateOptions::builder()
+                    .upsert(true)
+                    .build(),
+            )
+            .await
+        {
+            eprintln!("Can't store first NET of launch: {e}");
+        }
+    }
+}
+
+/// Whether a finished launch has to be written to the launch history, which is
+/// when its outcome changed since the last update or when it hasn't been seen
+/// since starting.
+fn outcome_changed(old: Option<&LaunchData>, new: &LaunchData) -> bool {
+    new.status
+        .is_finished()
+        && old.is_none_or(|ol| {
+            ol.status != new.status
+                || ol.failreason != new.failreason
+                || !landed_boosters(ol, new).is_empty()
+        })
+}
+
+/// Keep the launches that have finished, so they can still be looked up once
+/// they drop out of the upcoming launches.
+async fn store_outcomes(db: &Database, launches: &[LaunchData], old_launches: &[LaunchData]) {
+    let first_nets = db.collection::<Document>("launch_first_nets");
+    let collection = db.collection::<LaunchRecord>("launch_history");
+
+    for launch in launches
+        .iter()
+        .filter(|nl| {
+            outcome_changed(
+                old_launches
+                    .iter()
+                    .find(|ol| ol.ll_id == nl.ll_id),
+                nl,
+            )
+        })
+    {
+        let net_slip = match first_nets
+            .find_one(doc! {"ll_id": &launch.ll_id}, None)
+            .await
+        {
+            Ok(first) => {
+                first
+                    .and_then(|d| {
+                        d.get_i64("net")
+                            .ok()
+                    })
+                    .map(|first_net| {
+                        launch
+                            .net
+                            .timestamp()
+                            - first_net
+                    })
+            },
+            Err(e) => {
+                eprintln!("Can't get first NET of launch: {e}");
+                None
+            },
+        };
+
+        if let Err(e) = collection
+            .replace_one(
+                doc! {"ll_id": &launch.ll_id},
+                LaunchRecord {
+                    launch: launch.clone(),
+                    net_slip,
+                },
+                ReplaceOptions::builder()
+                    .upsert(true)
+                    .build(),
+            )
+            .await
+        {
+            eprintln!("Can't store launch outcome: {e}");
+        }
+    }
+}
+
 async fn get_new_launches() -> Result<LaunchContainer> {
     let mut params = HashMap::new();
     params.insert("limit", "100");
     params.insert("mode", "detailed");
 
     DEFAULT_CLIENT
-        .get("https://ll.thespacedevs.com/2.0.0/launch/upcoming/")
+        .get("https://ll.thespacedevs.com/2.2.0/launch/upcoming/")
         .header(AUTHORIZATION, LL_KEY.as_str())
         .query(&params)
         .send()
@@ -154,3 +415,525 @@ async fn get_new_launches() -> Result<LaunchContainer> {
         .json()
         .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn booster(serial: &str, landing_success: Option<bool>) -> BoosterData {
+        BoosterData {
+            stage_type: "Core".to_owned(),
+            serial: Some(serial.to_owned()),
+            flight_number: Some(3),
+            flight_proven: true,
+            landing_attempt: Some(true),
+            landing_success,
+            landing_location: None,
+        }
+    }
+
+    fn launch(status: LaunchStatus, boosters: Vec<BoosterData>) -> LaunchData {
+        LaunchData {
+            ll_id: "a1".to_owned(),
+            status,
+            boosters,
+            ..LaunchData::default()
+        }
+    }
+
+    #[test]
+    fn newly_assigned_boosters() {
+        let old = launch(
+            LaunchStatus::Go,
+            vec![booster("B1058", None)],
+        );
+        let new = launch(
+            LaunchStatus::Go,
+            vec![
+                booster("B1058", None),
+                booster("B1060", None),
+            ],
+        );
+
+        assert_eq!(
+            assigned_boosters(Some(&old), &new),
+            vec!["B1060"]
+        );
+        assert_eq!(
+            assigned_boosters(None, &new),
+            vec!["B1058", "B1060"]
+        );
+        assert!(assigned_boosters(Some(&new), &new).is_empty());
+    }
+
+    #[test]
+    fn landings_are_reported_once() {
+        let pending = launch(
+            LaunchStatus::Go,
+            vec![booster("B1058", None)],
+        );
+        let launched = launch(
+            LaunchStatus::Success,
+            vec![booster("B1058", None)],
+        );
+        let landed = launch(
+            LaunchStatus::Success,
+            vec![booster("B1058", Some(true))],
+        );
+
+        assert!(landed_boosters(&pending, &launched).is_empty());
+        assert_eq!(
+            landed_boosters(&launched, &landed).len(),
+            1
+        );
+        assert_eq!(
+            landed_boosters(&pending, &landed).len(),
+            1
+        );
+        assert!(landed_boosters(&landed, &landed).is_empty());
+    }
+
+    /// An upcoming launch the way the detailed mode of launch library 2.2.0
+    /// gives it.
+    const LL2_LAUNCH: &str = r#"{
+        "count": 1,
+        "next": null,
+        "previous": null,
+        "results": [{
+            "id": "e3df2ecd-c239-472f-95e4-2b89b4f75800",
+            "url": "https://ll.thespacedevs.com/2.2.0/launch/e3df2ecd-c239-472f-95e4-2b89b4f75800/",
+            "slug": "falcon-9-block-5-starlink-group-6-2",
+            "flightclub_url": null,
+            "r_spacex_api_id": null,
+            "name": "Falcon 9 Block 5 | Starlink Group 6-2",
+            "status": {
+                "id": 1,
+                "name": "Go for Launch",
+                "abbrev": "Go",
+                "description": "Current T-0 confirmed by official or reliable sources."
+            },
+            "last_updated": "2023-10-18T21:06:21Z",
+            "updates": [],
+            "net": "2023-10-21T03:01:00Z",
+            "net_precision": {
+                "id": 1,
+                "name": "Minute",
+                "abbrev": "MIN",
+                "description": "The T-0 is accurate to the minute."
+            },
+            "window_end": "2023-10-21T06:31:00Z",
+            "window_start": "2023-10-21T03:01:00Z",
+            "probability": 90,
+            "weather_concerns": "Cumulus Clouds",
+            "holdreason": "",
+            "failreason": "",
+            "hashtag": null,
+            "launch_service_provider": {
+                "id": 121,
+                "url": "https://ll.thespacedevs.com/2.2.0/agencies/121/",
+                "name": "SpaceX",
+                "featured": true,
+                "type": "Commercial",
+                "country_code": "USA",
+                "abbrev": "SpX",
+                "description": "Space Exploration Technologies Corp., known as SpaceX, is an American aerospace manufacturer and space transport services company headquartered in Hawthorne, California.",
+                "administrator": "CEO: Elon Musk",
+                "founding_year": "2002",
+                "launchers": "Falcon | Starship",
+                "spacecraft": "Dragon",
+                "parent": null,
+                "image_url": null,
+                "logo_url": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/logo/spacex_logo_20220826094919.png"
+            },
+            "rocket": {
+                "id": 7952,
+                "configuration": {
+                    "id": 164,
+                    "url": "https://ll.thespacedevs.com/2.2.0/config/launcher/164/",
+                    "name": "Falcon 9",
+                    "active": true,
+                    "reusable": true,
+                    "description": "Falcon 9 is a two-stage rocket designed and manufactured by SpaceX for the reliable and safe transport of satellites and the Dragon spacecraft into orbit.",
+                    "family": "Falcon",
+                    "full_name": "Falcon 9 Block 5",
+                    "manufacturer": {
+                        "id": 121,
+                        "url": "https://ll.thespacedevs.com/2.2.0/agencies/121/",
+                        "name": "SpaceX",
+                        "type": "Commercial"
+                    },
+                    "program": [],
+                    "variant": "Block 5",
+                    "alias": "",
+                    "min_stage": 1,
+                    "max_stage": 2,
+                    "length": 70.0,
+                    "diameter": 3.65,
+                    "maiden_flight": "2018-05-11",
+                    "launch_cost": "52000000",
+                    "launch_mass": 549,
+                    "leo_capacity": 22800.0,
+                    "gto_capacity": 8300.0,
+                    "to_thrust": 7607,
+                    "apogee": null,
+                    "vehicle_range": null,
+                    "image_url": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/images/falcon_9_block__image_20210506060831.jpg",
+                    "info_url": "https://www.spacex.com/vehicles/falcon-9/",
+                    "wiki_url": "https://en.wikipedia.org/wiki/Falcon_9",
+                    "total_launch_count": 233,
+                    "consecutive_successful_launches": 220,
+                    "successful_launches": 232,
+                    "failed_launches": 0,
+                    "pending_launches": 96,
+                    "attempted_landings": 227,
+                    "successful_landings": 224,
+                    "failed_landings": 3,
+                    "consecutive_successful_landings": 188
+                },
+                "launcher_stage": [{
+                    "id": 2179,
+                    "type": "Core",
+                    "reused": true,
+                    "launcher_flight_number": 11,
+                    "launcher": {
+                        "id": 1208,
+                        "url": "https://ll.thespacedevs.com/2.2.0/launcher/1208/",
+                        "details": "Falcon 9 first stage, used for Starlink missions.",
+                        "flight_proven": true,
+                        "serial_number": "B1069",
+                        "status": "active",
+                        "image_url": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/images/b1069_image_20220807.jpg",
+                        "successful_landings": 10,
+                        "attempted_landings": 10,
+                        "flights": 11,
+                        "last_launch_date": "2023-10-21T03:01:00Z",
+                        "first_launch_date": "2021-08-29T07:14:49Z"
+                    },
+                    "landing": {
+                        "id": 2658,
+                        "attempt": true,
+                        "success": null,
+                        "description": "The Falcon 9 first stage will land on the drone ship A Shortfall of Gravitas.",
+                        "downrange_distance": 600.0,
+                        "location": {
+                            "id": 2,
+                            "name": "A Shortfall of Gravitas",
+                            "abbrev": "ASOG",
+                            "description": "A Shortfall of Gravitas is an autonomous spaceport drone ship.",
+                            "location": null,
+                            "successful_landings": 68
+                        },
+                        "type": {
+                            "id": 1,
+                            "name": "Autonomous Spaceport Drone Ship",
+                            "abbrev": "ASDS",
+                            "description": "An autonomous spaceport drone ship is an ocean-going vessel."
+                        }
+                    },
+                    "previous_flight_date": "2023-09-08T03:12:00Z",
+                    "turn_around_time_days": 43,
+                    "previous_flight": null
+                }],
+                "spacecraft_stage": null
+            },
+            "mission": {
+                "id": 6599,
+                "name": "Starlink Group 6-2",
+                "description": "A batch of 22 satellites for the Starlink mega-constellation - SpaceX's project for space-based Internet communication system.",
+                "launch_designator": null,
+                "type": "Communications",
+                "orbit": {
+                    "id": 8,
+                    "name": "Low Earth Orbit",
+                    "abbrev": "LEO"
+                },
+                "agencies": [],
+                "info_urls": [],
+                "vid_urls": []
+            },
+            "pad": {
+                "id": 80,
+                "url": "https://ll.thespacedevs.com/2.2.0/pad/80/",
+                "agency_id": 121,
+                "name": "Space Launch Complex 40",
+                "description": null,
+                "info_url": null,
+                "wiki_url": "https://en.wikipedia.org/wiki/Cape_Canaveral_Space_Launch_Complex_40",
+                "map_url": "https://www.google.com/maps?q=28.56194122,-80.57735736",
+                "latitude": "28.56194122",
+                "longitude": "-80.57735736",
+                "location": {
+                    "id": 12,
+                    "url": "https://ll.thespacedevs.com/2.2.0/location/12/",
+                    "name": "Cape Canaveral, FL, USA",
+                    "country_code": "USA",
+                    "description": "",
+                    "map_image": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/launch_images/location_12_20200803142519.jpg",
+                    "timezone_name": "America/New_York",
+                    "total_launch_count": 925,
+                    "total_landing_count": 38
+                },
+                "country_code": "USA",
+                "map_image": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/launch_images/pad_80_20200803143323.jpg",
+                "total_launch_count": 238,
+                "orbital_launch_attempt_count": 238
+            },
+            "webcast_live": false,
+            "image": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/launcher_images/falcon_9_block__image_20210506060831.jpg",
+            "infographic": null,
+            "program": [],
+            "orbital_launch_attempt_count": 6393,
+            "location_launch_attempt_count": 921,
+            "pad_launch_attempt_count": 236,
+            "agency_launch_attempt_count": 296,
+            "orbital_launch_attempt_count_year": 175,
+            "location_launch_attempt_count_year": 58,
+            "pad_launch_attempt_count_year": 53,
+            "agency_launch_attempt_count_year": 76,
+            "infoURLs": [],
+            "vidURLs": [{
+                "priority": 10,
+                "source": "youtube.com",
+                "publisher": "SpaceX",
+                "title": "Starlink Mission",
+                "description": "SpaceX is targeting Saturday, October 21 for a Falcon 9 launch of 22 Starlink satellites to low-Earth orbit.",
+                "feature_image": "https://i.ytimg.com/vi/example/maxresdefault_live.jpg",
+                "url": "https://www.youtube.com/watch?v=example",
+                "type": {
+                    "id": 1,
+                    "name": "Official Webcast"
+                },
+                "language": {
+                    "id": 1,
+                    "name": "English",
+                    "code": "en"
+                },
+                "start_time": null,
+                "end_time": null
+            }],
+            "timeline": [],
+            "mission_patches": []
+        }]
+    }"#;
+
+    /// A page of upcoming launches that continues on a next page, with a launch
+    /// that has little more than a rough date yet.
+    const LL2_UPCOMING_PAGE: &str = r#"{
+        "count": 312,
+        "next": "https://ll.thespacedevs.com/2.2.0/launch/upcoming/?limit=1&mode=detailed&offset=1",
+        "previous": null,
+        "results": [{
+            "id": "7b5f3c8e-0a3c-4f4e-9a43-0a5c3b6f1d2e",
+            "url": "https://ll.thespacedevs.com/2.2.0/launch/7b5f3c8e-0a3c-4f4e-9a43-0a5c3b6f1d2e/",
+            "slug": "long-march-2c-unknown-payload",
+            "flightclub_url": null,
+            "r_spacex_api_id": null,
+            "name": "Long March 2C | Unknown Payload",
+            "status": {
+                "id": 2,
+                "name": "To Be Determined",
+                "abbrev": "TBD",
+                "description": "Current T-0 is an estimate and not confirmed."
+            },
+            "last_updated": "2023-10-12T08:40:11Z",
+            "updates": [],
+            "net": "2023-11-30T00:00:00Z",
+            "net_precision": {
+                "id": 7,
+                "name": "Month",
+                "abbrev": "MON",
+                "description": "The T-0 is accurate to the month."
+            },
+            "window_end": "2023-11-30T00:00:00Z",
+            "window_start": "2023-11-30T00:00:00Z",
+            "probability": null,
+            "weather_concerns": null,
+            "holdreason": null,
+            "failreason": null,
+            "hashtag": null,
+            "launch_service_provider": {
+                "id": 88,
+                "url": "https://ll.thespacedevs.com/2.2.0/agencies/88/",
+                "name": "China Aerospace Science and Technology Corporation",
+                "type": "Government"
+            },
+            "rocket": {
+                "id": 8120,
+                "configuration": {
+                    "id": 25,
+                    "url": "https://ll.thespacedevs.com/2.2.0/config/launcher/25/",
+                    "name": "Long March 2C",
+                    "active": true,
+                    "reusable": false,
+                    "family": "Long March 2",
+                    "full_name": "Long March 2C",
+                    "variant": "C"
+                },
+                "launcher_stage": [],
+                "spacecraft_stage": null
+            },
+            "mission": null,
+            "pad": {
+                "id": 36,
+                "url": "https://ll.thespacedevs.com/2.2.0/pad/36/",
+                "agency_id": null,
+                "name": "Launch Complex 3",
+                "description": null,
+                "info_url": null,
+                "wiki_url": "",
+                "map_url": "https://www.google.com/maps?q=28.2468,102.0263",
+                "latitude": "28.2468",
+                "longitude": "102.0263",
+                "location": {
+                    "id": 16,
+                    "url": "https://ll.thespacedevs.com/2.2.0/location/16/",
+                    "name": "Xichang Satellite Launch Center, People's Republic of China",
+                    "country_code": "CHN",
+                    "description": "",
+                    "map_image": null,
+                    "timezone_name": "Asia/Shanghai",
+                    "total_launch_count": 198,
+                    "total_landing_count": 0
+                },
+                "country_code": "CHN",
+                "map_image": null,
+                "total_launch_count": 107,
+                "orbital_launch_attempt_count": 107
+            },
+            "webcast_live": false,
+            "image": null,
+            "infographic": null,
+            "program": [],
+            "infoURLs": [],
+            "vidURLs": [],
+            "timeline": [],
+            "mission_patches": []
+        }]
+    }"#;
+
+    #[test]
+    fn reads_launch_library_launches() {
+        let container: LaunchContainer =
+            serde_json::from_str(LL2_LAUNCH).expect("launch library 2.2.0 launches should parse");
+        let launch = LaunchData::from(
+            container
+                .results
+                .into_iter()
+                .next()
+                .unwrap(),
+        );
+
+        assert_eq!(launch.status, LaunchStatus::Go);
+        assert_eq!(launch.payload, "Starlink Group 6-2");
+        assert_eq!(launch.vehicle, "Falcon 9 Block 5");
+        assert_eq!(launch.lsp_id, Some(121));
+        assert_eq!(
+            launch.launch_window,
+            Duration::hours(3) + Duration::minutes(30)
+        );
+        assert_eq!(launch.failreason, None);
+        assert_eq!(launch.probability, Some(90));
+        assert_eq!(
+            launch
+                .weather_concerns
+                .as_deref(),
+            Some("Cumulus Clouds")
+        );
+        assert_eq!(
+            launch
+                .net_precision
+                .as_deref(),
+            Some("Minute")
+        );
+        assert_eq!(
+            launch.boosters[0]
+                .serial
+                .as_deref(),
+            Some("B1069")
+        );
+        assert_eq!(
+            launch
+                .pad
+                .map(|p| p.location_launch_count),
+            Some(925)
+        );
+        assert_eq!(
+            launch
+                .vid_urls
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn outcomes_are_stored_when_they_change() {
+        let pending = launch(
+            LaunchStatus::Go,
+            vec![booster("B1058", None)],
+        );
+        let launched = launch(
+            LaunchStatus::Success,
+            vec![booster("B1058", None)],
+        );
+        let landed = launch(
+            LaunchStatus::Success,
+            vec![booster("B1058", Some(true))],
+        );
+
+        assert!(!outcome_changed(None, &pending));
+        assert!(outcome_changed(None, &launched));
+        assert!(outcome_changed(
+            Some(&pending),
+            &launched
+        ));
+        assert!(!outcome_changed(
+            Some(&launched),
+            &launched
+        ));
+        assert!(outcome_changed(
+            Some(&launched),
+            &landed
+        ));
+    }
+
+    #[test]
+    fn reads_uncertain_launches_and_the_next_page() {
+        let container: LaunchContainer = serde_json::from_str(LL2_UPCOMING_PAGE)
+            .expect("launch library 2.2.0 upcoming launches should parse");
+        assert!(container
+            .next
+            .is_some());
+
+        let launch = LaunchData::from(
+            container
+                .results
+                .into_iter()
+                .next()
+                .unwrap(),
+        );
+        assert_eq!(launch.status, LaunchStatus::Tbd);
+        assert_eq!(launch.vehicle, "Long March 2C");
+        assert_eq!(launch.lsp_id, Some(88));
+        assert_eq!(launch.launch_window, Duration::zero());
+        assert_eq!(launch.probability, None);
+        assert_eq!(launch.weather_concerns, None);
+        assert_eq!(
+            launch
+                .net_precision
+                .as_deref(),
+            Some("Month")
+        );
+        assert!(launch
+            .boosters
+            .is_empty());
+        assert!(launch
+            .vid_urls
+            .is_empty());
+        assert_eq!(
+            launch
+                .pad
+                .map(|p| p.country_code),
+            Some("CHN".to_owned())
+        );
+    }
+}
@@ -1,11 +1,33 @@
 mod change_notifications;
+mod countdown_tracking;
+mod event_tracking;
 mod filtering;
+mod launch_pings;
+mod launch_threads;
 mod launch_tracking;
+mod notice;
+mod plain_text;
 mod reminder_tracking;
+mod templates;
 
 use change_notifications::{
+    notify_booster_assigned,
+    notify_landing,
     notify_outcome,
     notify_scrub,
 };
+pub use countdown_tracking::{
+    countdown_embed,
+    countdown_tracking,
+};
+use event_tracking::event_tracking;
+pub use launch_pings::LAUNCH_PINGS_COMPONENT;
 use launch_tracking::launch_tracking;
+pub use notice::Notice;
+pub use plain_text::plain_messages;
 pub use reminder_tracking::reminder_tracking;
+pub use templates::{
+    check_template,
+    preview_templates,
+    PLACEHOLDERS,
+};
@@ -0,0 +1,132 @@
+use serenity::{
+    builder::{
+        CreateEmbed,
+        CreateEmbedAuthor,
+    },
+    model::{
+        Colour,
+        Timestamp,
+    },
+};
+
+use crate::utils::constants::{
+    DEFAULT_COLOR,
+    DEFAULT_ICON,
+};
+
+/// A reminder or notification, kept as plain parts so the same message can be
+/// sent as an embed or as text.
+#[derive(Clone, Debug)]
+pub struct Notice {
+    /// The line of text that goes above the embed.
+    pub content: Option<String>,
+    pub author: String,
+    pub title: Option<String>,
+    pub description: String,
+    pub color: Colour,
+    pub thumbnail: Option<String>,
+    pub timestamp: Timestamp,
+}
+
+impl Notice {
+    pub fn new(author: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            content: None,
+            author: author.into(),
+            title: None,
+            description: description.into(),
+            color: DEFAULT_COLOR.into(),
+            thumbnail: None,
+            timestamp: Timestamp::now(),
+        }
+    }
+
+    /// A notice with the look of `default_embed`.
+    pub fn okto(description: impl Into<String>, success: bool) -> Self {
+        let mut notice = Self::new("OKTO", description);
+        if !success {
+            notice.color = Colour::RED;
+        }
+        notice
+    }
+
+    pub fn embed(&self) -> CreateEmbed {
+        let mut e = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new(&self.author).icon_url(DEFAULT_ICON))
+            .color(self.color)
+            .timestamp(self.timestamp);
+
+        if let Some(title) = &self.title {
+            e = e.title(title);
+        }
+        // discord doesn't take an empty description
+        if !self
+            .description
+            .trim()
+            .is_empty()
+        {
+            e = e.description(&self.description);
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            e = e.thumbnail(thumbnail);
+        }
+
+        e
+    }
+
+    /// The notice as markdown, for the channels and users that get their
+    /// messages without embeds. The line of text that would go above the
+    /// embed comes first.
+    pub fn text(&self) -> String {
+        [
+            self.content
+                .clone(),
+            Some(format!("**{}**", self.author)),
+            self.title
+                .as_ref()
+                .map(|title| format!("**{title}**")),
+            Some(
+                self.description
+                    .trim_end()
+                    .to_owned(),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|line| {
+            !line
+                .trim()
+                .is_empty()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_as_markdown() {
+        let mut notice = Notice::new(
+            "15 minutes till launch",
+            "**Payload:** Starlink Group 6-2\n",
+        );
+        notice.thumbnail = Some("https://example.com/rocket.png".to_owned());
+
+        assert_eq!(
+            notice.text(),
+            "**15 minutes till launch**\n\
+            **Payload:** Starlink Group 6-2"
+        );
+
+        notice.content = Some("Liftoff soon!".to_owned());
+        notice.title = Some("Starlink".to_owned());
+        notice.description = String::new();
+        assert_eq!(
+            notice.text(),
+            "Liftoff soon!\n**15 minutes till launch**\n**Starlink**"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use serenity::builder::{
+    CreateEmbed,
+    CreateMessage,
+};
+
+/// The most characters discord allows in the text of a message.
+pub(super) const MESSAGE_LENGTH: usize = 2000;
+
+fn length(text: &str) -> usize {
+    text.chars()
+        .count()
+}
+
+/// Cut off text that is longer than discord allows for where it goes.
+pub(super) fn truncate(text: &str, max: usize) -> String {
+    if length(text) <= max {
+        return text.to_owned();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    text.chars()
+        .take(max - 1)
+        .chain(['…'])
+        .collect()
+}
+
+/// Put the mentions after a text, in messages of their own when they don't
+/// fit with it. Mentions are never cut in half.
+pub(super) fn with_mentions(text: String, mentions: &str) -> Vec<String> {
+    if length(&text) + length(mentions) <= MESSAGE_LENGTH {
+        let message = text + mentions;
+        return if message
+            .trim()
+            .is_empty()
+        {
+            Vec::new()
+        } else {
+            vec![message]
+        };
+    }
+
+    let mut messages: Vec<String> = Vec::new();
+    if !text
+        .trim()
+        .is_empty()
+    {
+        messages.push(text);
+    }
+
+    let mut current = String::new();
+    for mention in mentions.split_whitespace() {
+        if !current.is_empty() && length(&current) + 1 + length(mention) > MESSAGE_LENGTH {
+            messages.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(mention);
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    }
+
+    messages
+}
+
+/// A message with embeds and the text that goes above them, the mentions that
+/// don't fit with the text follow in messages of their own.
+pub(super) fn embed_messages(
+    embeds: Vec<CreateEmbed>,
+    text: &str,
+    mentions: &str,
+) -> Vec<CreateMessage> {
+    let mut texts = with_mentions(truncate(text, MESSAGE_LENGTH), mentions).into_iter();
+
+    let mut first = CreateMessage::new().embeds(embeds);
+    if let Some(text) = texts.next() {
+        first = first.content(text);
+    }
+
+    std::iter::once(first)
+        .chain(texts.map(|text| CreateMessage::new().content(text)))
+        .collect()
+}
+
+/// Put the texts in as few messages as discord allows, the mentions go at
+/// the end of the last one or after it when there is no room left.
+pub fn plain_messages<I>(texts: I, mentions: &str) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut messages: Vec<String> = Vec::new();
+    for text in texts {
+        if text
+            .trim()
+            .is_empty()
+        {
+            continue;
+        }
+
+        let text = truncate(&text, MESSAGE_LENGTH);
+        match messages.last_mut() {
+            Some(last) if length(last) + length(&text) + 2 <= MESSAGE_LENGTH => {
+                last.push_str("\n\n");
+                last.push_str(&text);
+            },
+            _ => messages.push(text),
+        }
+    }
+
+    let last = messages
+        .pop()
+        .unwrap_or_default();
+    messages.extend(with_mentions(last, mentions));
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_texts_in_as_few_messages_as_fit() {
+        let messages = plain_messages(
+            vec![
+                "a".repeat(900),
+                "b".repeat(900),
+                "c".repeat(900),
+            ],
+            " <@&1>",
+        );
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].len(), 1802);
+        assert!(messages[1].ends_with("c <@&1>"));
+
+        let long = plain_messages(vec!["d".repeat(3000)], "");
+        assert_eq!(length(&long[0]), MESSAGE_LENGTH);
+        assert!(long[0].ends_with('…'));
+
+        assert!(plain_messages(Vec::new(), "").is_empty());
+        assert!(plain_messages(vec![" \n".to_owned()], "").is_empty());
+        assert_eq!(truncate("abc", 0), "");
+    }
+
+    #[test]
+    fn mentions_that_dont_fit_get_messages_of_their_own() {
+        let mentions = " <@&123456789012345678>".repeat(200);
+        let messages = plain_messages(vec!["e".repeat(1900)], &mentions);
+
+        assert_eq!(messages[0], "e".repeat(1900));
+        assert!(messages
+            .iter()
+            .all(|m| length(m) <= MESSAGE_LENGTH && !m.is_empty()));
+        assert_eq!(
+            messages[1..]
+                .iter()
+                .flat_map(|m| m.split_whitespace())
+                .count(),
+            200
+        );
+    }
+}
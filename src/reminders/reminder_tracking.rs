@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    fmt::Write,
     str::FromStr,
     sync::Arc,
 };
@@ -9,13 +10,10 @@ use chrono::{
     NaiveDateTime,
     Utc,
 };
-use futures::{
-    future,
-    stream::{
-        self,
-        FuturesUnordered,
-        StreamExt,
-    },
+use futures::stream::{
+    self,
+    FuturesUnordered,
+    StreamExt,
 };
 use itertools::Itertools;
 use mongodb::{
@@ -29,42 +27,129 @@ use mongodb::{
 };
 use serenity::{
     builder::{
-        CreateEmbed,
-        CreateEmbedAuthor,
+        CreateActionRow,
+        CreateButton,
         CreateMessage,
     },
     http::Http,
-    model::Timestamp,
+    model::{
+        id::{
+            ChannelId,
+            GuildId,
+            RoleId,
+            UserId,
+        },
+        Timestamp,
+    },
     prelude::RwLock,
 };
 
 use super::{
-    filtering::passes_filters,
+    event_tracking,
+    filtering::{
+        launch_mentions,
+        passes_event_filters,
+        passes_filters,
+    },
+    launch_pings::{
+        launch_pings,
+        ping_button,
+    },
+    launch_threads::send_in_launch_thread,
     launch_tracking,
+    notice::Notice,
+    plain_text::{
+        embed_messages,
+        plain_messages,
+    },
+    templates::style_message,
 };
 use crate::{
     models::{
+        events::EventData,
         launches::{
             LaunchData,
             LaunchStatus,
         },
-        reminders::Reminder,
+        reminders::{
+            GuildSettings,
+            MentionKind,
+            Reminder,
+            ReminderSettings,
+            UserSettings,
+        },
     },
     utils::{
-        constants::{
-            DEFAULT_COLOR,
-            DEFAULT_ICON,
-        },
+        catalog::Catalog,
         error_log,
         format_duration,
+        launches::launch_conditions,
         reminders::{
+            format_mentions,
             get_guild_settings,
             get_user_settings,
+            guild_mentions,
         },
     },
 };
 
-pub async fn reminder_tracking(http: Arc<Http>, cache: Arc<RwLock<Vec<LaunchData>>>, db: Database) {
+/// The most embeds discord allows in a single message.
+const EMBEDS_PER_MESSAGE: usize = 10;
+
+/// Something that reminders get sent out for.
+#[derive(Clone)]
+enum Subject {
+    Launch(Box<LaunchData>),
+    Event(EventData),
+}
+
+impl Subject {
+    fn passes<T>(&self, settings: &T, catalog: &Catalog) -> bool
+    where
+        T: ReminderSettings,
+    {
+        match self {
+            Self::Launch(l) => passes_filters(settings, catalog, l),
+            Self::Event(e) => passes_event_filters(settings, e),
+        }
+    }
+
+    fn notice(&self, diff: Duration) -> Notice {
+        match self {
+            Self::Launch(l) => reminder_notice(l, diff),
+            Self::Event(e) => event_reminder_notice(e, diff),
+        }
+    }
+}
+
+/// A subject that a reminder is due for in this tick.
+struct Due {
+    subject: Subject,
+    difference: Duration,
+}
+
+impl Due {
+    fn notice(&self) -> Notice {
+        self.subject
+            .notice(self.difference)
+    }
+
+    /// The reminder the way a guild wants it to look.
+    fn styled(&self, settings: &GuildSettings) -> Notice {
+        match &self.subject {
+            Subject::Launch(l) => styled_reminder(settings, l, self.difference),
+            Subject::Event(_) => self.notice(),
+        }
+    }
+}
+
+pub async fn reminder_tracking(
+    http: Arc<Http>,
+    cache: Arc<RwLock<Vec<LaunchData>>>,
+    events_cache: Arc<RwLock<Vec<EventData>>>,
+    catalog: Arc<RwLock<Catalog>>,
+    db: Database,
+) {
     // wait for client to have started
     tokio::time::sleep(std::time::Duration::from_secs(60)).await;
 
@@ -79,62 +164,95 @@ pub async fn reminder_tracking(http: Arc<Http>, cache: Arc<RwLock<Vec<LaunchData
                 http.clone(),
                 db.clone(),
                 cache.clone(),
+                catalog.clone(),
             ));
+            tokio::spawn(event_tracking(events_cache.clone()));
         }
 
         loop_count += 1;
 
-        let launches: Vec<LaunchData> = cache
+        let mut subjects: Vec<(String, NaiveDateTime, Subject)> = cache
             .read()
             .await
             .iter()
             .filter(|l| l.status == LaunchStatus::Go)
-            .cloned()
+            .map(|l| {
+                (
+                    l.ll_id
+                        .clone(),
+                    l.net,
+                    Subject::Launch(Box::new(l.clone())),
+                )
+            })
             .collect();
-        if launches.is_empty() {
+        subjects.extend(
+            events_cache
+                .read()
+                .await
+                .iter()
+                .map(|e| {
+                    (
+                        format!("event-{}", e.ll_id),
+                        e.date,
+                        Subject::Event(e.clone()),
+                    )
+                }),
+        );
+        if subjects.is_empty() {
             tokio::time::sleep(std::time::Duration::from_secs(55)).await;
             continue;
         }
 
+        subjects.sort_by_key(|(_, time, _)| *time);
+
         let now = Utc::now().timestamp();
+        let mut due: Vec<(Reminder, Due)> = Vec::new();
 
-        for l in launches {
-            let difference = l.net
+        for (key, time, subject) in subjects {
+            let difference = time
                 - NaiveDateTime::from_timestamp_opt(now, 0)
                     .expect("invalid timestamp for launch difference");
 
-            if let Some(dur) = reminded.get(&l.ll_id) {
+            if let Some(dur) = reminded.get(&key) {
                 if *dur == difference.num_minutes() {
                     continue;
                 }
             }
-            reminded.insert(
-                l.ll_id
-                    .clone(),
-                difference.num_minutes(),
-            );
+            reminded.insert(key, difference.num_minutes());
 
             if let Ok(Some(r)) = get_reminders(&db, difference.num_minutes()).await {
                 if let Ok(res) = bson::from_bson(r.into()) {
-                    let handle = tokio::spawn(execute_reminder(
-                        db.clone(),
-                        http.clone(),
+                    due.push((
                         res,
-                        l.clone(),
-                        difference,
+                        Due {
+                            subject,
+                            difference,
+                        },
                     ));
-
-                    if let Err(e) = handle.await {
-                        error_log(
-                            http.clone(),
-                            &format!("A panic happened in reminders: ```{e}```",),
-                        )
-                        .await
-                    }
                 }
             }
         }
 
+        if !due.is_empty() {
+            let handle = tokio::spawn(execute_reminders(
+                db.clone(),
+                http.clone(),
+                catalog
+                    .read()
+                    .await
+                    .clone(),
+                due,
+            ));
+
+            if let Err(e) = handle.await {
+                error_log(
+                    http.clone(),
+                    &format!("A panic happened in reminders: ```{e}```",),
+                )
+                .await
+            }
+        }
+
         tokio::time::sleep(std::time::Duration::from_secs(55)).await;
     }
 }
@@ -145,53 +263,65 @@ async fn get_reminders(db: &Database, minutes: i64) -> MongoResult<Option<Docume
         .await
 }
 
-async fn execute_reminder(
+/// The reminders that are due, grouped by where they get sent to.
+#[derive(Default)]
+struct Targets<'a> {
+    channels: HashMap<ChannelId, (GuildId, Vec<&'a Due>)>,
+    users: HashMap<UserId, Vec<&'a Due>>,
+}
+
+/// Group the reminders that are due by where they get sent to, so every
+/// channel and user gets one message for all of them.
+fn group_by_target(due: &[(Reminder, Due)]) -> Targets<'_> {
+    let mut targets = Targets::default();
+
+    for (reminder, d) in due {
+        for c in &reminder.channels {
+            targets
+                .channels
+                .entry(c.channel)
+                .or_insert_with(|| (c.guild, Vec::new()))
+                .1
+                .push(d);
+        }
+
+        for u in &reminder.users {
+            targets
+                .users
+                .entry(*u)
+                .or_default()
+                .push(d);
+        }
+    }
+
+    targets
+}
+
+async fn execute_reminders(
     db: Database,
     http: Arc<Http>,
-    reminder: Reminder,
-    l: LaunchData,
-    difference: Duration,
+    catalog: Catalog,
+    due: Vec<(Reminder, Due)>,
 ) {
-    let Reminder {
+    let Targets {
         channels,
         users,
-        ..
-    } = reminder;
+    } = group_by_target(&due);
 
     stream::iter(channels.into_iter())
-        .filter_map(|c| {
+        .filter_map(|(channel, (guild, dues))| {
             let db = db.clone();
             async move {
-                get_guild_settings(
-                    &db,
-                    c.guild
-                        .into(),
-                )
-                .await
-                .ok()
-                .map(|s| (c, s))
+                get_guild_settings(&db, guild.into())
+                    .await
+                    .ok()
+                    .map(|s| (channel, s, dues))
             }
         })
-        .filter(|(_, settings)| future::ready(passes_filters(settings, &l)))
-        .map(|(c, settings)| {
-            let mentions = settings
-                .mentions
-                .iter()
-                .fold(String::new(), |acc, mention| {
-                    acc + &format!(" <@&{}>", mention.get())
-                });
-
-            (c, mentions)
-        })
-        .map(|(c, mentions)| {
-            let mut m = CreateMessage::new().embed(reminder_embed(&l, difference));
-
-            if !mentions.is_empty() {
-                m = m.content(mentions);
-            }
-
-            c.channel
-                .send_message(&http, m)
+        .map(|(channel, settings, dues)| {
+            send_channel_reminders(
+                &http, &db, &catalog, channel, settings, dues,
+            )
         })
         .collect::<FuturesUnordered<_>>()
         .await
@@ -199,37 +329,248 @@ async fn execute_reminder(
         .await;
 
     stream::iter(users.into_iter())
-        .filter_map(|u| {
+        .filter_map(|(u, dues)| {
             let db = db.clone();
             async move {
                 get_user_settings(&db, u.get())
                     .await
                     .ok()
-                    .map(|s| (u, s))
-            }
-        })
-        .filter(|(_, settings)| future::ready(passes_filters(settings, &l)))
-        .filter_map(|(u, _)| {
-            let http = http.clone();
-            async move {
-                u.create_dm_channel(&http)
-                    .await
-                    .ok()
+                    .map(|s| (u, s, dues))
             }
         })
-        .map(|c| {
-            c.id.send_message(
-                &http,
-                CreateMessage::new().embed(reminder_embed(&l, difference)),
-            )
-        })
+        .map(|(u, settings, dues)| send_user_reminders(&http, &catalog, u, settings, dues))
         .collect::<FuturesUnordered<_>>()
         .await
         .collect::<Vec<_>>()
         .await;
 }
 
-fn reminder_embed(l: &LaunchData, diff: Duration) -> CreateEmbed {
+/// A reminder on its way to a channel or user.
+struct Outgoing {
+    notice: Notice,
+    roles: Vec<RoleId>,
+    users: Vec<UserId>,
+    button: Option<CreateButton>,
+}
+
+impl Outgoing {
+    fn new(notice: Notice) -> Self {
+        Self {
+            notice,
+            roles: Vec::new(),
+            users: Vec::new(),
+            button: None,
+        }
+    }
+}
+
+/// Messages with the reminders, as few as discord allows. Every message has
+/// the text of its reminders and mentions their roles and users once, as
+/// embeds or as plain text when the target wants that. Mentions that don't
+/// fit go in the messages after it.
+fn reminder_messages(reminders: &[Outgoing], plain_text: bool) -> Vec<CreateMessage> {
+    reminders
+        .chunks(EMBEDS_PER_MESSAGE)
+        .flat_map(|chunk| {
+            let roles: Vec<RoleId> = chunk
+                .iter()
+                .flat_map(|r| &r.roles)
+                .unique()
+                .copied()
+                .collect();
+            let users = chunk
+                .iter()
+                .flat_map(|r| &r.users)
+                .unique()
+                .fold(String::new(), |acc, user| {
+                    acc + &format!(" <@{}>", user.get())
+                });
+            let mentions = format_mentions(&roles) + &users;
+            let buttons: Vec<CreateButton> = chunk
+                .iter()
+                .filter_map(|r| {
+                    r.button
+                        .clone()
+                })
+                .collect();
+
+            let mut messages: Vec<CreateMessage> = if plain_text {
+                plain_messages(
+                    chunk
+                        .iter()
+                        .map(|r| {
+                            r.notice
+                                .text()
+                        }),
+                    &mentions,
+                )
+                .into_iter()
+                .map(|text| CreateMessage::new().content(text))
+                .collect()
+            } else {
+                embed_messages(
+                    chunk
+                        .iter()
+                        .map(|r| {
+                            r.notice
+                                .embed()
+                        })
+                        .collect(),
+                    &chunk
+                        .iter()
+                        .filter_map(|r| {
+                            r.notice
+                                .content
+                                .as_deref()
+                        })
+                        .join("\n"),
+                    &mentions,
+                )
+            };
+
+            // the buttons go below the last of the reminders they are for
+            if !buttons.is_empty() {
+                if let Some(last) = messages.pop() {
+                    messages.push(
+                        last.components(
+                            buttons
+                                .chunks(5)
+                                .map(|row| CreateActionRow::Buttons(row.to_vec()))
+                                .collect(),
+                        ),
+                    );
+                }
+            }
+
+            messages
+        })
+        .collect()
+}
+
+/// The roles a guild wants mentioned on a reminder, the roles for the agency
+/// or vehicle of a launch replace the other ones when the guild wants that.
+fn reminder_mentions(settings: &GuildSettings, catalog: &Catalog, d: &Due) -> Vec<RoleId> {
+    let mut roles = guild_mentions(
+        settings,
+        MentionKind::Reminder,
+        Some(
+            d.difference
+                .num_minutes(),
+        ),
+    );
+    let Subject::Launch(l) = &d.subject else {
+        return roles;
+    };
+
+    let launch_roles = launch_mentions(settings, catalog, l);
+    if settings.launch_mentions_only && !launch_roles.is_empty() {
+        return launch_roles;
+    }
+
+    for role in launch_roles {
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+    }
+
+    roles
+}
+
+async fn send_channel_reminders(
+    http: &Http,
+    db: &Database,
+    catalog: &Catalog,
+    channel: ChannelId,
+    settings: GuildSettings,
+    dues: Vec<&Due>,
+) {
+    let plain_text = settings.plain_text_in(channel);
+    let mut batched = Vec::new();
+    for d in dues
+        .into_iter()
+        .filter(|d| {
+            d.subject
+                .passes(&settings, catalog)
+        })
+    {
+        let mut reminder = Outgoing::new(d.styled(&settings));
+        reminder.roles = reminder_mentions(&settings, catalog, d);
+        if let Subject::Launch(l) = &d.subject {
+            reminder.users = launch_pings(db, channel, &l.ll_id).await;
+            reminder.button = Some(ping_button(channel, l));
+        }
+
+        match &d.subject {
+            // every launch has a thread of its own, so these can't be combined
+            Subject::Launch(l) if settings.launch_threads => {
+                for m in reminder_messages(&[reminder], plain_text) {
+                    if let Err(e) = send_in_launch_thread(http, db, channel, l, m).await {
+                        eprintln!("Can't send reminder: {e}");
+                    }
+                }
+            },
+            _ => batched.push(reminder),
+        }
+    }
+
+    for m in reminder_messages(&batched, plain_text) {
+        if let Err(e) = channel
+            .send_message(http, m)
+            .await
+        {
+            eprintln!("Can't send reminder: {e}");
+        }
+    }
+}
+
+async fn send_user_reminders(
+    http: &Http,
+    catalog: &Catalog,
+    user: UserId,
+    settings: UserSettings,
+    dues: Vec<&Due>,
+) {
+    let reminders: Vec<Outgoing> = dues
+        .into_iter()
+        .filter(|d| {
+            d.subject
+                .passes(&settings, catalog)
+        })
+        .map(|d| Outgoing::new(d.notice()))
+        .collect();
+    if reminders.is_empty() {
+        return;
+    }
+
+    let Ok(dm) = user
+        .create_dm_channel(http)
+        .await
+    else {
+        return;
+    };
+
+    for m in reminder_messages(&reminders, settings.plain_text) {
+        if let Err(e) = dm
+            .id
+            .send_message(http, m)
+            .await
+        {
+            eprintln!("Can't send reminder: {e}");
+        }
+    }
+}
+
+/// A reminder for a launch the way a guild wants it to look.
+pub(super) fn styled_reminder(settings: &GuildSettings, l: &LaunchData, diff: Duration) -> Notice {
+    style_message(
+        settings,
+        MentionKind::Reminder,
+        l,
+        reminder_notice(l, diff),
+    )
+}
+
+fn reminder_notice(l: &LaunchData, diff: Duration) -> Notice {
     let live = if let Some(link) = l
         .vid_urls.iter().find_or_first(|v| v.url.contains("youtube.com"))
     {
@@ -238,39 +579,82 @@ fn reminder_embed(l: &LaunchData, diff: Duration) -> CreateEmbed {
         String::new()
     };
 
-    let mut e = CreateEmbed::new()
-        .color(DEFAULT_COLOR)
-        .author(
-            CreateEmbedAuthor::new(format!(
-                "{} till launch",
-                &format_duration(diff, false)
-            ))
-            .icon_url(DEFAULT_ICON),
-        )
-        .description(format!(
+    let notice = Notice::new(
+        format!(
+            "{} till launch",
+            &format_duration(diff, false)
+        ),
+        format!(
             "**Payload:** {}\n\
             **Vehicle:** {}\n\
             **NET:** <t:{}>\n\
-            {}",
+            {}{}",
             &l.payload,
             &l.vehicle,
             l.net
                 .timestamp(),
+            launch_conditions(l).unwrap_or_default(),
             live
-        ))
-        .timestamp(
-            Timestamp::from_unix_timestamp(
-                l.net
-                    .timestamp(),
-            )
-            .expect("Invalid timestamp"),
-        );
+        ),
+    );
+
+    Notice {
+        thumbnail: l
+            .rocket_img
+            .clone(),
+        timestamp: Timestamp::from_unix_timestamp(
+            l.net
+                .timestamp(),
+        )
+        .expect("Invalid timestamp"),
+        ..notice
+    }
+}
+
+fn event_reminder_notice(e: &EventData, diff: Duration) -> Notice {
+    let mut details = format!(
+        "**Event:** {}\n\
+        **Type:** {}\n\
+        **Date:** <t:{}>\n",
+        &e.name,
+        &e.event_type,
+        e.date
+            .timestamp(),
+    );
+
+    if let Some(location) = &e.location {
+        writeln!(details, "**Location:** {location}").expect("write to String: can't fail");
+    }
 
-    if let Some(img) = &l.rocket_img {
-        e = e.thumbnail(img);
+    if let Some(video) = &e.video_url {
+        write!(
+            details,
+            "**Live at:** {}",
+            format_url(video)
+        )
+        .expect("write to String: can't fail");
     }
 
-    e
+    let notice = Notice::new(
+        format!(
+            "{} till {}",
+            &format_duration(diff, false),
+            &e.event_type
+        ),
+        details,
+    );
+
+    Notice {
+        thumbnail: e
+            .image
+            .clone(),
+        timestamp: Timestamp::from_unix_timestamp(
+            e.date
+                .timestamp(),
+        )
+        .expect("Invalid timestamp"),
+        ..notice
+    }
 }
 
 fn format_url(rawlink: &str) -> String {
@@ -282,3 +666,140 @@ fn format_url(rawlink: &str) -> String {
     };
     rawlink.to_owned()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::reminders::ChannelReminder;
+
+    fn event(ll_id: i32) -> Due {
+        Due {
+            subject: Subject::Event(EventData {
+                ll_id,
+                name: format!("Event {ll_id}"),
+                event_type: "Spacewalk".to_owned(),
+                description: String::new(),
+                location: None,
+                news_url: None,
+                video_url: None,
+                image: None,
+                date: NaiveDateTime::default(),
+            }),
+            difference: Duration::minutes(15),
+        }
+    }
+
+    fn reminder(channels: &[u64], users: &[u64]) -> Reminder {
+        Reminder {
+            minutes: 15,
+            channels: channels
+                .iter()
+                .map(|c| {
+                    ChannelReminder {
+                        guild: GuildId::new(1),
+                        channel: ChannelId::new(*c),
+                    }
+                })
+                .collect(),
+            users: users
+                .iter()
+                .map(|u| UserId::new(*u))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn batches_reminders_per_target() {
+        let due = vec![
+            (reminder(&[10, 11], &[20]), event(1)),
+            (reminder(&[10], &[20, 21]), event(2)),
+        ];
+
+        let Targets {
+            channels,
+            users,
+        } = group_by_target(&due);
+
+        assert_eq!(channels.len(), 2);
+        assert_eq!(
+            channels[&ChannelId::new(10)]
+                .1
+                .len(),
+            2
+        );
+        assert_eq!(
+            channels[&ChannelId::new(11)]
+                .1
+                .len(),
+            1
+        );
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[&UserId::new(20)].len(), 2);
+        assert_eq!(users[&UserId::new(21)].len(), 1);
+    }
+
+    #[test]
+    fn splits_messages_at_the_embed_limit() {
+        let reminders = (0..=EMBEDS_PER_MESSAGE)
+            .map(|_| {
+                Outgoing::new(Notice::new(
+                    "15 minutes till launch",
+                    "",
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            reminder_messages(&reminders, false).len(),
+            2
+        );
+        assert_eq!(
+            reminder_messages(&reminders, true).len(),
+            2
+        );
+        assert!(reminder_messages(&[], false).is_empty());
+    }
+
+    #[test]
+    fn hundreds_of_pings_fit_in_messages() {
+        let mut reminder = Outgoing::new(Notice::new(
+            "15 minutes till launch",
+            "**Payload:** Starlink Group 6-2",
+        ));
+        reminder.users = (0..400)
+            .map(|i| UserId::new(247745860979392512 + i))
+            .collect();
+        reminder.roles = vec![RoleId::new(429307670730637312)];
+
+        let reminders = [reminder];
+
+        for plain_text in [false, true] {
+            let contents: Vec<String> = reminder_messages(&reminders, plain_text)
+                .iter()
+                .map(|m| {
+                    serde_json::to_value(m).unwrap()["content"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_owned()
+                })
+                .collect();
+
+            assert!(contents.len() > 2);
+            assert!(contents
+                .iter()
+                .all(|c| {
+                    !c.is_empty()
+                        && c.chars()
+                            .count()
+                            <= 2000
+                }));
+            assert_eq!(
+                contents
+                    .concat()
+                    .matches("<@2477458609")
+                    .count(),
+                400
+            );
+        }
+    }
+}
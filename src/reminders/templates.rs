@@ -0,0 +1,340 @@
+use chrono::Duration;
+use itertools::Itertools;
+use regex::Captures;
+
+use super::{
+    change_notifications::{
+        outcome_notice,
+        scrub_notice,
+    },
+    notice::Notice,
+    plain_text::{
+        truncate,
+        MESSAGE_LENGTH,
+    },
+    reminder_tracking::styled_reminder,
+};
+use crate::{
+    models::{
+        launches::{
+            LaunchData,
+            LaunchStatus,
+        },
+        reminders::{
+            GuildSettings,
+            MentionKind,
+        },
+    },
+    utils::constants::TEMPLATE_PLACEHOLDER_REGEX,
+};
+
+/// The most characters discord allows in the title of an embed.
+const TITLE_LENGTH: usize = 256;
+
+/// The most characters discord allows in the description of an embed.
+const DESCRIPTION_LENGTH: usize = 4096;
+
+/// The placeholders that can be used in templates.
+pub const PLACEHOLDERS: [&str; 9] = [
+    "name",
+    "payload",
+    "vehicle",
+    "lsp",
+    "net",
+    "countdown",
+    "stream",
+    "pad",
+    "status",
+];
+
+/// Check that a template only uses placeholders that exist.
+pub fn check_template(input: &str) -> Result<(), String> {
+    let unknown = TEMPLATE_PLACEHOLDER_REGEX
+        .captures_iter(input)
+        .filter_map(|caps| caps.get(1))
+        .map(|name| name.as_str())
+        .filter(|name| !PLACEHOLDERS.contains(name))
+        .unique()
+        .map(|name| format!("`{{{name}}}`"))
+        .join(", ");
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "{unknown} can't be used, the placeholders are {}",
+        PLACEHOLDERS
+            .iter()
+            .map(|name| format!("`{{{name}}}`"))
+            .join(", ")
+    ))
+}
+
+fn placeholder(name: &str, l: &LaunchData) -> Option<String> {
+    let value = match name {
+        "name" => {
+            l.launch_name
+                .clone()
+        },
+        "payload" => {
+            l.payload
+                .clone()
+        },
+        "vehicle" => {
+            l.vehicle
+                .clone()
+        },
+        "lsp" => {
+            l.lsp
+                .clone()
+        },
+        "net" => {
+            format!(
+                "<t:{}:F>",
+                l.net
+                    .timestamp()
+            )
+        },
+        "countdown" => {
+            format!(
+                "<t:{}:R>",
+                l.net
+                    .timestamp()
+            )
+        },
+        "stream" => {
+            l.vid_urls
+                .iter()
+                .find_or_first(|v| {
+                    v.url
+                        .contains("youtube.com")
+                })
+                .map_or_else(
+                    || "no stream yet".to_owned(),
+                    |v| {
+                        v.url
+                            .clone()
+                    },
+                )
+        },
+        "pad" => {
+            l.pad
+                .as_ref()
+                .map_or_else(
+                    || {
+                        l.location
+                            .clone()
+                    },
+                    |pad| format!("{}, {}", pad.name, pad.location),
+                )
+        },
+        "status" => {
+            l.status
+                .as_str()
+                .to_owned()
+        },
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Fill in the placeholders of a template for a launch, ones that don't exist
+/// are left like they are.
+pub fn fill_template(template: &str, l: &LaunchData) -> String {
+    TEMPLATE_PLACEHOLDER_REGEX
+        .replace_all(template, |caps: &Captures| {
+            placeholder(&caps[1], l).unwrap_or_else(|| caps[0].to_owned())
+        })
+        .into_owned()
+}
+
+/// Put the color, the rocket image and the wording of the template a guild
+/// set for a kind of message over the standard message. The filled in parts
+/// are cut off where discord wouldn't take them anymore.
+pub(super) fn style_message(
+    settings: &GuildSettings,
+    kind: MentionKind,
+    l: &LaunchData,
+    mut notice: Notice,
+) -> Notice {
+    if let Some(color) = settings.embed_color {
+        notice.color = color.into();
+    }
+
+    if settings.hide_thumbnail {
+        notice.thumbnail = None;
+    }
+
+    let Some(template) = settings
+        .templates
+        .iter()
+        .find(|t| t.kind == kind)
+    else {
+        return notice;
+    };
+
+    if let Some(title) = &template.title {
+        notice.title = Some(truncate(
+            &fill_template(title, l),
+            TITLE_LENGTH,
+        ));
+    }
+
+    if let Some(description) = &template.description {
+        notice.description = truncate(
+            &fill_template(description, l),
+            DESCRIPTION_LENGTH,
+        );
+    }
+
+    notice.content = template
+        .content
+        .as_deref()
+        .map(|content| {
+            truncate(
+                &fill_template(content, l),
+                MESSAGE_LENGTH,
+            )
+        });
+
+    notice
+}
+
+/// How the reminders, scrubs and outcomes of a guild would look for a launch.
+pub fn preview_templates(settings: &GuildSettings, l: &LaunchData) -> Vec<Notice> {
+    let mut delayed = l.clone();
+    delayed.net += Duration::days(1);
+
+    let mut finished = l.clone();
+    finished.status = LaunchStatus::Success;
+
+    vec![
+        styled_reminder(settings, l, Duration::hours(1)),
+        style_message(
+            settings,
+            MentionKind::Scrub,
+            &delayed,
+            scrub_notice(l, &delayed),
+        ),
+        style_message(
+            settings,
+            MentionKind::Outcome,
+            &finished,
+            outcome_notice(&finished),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+    use crate::models::reminders::MessageTemplate;
+
+    fn launch() -> LaunchData {
+        LaunchData {
+            launch_name: "Falcon 9 Block 5 | Starlink Group 6-2".into(),
+            payload: "Starlink Group 6-2".into(),
+            vehicle: "Falcon 9 Block 5".into(),
+            vehicle_family: "Falcon".into(),
+            location: "Space Launch Complex 40".into(),
+            net: NaiveDateTime::from_timestamp_opt(1635409251, 0).unwrap(),
+            lsp: "SpaceX".into(),
+            ..LaunchData::default()
+        }
+    }
+
+    #[test]
+    fn only_known_placeholders() {
+        assert!(check_template("{payload} on a {vehicle} at {net}").is_ok());
+        assert!(check_template("no placeholders at all").is_ok());
+
+        let err = check_template("{payload} by {agency} {agency} {}").unwrap_err();
+        assert!(err.starts_with("`{agency}`, `{}` can't be used"));
+    }
+
+    #[test]
+    fn fills_in_placeholders() {
+        assert_eq!(
+            fill_template(
+                "{payload} by {lsp} from {pad}, {stream} {agency}",
+                &launch()
+            ),
+            "Starlink Group 6-2 by SpaceX from Space Launch Complex 40, no stream yet {agency}"
+        );
+        assert_eq!(
+            fill_template("T- {countdown}", &launch()),
+            "T- <t:1635409251:R>"
+        );
+    }
+
+    #[test]
+    fn filled_in_parts_fit_in_an_embed() {
+        let settings = GuildSettings {
+            templates: vec![MessageTemplate {
+                kind: MentionKind::Reminder,
+                content: Some("{name} ".repeat(60)),
+                title: Some("{name} ".repeat(10)),
+                description: Some("{payload} ".repeat(300)),
+            }],
+            ..GuildSettings::default()
+        };
+
+        let notice = styled_reminder(&settings, &launch(), Duration::hours(1));
+        let length = |text: Option<&String>| {
+            text.map(|t| {
+                t.chars()
+                    .count()
+            })
+        };
+        assert_eq!(
+            length(
+                notice
+                    .content
+                    .as_ref()
+            ),
+            Some(MESSAGE_LENGTH)
+        );
+        assert_eq!(
+            length(
+                notice
+                    .title
+                    .as_ref()
+            ),
+            Some(TITLE_LENGTH)
+        );
+        assert_eq!(
+            length(Some(&notice.description)),
+            Some(DESCRIPTION_LENGTH)
+        );
+    }
+
+    #[test]
+    fn notifications_get_the_guild_look() {
+        let launch = LaunchData {
+            rocket_img: Some("https://example.com/rocket.png".into()),
+            ..launch()
+        };
+        let mut settings = GuildSettings {
+            embed_color: Some(0x00ff00),
+            ..GuildSettings::default()
+        };
+
+        for notice in preview_templates(&settings, &launch) {
+            assert_eq!(notice.color, 0x00ff00.into());
+            assert!(notice
+                .thumbnail
+                .is_some());
+        }
+
+        settings.hide_thumbnail = true;
+        for notice in preview_templates(&settings, &launch) {
+            assert!(notice
+                .thumbnail
+                .is_none());
+        }
+    }
+}
@@ -0,0 +1,501 @@
+use std::sync::Arc;
+
+use futures::stream::TryStreamExt;
+use itertools::Itertools;
+use mongodb::{
+    bson::doc,
+    options::ReplaceOptions,
+    Database,
+};
+use serenity::prelude::{
+    Context,
+    RwLock,
+};
+
+use crate::{
+    models::{
+        caches::CatalogKey,
+        catalog::{
+            Agency,
+            Vehicle,
+        },
+        launches::LaunchData,
+    },
+    utils::constants::{
+        AGENCY_ALIASES,
+        VEHICLE_ALIASES,
+    },
+};
+
+/// The agencies and vehicles that have been seen in launch library data, which
+/// together with the curated aliases are what launches can be filtered on.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    agencies: Vec<Agency>,
+    vehicles: Vec<Vehicle>,
+}
+
+/// An agency filter looked up in the [`Catalog`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgencyFilter {
+    /// Unknown for aliases of agencies that haven't been seen yet.
+    pub ll_id: Option<i32>,
+    pub name: String,
+}
+
+impl AgencyFilter {
+    /// Launches are matched on the launch library id of their provider, the
+    /// name is only used when either side doesn't know the id, like for
+    /// launches stored from before the id was kept.
+    pub fn matches(&self, launch: &LaunchData) -> bool {
+        match (self.ll_id, launch.lsp_id) {
+            (Some(filter_id), Some(id)) => filter_id == id,
+            _ => launch.lsp == self.name,
+        }
+    }
+}
+
+/// A vehicle filter looked up in the [`Catalog`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VehicleFilter {
+    pub ll_ids: Vec<i32>,
+    /// Also has the names of configurations of an alias that haven't been
+    /// seen yet.
+    pub names: Vec<String>,
+}
+
+impl VehicleFilter {
+    /// Launches are matched on the launch library id of their rocket
+    /// configuration, or on the name for configurations without a known id.
+    pub fn matches(&self, launch: &LaunchData) -> bool {
+        launch
+            .vehicle_id
+            .is_some_and(|id| {
+                self.ll_ids
+                    .contains(&id)
+            })
+            || self
+                .names
+                .contains(&launch.vehicle)
+    }
+}
+
+/// Turn a name into the form used for filters, like "Stoke Space" into
+/// "stoke-space".
+pub fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .join("-")
+}
+
+impl Catalog {
+    /// Look up an agency filter, curated aliases come before the names made
+    /// from launch library data.
+    pub fn agency(&self, filter: &str) -> Option<AgencyFilter> {
+        if let Some(name) = AGENCY_ALIASES.get(filter) {
+            return Some(AgencyFilter {
+                ll_id: self
+                    .agencies
+                    .iter()
+                    .find(|a| a.name == *name)
+                    .map(|a| a.ll_id),
+                name: (*name).to_owned(),
+            });
+        }
+
+        self.agencies
+            .iter()
+            .find(|a| a.key == filter)
+            .map(|a| {
+                AgencyFilter {
+                    ll_id: Some(a.ll_id),
+                    name: a
+                        .name
+                        .clone(),
+                }
+            })
+    }
+
+    /// The name of the agency a filter is for.
+    pub fn agency_name(&self, filter: &str) -> Option<String> {
+        self.agency(filter)
+            .map(|a| a.name)
+    }
+
+    /// Look up a vehicle filter, curated aliases come before the families
+    /// made from launch library data.
+    pub fn vehicle(&self, filter: &str) -> Option<VehicleFilter> {
+        if let Some(names) = VEHICLE_ALIASES.get(filter) {
+            return Some(VehicleFilter {
+                ll_ids: self
+                    .vehicles
+                    .iter()
+                    .filter(|v| {
+                        names.contains(
+                            &v.name
+                                .as_str(),
+                        )
+                    })
+                    .map(|v| v.ll_id)
+                    .collect(),
+                names: names
+                    .iter()
+                    .map(|n| (*n).to_owned())
+                    .collect(),
+            });
+        }
+
+        let family = self
+            .vehicles
+            .iter()
+            .filter(|v| v.key == filter)
+            .collect::<Vec<&Vehicle>>();
+        if family.is_empty() {
+            return None;
+        }
+
+        Some(VehicleFilter {
+            ll_ids: family
+                .iter()
+                .map(|v| v.ll_id)
+                .collect(),
+            names: family
+                .iter()
+                .map(|v| {
+                    v.name
+                        .clone()
+                })
+                .collect(),
+        })
+    }
+
+    /// Every agency filter together with the name of the agency, sorted on
+    /// the filter.
+    pub fn agency_filters(&self) -> Vec<(String, String)> {
+        AGENCY_ALIASES
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .chain(
+                self.agencies
+                    .iter()
+                    .filter(|a| {
+                        !AGENCY_ALIASES.contains_key(
+                            a.key
+                                .as_str(),
+                        ) && !AGENCY_ALIASES
+                            .values()
+                            .any(|name| *name == a.name)
+                    })
+                    .map(|a| {
+                        (
+                            a.key
+                                .clone(),
+                            a.name
+                                .clone(),
+                        )
+                    }),
+            )
+            .sorted()
+            .collect()
+    }
+
+    /// Every vehicle filter, sorted.
+    pub fn vehicle_filters(&self) -> Vec<String> {
+        VEHICLE_ALIASES
+            .keys()
+            .map(|k| (*k).to_owned())
+            .chain(
+                self.vehicles
+                    .iter()
+                    .map(|v| {
+                        v.key
+                            .clone()
+                    }),
+            )
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// Add the agencies and vehicles of the launches that aren't known yet or
+    /// have been renamed, giving back the ones that changed.
+    fn learn(&mut self, launches: &[LaunchData]) -> (Vec<Agency>, Vec<Vehicle>) {
+        let mut agencies = Vec::new();
+        let mut vehicles = Vec::new();
+
+        for launch in launches {
+            if let Some(ll_id) = launch.lsp_id {
+                let agency = Agency {
+                    ll_id,
+                    name: launch
+                        .lsp
+                        .clone(),
+                    key: slug(&launch.lsp),
+                };
+                if !self
+                    .agencies
+                    .contains(&agency)
+                {
+                    self.agencies
+                        .retain(|a| a.ll_id != ll_id);
+                    self.agencies
+                        .push(agency.clone());
+                    agencies.push(agency);
+                }
+            }
+
+            if let Some(ll_id) = launch.vehicle_id {
+                // some configurations don't have a family, they are their own
+                let family = if launch
+                    .vehicle_family
+                    .is_empty()
+                {
+                    &launch.vehicle
+                } else {
+                    &launch.vehicle_family
+                };
+                let vehicle = Vehicle {
+                    ll_id,
+                    name: launch
+                        .vehicle
+                        .clone(),
+                    family: family.clone(),
+                    key: slug(family),
+                };
+                if !self
+                    .vehicles
+                    .contains(&vehicle)
+                {
+                    self.vehicles
+                        .retain(|v| v.ll_id != ll_id);
+                    self.vehicles
+                        .push(vehicle.clone());
+                    vehicles.push(vehicle);
+                }
+            }
+        }
+
+        (agencies, vehicles)
+    }
+}
+
+/// The catalog that is shared between the commands and the reminders.
+pub async fn shared_catalog(ctx: &Context) -> Result<Arc<RwLock<Catalog>>, &'static str> {
+    ctx.data
+        .read()
+        .await
+        .get::<CatalogKey>()
+        .cloned()
+        .ok_or("Can't get the catalog")
+}
+
+/// A copy of the catalog as it is known right now, which doesn't keep it
+/// locked while the filters are looked up.
+pub async fn get_catalog(ctx: &Context) -> Result<Catalog, &'static str> {
+    Ok(shared_catalog(ctx)
+        .await?
+        .read()
+        .await
+        .clone())
+}
+
+/// Fill the catalog with the agencies and vehicles stored in the database.
+pub async fn load_catalog(db: &Database, catalog: &RwLock<Catalog>) {
+    let agencies: Vec<Agency> = match db
+        .collection::<Agency>("agencies")
+        .find(None, None)
+        .await
+    {
+        Ok(cursor) => {
+            cursor
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("Can't read agencies: {e}");
+                    Vec::new()
+                })
+        },
+        Err(e) => {
+            eprintln!("Can't read agencies: {e}");
+            Vec::new()
+        },
+    };
+
+    let vehicles: Vec<Vehicle> = match db
+        .collection::<Vehicle>("vehicles")
+        .find(None, None)
+        .await
+    {
+        Ok(cursor) => {
+            cursor
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("Can't read vehicles: {e}");
+                    Vec::new()
+                })
+        },
+        Err(e) => {
+            eprintln!("Can't read vehicles: {e}");
+            Vec::new()
+        },
+    };
+
+    *catalog
+        .write()
+        .await = Catalog {
+        agencies,
+        vehicles,
+    };
+}
+
+/// Learn the agencies and vehicles of freshly fetched launches and store the
+/// ones that changed.
+pub async fn update_catalog(db: &Database, catalog: &RwLock<Catalog>, launches: &[LaunchData]) {
+    let (agencies, vehicles) = catalog
+        .write()
+        .await
+        .learn(launches);
+
+    let collection = db.collection::<Agency>("agencies");
+    for agency in agencies {
+        if let Err(e) = collection
+            .replace_one(
+                doc! {"ll_id": agency.ll_id},
+                &agency,
+                ReplaceOptions::builder()
+                    .upsert(true)
+                    .build(),
+            )
+            .await
+        {
+            eprintln!("Can't store agency: {e}");
+        }
+    }
+
+    let collection = db.collection::<Vehicle>("vehicles");
+    for vehicle in vehicles {
+        if let Err(e) = collection
+            .replace_one(
+                doc! {"ll_id": vehicle.ll_id},
+                &vehicle,
+                ReplaceOptions::builder()
+                    .upsert(true)
+                    .build(),
+            )
+            .await
+        {
+            eprintln!("Can't store vehicle: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(lsp: (i32, &str), vehicle: (i32, &str, &str)) -> LaunchData {
+        LaunchData {
+            vehicle: vehicle
+                .1
+                .to_owned(),
+            vehicle_family: vehicle
+                .2
+                .to_owned(),
+            vehicle_id: Some(vehicle.0),
+            lsp: lsp
+                .1
+                .to_owned(),
+            lsp_id: Some(lsp.0),
+            ..LaunchData::default()
+        }
+    }
+
+    #[test]
+    fn learns_new_agencies_and_vehicles() {
+        let launches = [
+            launch(
+                (121, "SpaceX"),
+                (164, "Falcon 9 Block 5", "Falcon"),
+            ),
+            launch(
+                (9001, "Stoke Space"),
+                (9002, "Nova", ""),
+            ),
+        ];
+
+        let mut catalog = Catalog::default();
+        let (agencies, vehicles) = catalog.learn(&launches);
+        assert_eq!(agencies.len(), 2);
+        assert_eq!(vehicles.len(), 2);
+
+        // nothing changed, so nothing has to be stored again
+        let (agencies, vehicles) = catalog.learn(&launches);
+        assert!(agencies.is_empty() && vehicles.is_empty());
+
+        assert_eq!(
+            catalog.agency("stoke-space"),
+            Some(AgencyFilter {
+                ll_id: Some(9001),
+                name: "Stoke Space".to_owned(),
+            })
+        );
+        // the curated alias gets the id of the agency with that name
+        assert_eq!(
+            catalog
+                .agency("spacex")
+                .and_then(|a| a.ll_id),
+            Some(121)
+        );
+        assert!(catalog
+            .agency("nova")
+            .is_none());
+
+        assert!(catalog
+            .vehicle("nova")
+            .is_some_and(|v| v.matches(&launches[1])));
+        assert!(catalog
+            .vehicle("falcon")
+            .is_some_and(|v| v.ll_ids == [164]));
+        assert!(catalog
+            .agency_filters()
+            .contains(&(
+                "stoke-space".to_owned(),
+                "Stoke Space".to_owned()
+            )));
+    }
+
+    #[test]
+    fn matches_on_ids() {
+        let mut catalog = Catalog::default();
+        catalog.learn(&[launch(
+            (121, "SpaceX"),
+            (164, "Falcon 9 Block 5", "Falcon"),
+        )]);
+        let agency = catalog
+            .agency("spacex")
+            .unwrap();
+
+        // a renamed agency is still the same agency
+        let renamed = launch(
+            (
+                121,
+                "Space Exploration Technologies Corp.",
+            ),
+            (164, "Falcon 9 Block 5", "Falcon"),
+        );
+        assert!(agency.matches(&renamed));
+
+        let other = launch(
+            (147, "SpaceX"),
+            (164, "Falcon 9 Block 5", "Falcon"),
+        );
+        assert!(!agency.matches(&other));
+
+        // launches stored before the ids were kept go by name
+        let mut stored = other;
+        stored.lsp_id = None;
+        assert!(agency.matches(&stored));
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     env,
+    time::Duration,
 };
 
 use lazy_static::lazy_static;
@@ -41,6 +42,18 @@ pub const BACK_EMOJI: char = '◀';
 pub const CERTAIN_EMOJI: u64 = 447805610482728964;
 pub const UNCERTAIN_EMOJI: u64 = 447805624923717642;
 pub const LAUNCH_LIBRARY_URL: &str = "https://thespacedevs.com";
+pub const EMBED_SESSION_TIMEOUT: Duration = Duration::from_mins(10);
+// discord interaction tokens are valid for 15 minutes, a session expires this
+// long after it got its interaction at the latest, so the sweep can still use
+// the token to disable its buttons
+pub const INTERACTION_TOKEN_LIFETIME: Duration = Duration::from_mins(13);
+pub const SELECT_MENU_TIMEOUT: Duration = Duration::from_mins(5);
+pub const MODAL_TIMEOUT: Duration = Duration::from_mins(10);
+pub const SESSION_SWEEP_INTERVAL: Duration = Duration::from_mins(1);
+// bounds for reminders that get typed in, longer ones would go off before most
+// launches are scheduled precisely enough
+pub const MIN_REMINDER_MINUTES: i64 = 1;
+pub const MAX_REMINDER_MINUTES: i64 = 14 * 24 * 60;
 
 fn default_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
@@ -84,6 +97,7 @@ lazy_static! {
     pub static ref WORD_REGEX: Regex = Regex::new(r"^[a-zA-Z\-_0-9]+$").unwrap();
     pub static ref WORD_FILTER_REGEX: Regex =
         Regex::new(r"^\(\?i\)\\b[a-zA-Z\-_0-9]+\\b$").unwrap();
+    pub static ref TEMPLATE_PLACEHOLDER_REGEX: Regex = Regex::new(r"\{([^{}\s]*)\}").unwrap();
     pub static ref NUMBER_EMOJIS: Vec<ReactionType> = [
         "1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣", "9️⃣", "🔟"
     ]
@@ -93,7 +107,8 @@ lazy_static! {
     pub static ref OWNERS: Vec<UserId> = vec![247745860979392512.into()];
 }
 
-fn agency_map() -> HashMap<&'static str, &'static str> {
+/// Curated filter names for agencies, pointing to their name in launch library.
+fn agency_aliases() -> HashMap<&'static str, &'static str> {
     let mut res = HashMap::with_capacity(25);
 
     res.insert("blueorigin", "Blue Origin");
@@ -165,7 +180,9 @@ fn agency_map() -> HashMap<&'static str, &'static str> {
     res
 }
 
-fn vehicle_map() -> HashMap<&'static str, Vec<&'static str>> {
+/// Curated filter names for groups of vehicles, pointing to the names of the
+/// rocket configurations in launch library.
+fn vehicle_aliases() -> HashMap<&'static str, Vec<&'static str>> {
     let mut res = HashMap::with_capacity(25);
 
     res.insert("electron", vec!["Electron"]);
@@ -329,7 +346,7 @@ fn vehicle_map() -> HashMap<&'static str, Vec<&'static str>> {
 }
 
 lazy_static! {
-    pub static ref LAUNCH_AGENCIES: HashMap<&'static str, &'static str> = agency_map();
-    pub static ref LAUNCH_VEHICLES: HashMap<&'static str, Vec<&'static str>> = vehicle_map();
+    pub static ref AGENCY_ALIASES: HashMap<&'static str, &'static str> = agency_aliases();
+    pub static ref VEHICLE_ALIASES: HashMap<&'static str, Vec<&'static str>> = vehicle_aliases();
     pub static ref RNG: Mutex<StdRng> = Mutex::new(StdRng::from_rng(thread_rng()).unwrap());
 }
@@ -0,0 +1,413 @@
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    Utc,
+};
+use futures::future::BoxFuture;
+use reqwest::header::AUTHORIZATION;
+
+use crate::{
+    models::crew::{
+        AgencyRef,
+        Astronaut,
+        AstronautContainer,
+        AstronautInfo,
+        DockingEventContainer,
+        DockingEventInfo,
+        Station,
+        StationContainer,
+        StationInfo,
+    },
+    utils::constants::{
+        DEFAULT_CLIENT,
+        LAUNCH_LIBRARY_URL,
+        LL_KEY,
+    },
+};
+
+/// Where the information about the people in space and the space stations
+/// comes from, so a source can be swapped out when it goes stale.
+pub trait CrewSource: Send + Sync {
+    /// Link to the source, to credit it with.
+    fn url(&self) -> &'static str;
+
+    fn people_in_space(&self) -> BoxFuture<'_, reqwest::Result<Vec<Astronaut>>>;
+
+    fn station<'a>(&'a self, name: &'a str) -> BoxFuture<'a, reqwest::Result<Option<Station>>>;
+}
+
+/// The source that is used by the commands.
+pub static CREW_SOURCE: &dyn CrewSource = &LaunchLibraryCrew;
+
+/// Astronaut and space station data from launch library.
+pub struct LaunchLibraryCrew;
+
+impl CrewSource for LaunchLibraryCrew {
+    fn url(&self) -> &'static str {
+        LAUNCH_LIBRARY_URL
+    }
+
+    fn people_in_space(&self) -> BoxFuture<'_, reqwest::Result<Vec<Astronaut>>> {
+        Box::pin(async move {
+            let astronauts: AstronautContainer = DEFAULT_CLIENT
+                .get("https://ll.thespacedevs.com/2.2.0/astronaut/")
+                .header(AUTHORIZATION, LL_KEY.as_str())
+                .query(&[("in_space", "true"), ("limit", "100")])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            let stations = active_stations().await?;
+
+            Ok(people_from(
+                &astronauts.results,
+                &stations,
+            ))
+        })
+    }
+
+    fn station<'a>(&'a self, name: &'a str) -> BoxFuture<'a, reqwest::Result<Option<Station>>> {
+        Box::pin(async move {
+            let container: StationContainer = DEFAULT_CLIENT
+                .get("https://ll.thespacedevs.com/2.2.0/spacestation/")
+                .header(AUTHORIZATION, LL_KEY.as_str())
+                .query(&[
+                    ("search", name),
+                    ("mode", "detailed"),
+                    ("limit", "1"),
+                ])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            let Some(info) = container
+                .results
+                .into_iter()
+                .next()
+            else {
+                return Ok(None);
+            };
+
+            let visits: DockingEventContainer = DEFAULT_CLIENT
+                .get("https://ll.thespacedevs.com/2.2.0/docking_event/")
+                .header(AUTHORIZATION, LL_KEY.as_str())
+                .query(&[
+                    (
+                        "space_station__ids",
+                        info.id
+                            .to_string(),
+                    ),
+                    (
+                        "docking__gte",
+                        Utc::now()
+                            .format("%Y-%m-%dT%H:%M:%SZ")
+                            .to_string(),
+                    ),
+                    ("ordering", "docking".to_owned()),
+                    ("limit", "5".to_owned()),
+                ])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            Ok(Some(station_from(
+                info,
+                &visits.results,
+            )))
+        })
+    }
+}
+
+async fn active_stations() -> reqwest::Result<Vec<StationInfo>> {
+    let container: StationContainer = DEFAULT_CLIENT
+        .get("https://ll.thespacedevs.com/2.2.0/spacestation/")
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .query(&[
+            ("status", "1"),
+            ("mode", "detailed"),
+            ("limit", "10"),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(container.results)
+}
+
+fn parse_date(date: Option<&str>) -> Option<NaiveDateTime> {
+    date.and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+        .map(|d| d.naive_utc())
+}
+
+fn agency_name(agency: &AgencyRef) -> String {
+    agency
+        .abbrev
+        .clone()
+        .unwrap_or_else(|| {
+            agency
+                .name
+                .clone()
+        })
+}
+
+fn astronaut_from(info: &AstronautInfo) -> Astronaut {
+    Astronaut {
+        name: info
+            .name
+            .clone(),
+        agency: info
+            .agency
+            .as_ref()
+            .map(agency_name),
+        nationality: info
+            .nationality
+            .clone(),
+        launched: parse_date(
+            info.last_flight
+                .as_deref(),
+        ),
+        station: None,
+        expedition: None,
+        role: None,
+    }
+}
+
+/// Combine the people in space with the expeditions of the stations they are
+/// on, ordered by station so the crews end up together.
+fn people_from(astronauts: &[AstronautInfo], stations: &[StationInfo]) -> Vec<Astronaut> {
+    let mut people: Vec<Astronaut> = astronauts
+        .iter()
+        .map(|info| {
+            let mut person = astronaut_from(info);
+
+            for station in stations {
+                for expedition in &station.active_expeditions {
+                    if let Some(crew) = expedition
+                        .crew
+                        .iter()
+                        .find(|c| {
+                            c.astronaut
+                                .id
+                                == info.id
+                        })
+                    {
+                        person.station = Some(
+                            station
+                                .name
+                                .clone(),
+                        );
+                        person.expedition = Some(
+                            expedition
+                                .name
+                                .clone(),
+                        );
+                        person.role = crew
+                            .role
+                            .as_ref()
+                            .map(|r| {
+                                r.role
+                                    .clone()
+                            });
+                    }
+                }
+            }
+
+            person
+        })
+        .collect();
+
+    // people not on a station, like on a free flying capsule, go last
+    people.sort_by(|a, b| {
+        (
+            a.station
+                .is_none(),
+            &a.station,
+            &a.name,
+        )
+            .cmp(&(
+                b.station
+                    .is_none(),
+                &b.station,
+                &b.name,
+            ))
+    });
+    people
+}
+
+fn station_from(info: StationInfo, visits: &[DockingEventInfo]) -> Station {
+    let vehicle_name = |event: &DockingEventInfo| {
+        event
+            .flight_vehicle
+            .as_ref()
+            .and_then(|v| {
+                v.spacecraft
+                    .as_ref()
+            })
+            .map_or_else(
+                || "Unknown vehicle".to_owned(),
+                |s| {
+                    s.name
+                        .clone()
+                },
+            )
+    };
+
+    Station {
+        name: info.name,
+        orbit: info.orbit,
+        founded: info.founded,
+        description: info.description,
+        image: info.image_url,
+        owners: info
+            .owners
+            .iter()
+            .map(agency_name)
+            .collect(),
+        expeditions: info
+            .active_expeditions
+            .iter()
+            .map(|e| {
+                e.name
+                    .clone()
+            })
+            .collect(),
+        crew: info
+            .active_expeditions
+            .iter()
+            .flat_map(|e| {
+                e.crew
+                    .iter()
+                    .map(|c| {
+                        let mut person = astronaut_from(&c.astronaut);
+                        person.expedition = Some(
+                            e.name
+                                .clone(),
+                        );
+                        person.role = c
+                            .role
+                            .as_ref()
+                            .map(|r| {
+                                r.role
+                                    .clone()
+                            });
+                        person
+                    })
+            })
+            .collect(),
+        docked: info
+            .docking_location
+            .iter()
+            .filter_map(|port| {
+                port.docked
+                    .as_ref()
+                    .map(|event| {
+                        (
+                            vehicle_name(event),
+                            port.name
+                                .clone(),
+                        )
+                    })
+            })
+            .collect(),
+        visits: visits
+            .iter()
+            .map(|event| {
+                (
+                    vehicle_name(event),
+                    parse_date(
+                        event
+                            .docking
+                            .as_deref(),
+                    ),
+                )
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::crew::{
+        CrewInfo,
+        CrewRole,
+        ExpeditionInfo,
+    };
+
+    fn astronaut(id: i32, name: &str) -> AstronautInfo {
+        AstronautInfo {
+            id,
+            name: name.to_owned(),
+            nationality: "American".to_owned(),
+            agency: Some(AgencyRef {
+                name: "National Aeronautics and Space Administration".to_owned(),
+                abbrev: Some("NASA".to_owned()),
+            }),
+            last_flight: Some("2024-03-04T03:53:38Z".to_owned()),
+        }
+    }
+
+    #[test]
+    fn people_get_their_expedition() {
+        let station = StationInfo {
+            id: 4,
+            name: "International Space Station".to_owned(),
+            orbit: "Low Earth Orbit".to_owned(),
+            founded: None,
+            description: String::new(),
+            image_url: None,
+            owners: Vec::new(),
+            active_expeditions: vec![ExpeditionInfo {
+                name: "Expedition 71".to_owned(),
+                crew: vec![CrewInfo {
+                    role: Some(CrewRole {
+                        role: "Commander".to_owned(),
+                    }),
+                    astronaut: astronaut(2, "Matthew Dominick"),
+                }],
+            }],
+            docking_location: Vec::new(),
+        };
+
+        let people = people_from(
+            &[
+                astronaut(1, "Anne Free"),
+                astronaut(2, "Matthew Dominick"),
+            ],
+            &[station],
+        );
+
+        assert_eq!(people[0].name, "Matthew Dominick");
+        assert_eq!(
+            people[0]
+                .expedition
+                .as_deref(),
+            Some("Expedition 71")
+        );
+        assert_eq!(
+            people[0]
+                .role
+                .as_deref(),
+            Some("Commander")
+        );
+        assert_eq!(
+            people[0]
+                .agency
+                .as_deref(),
+            Some("NASA")
+        );
+        assert!(people[0]
+            .launched
+            .is_some());
+        assert_eq!(people[1].name, "Anne Free");
+        assert_eq!(people[1].station, None);
+    }
+}
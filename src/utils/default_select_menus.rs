@@ -5,6 +5,7 @@ use serenity::{
     http::Http,
     model::{
         application::Interaction,
+        channel::ChannelType,
         id::{
             ChannelId,
             GuildId,
@@ -18,15 +19,59 @@ use serenity::{
     },
 };
 
-use crate::events::select_menu::SelectMenu;
+use crate::events::select_menu::{
+    SelectMenu,
+    SelectMenuKind,
+};
 
+/// Let the user pick up to `max_values` roles from all roles in the guild.
 pub async fn role_select_menu<F>(
     http: Arc<Http>,
     user_id: UserId,
     interaction: &Interaction,
     data: Arc<RwLock<TypeMap>>,
-    exclude: Option<Vec<RoleId>>,
-    include: Option<Vec<RoleId>>,
+    max_values: u8,
+    callback: F,
+) where
+    F: Fn(Vec<RoleId>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+{
+    SelectMenu::multi_builder(move |chosen| {
+        callback(
+            chosen
+                .into_iter()
+                .map(|(id, _)| {
+                    id.parse()
+                        .expect("Got invalid role id from role select")
+                })
+                .collect(),
+        )
+    })
+    .set_description(
+        if max_values > 1 {
+            "Select one or more roles"
+        } else {
+            "Select a role"
+        },
+    )
+    .set_custom_id(&format!("{user_id}-role-select"))
+    .set_user(user_id)
+    .set_kind(SelectMenuKind::Role)
+    .set_max_values(max_values)
+    .make_ephemeral()
+    .build()
+    .unwrap()
+    .listen(http, interaction, data)
+    .await;
+}
+
+/// Let the user pick one of the given roles, for when only a specific set of
+/// roles is valid.
+pub async fn role_choice_menu<F>(
+    http: Arc<Http>,
+    user_id: UserId,
+    interaction: &Interaction,
+    data: Arc<RwLock<TypeMap>>,
+    include: Vec<RoleId>,
     callback: F,
 ) where
     F: Fn(RoleId) -> BoxFuture<'static, ()> + Send + Sync + 'static,
@@ -37,13 +82,22 @@ pub async fn role_select_menu<F>(
         .await
         .expect("Can't get roles from guild");
 
-    if let Some(include) = include {
-        roles.retain(|id, _| include.contains(id));
-    }
+    roles.retain(|id, _| include.contains(id));
 
-    if let Some(exclude) = exclude {
-        roles.retain(|id, _| !exclude.contains(id));
-    }
+    // roles that have been deleted since can still be picked by their id
+    let mut options = include
+        .iter()
+        .map(|id| {
+            (
+                id.get()
+                    .to_string(),
+                roles
+                    .remove(id)
+                    .map_or_else(|| id.to_string(), |role| role.name),
+            )
+        })
+        .collect::<Vec<_>>();
+    options.truncate(125);
 
     SelectMenu::builder(move |(id, _)| {
         let id: RoleId = id
@@ -56,16 +110,8 @@ pub async fn role_select_menu<F>(
     .set_user(user_id)
     .make_ephemeral()
     .set_options(
-        roles
+        options
             .into_iter()
-            .take(125)
-            .map(|(k, v)| {
-                (
-                    k.get()
-                        .to_string(),
-                    v.name,
-                )
-            })
             .collect(),
     )
     .build()
@@ -74,21 +120,18 @@ pub async fn role_select_menu<F>(
     .await;
 }
 
+/// Let the user pick a channel of one of the given types from all channels in
+/// the guild.
 pub async fn channel_select_menu<F>(
     http: Arc<Http>,
     user_id: UserId,
     interaction: &Interaction,
     data: Arc<RwLock<TypeMap>>,
+    channel_types: Vec<ChannelType>,
     callback: F,
 ) where
     F: Fn(ChannelId) -> BoxFuture<'static, ()> + Send + Sync + 'static,
 {
-    let guild = get_guild(interaction);
-    let channels = guild
-        .channels(&http)
-        .await
-        .expect("Can't get channels from guild");
-
     SelectMenu::builder(move |(id, _)| {
         let id = ChannelId::new(
             id.parse()
@@ -99,20 +142,8 @@ pub async fn channel_select_menu<F>(
     .set_description("Select a channel")
     .set_custom_id(&format!("{user_id}-channel-select"))
     .set_user(user_id)
+    .set_kind(SelectMenuKind::Channel(channel_types))
     .make_ephemeral()
-    .set_options(
-        channels
-            .into_iter()
-            .take(125)
-            .map(|(k, v)| {
-                (
-                    k.get()
-                        .to_string(),
-                    v.name,
-                )
-            })
-            .collect(),
-    )
     .build()
     .unwrap()
     .listen(http, interaction, data)
@@ -1,4 +1,5 @@
 use std::{
+    cmp::Reverse,
     fmt::{
         self,
         Display,
@@ -7,16 +8,47 @@ use std::{
     str::FromStr,
 };
 
+use chrono::{
+    NaiveDate,
+    NaiveDateTime,
+};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{
+        doc,
+        Document,
+    },
+    options::FindOptions,
+    Database,
+};
+use reqwest::header::AUTHORIZATION;
 use serenity::model::application::CommandInteraction;
 
 use crate::{
     models::launches::{
+        BoosterData,
+        LaunchContainer,
         LaunchData,
+        LaunchRecord,
+        LaunchStatus,
+        LauncherConfigContainer,
+        LauncherConfigDetail,
+        PadContainer,
+        PadData,
+        PadInfo,
         VidURL,
     },
-    utils::constants::{
-        LAUNCH_AGENCIES,
-        LAUNCH_VEHICLES,
+    utils::{
+        catalog::{
+            AgencyFilter,
+            Catalog,
+            VehicleFilter,
+        },
+        constants::{
+            DEFAULT_CLIENT,
+            LL_KEY,
+        },
+        serde::datetime_formatting::FORMAT as DATE_FORMAT,
     },
 };
 
@@ -50,83 +82,1327 @@ pub fn format_links(links: &[VidURL]) -> Option<String> {
     }
 }
 
-pub fn filter_launches(
-    launches: Vec<LaunchData>,
-    interaction: &CommandInteraction,
-) -> Result<Vec<LaunchData>, FilterErrorType> {
-    let agency_filter = interaction
-        .data
-        .options
+/// The links to the webcasts of a launch on a single line, for places where
+/// the full list of [`format_links`] takes up too much space.
+pub fn replay_links(links: &[VidURL]) -> Option<String> {
+    let res = links
         .iter()
-        .find(|o| o.name == "lsp")
-        .and_then(|o| {
-            o.value
-                .as_str()
+        .filter_map(|link_obj| {
+            let link = url::Url::from_str(&link_obj.url).ok()?;
+            let domain = link
+                .domain()?
+                .trim_start_matches("www.");
+            Some(format!("[{domain}]({})", link_obj.url))
         })
-        .map(str::to_lowercase);
+        .collect::<Vec<String>>();
 
-    if let Some(lsp) = agency_filter {
-        if let Some(filter) = LAUNCH_AGENCIES.get(&lsp.as_str()) {
-            let filtered = launches
-                .into_iter()
-                .filter(|l| l.lsp == *filter)
-                .collect::<Vec<LaunchData>>();
-            if filtered.is_empty() {
-                return Err(FilterErrorType::Lsp);
-            }
-            return Ok(filtered);
+    if res.is_empty() {
+        None
+    } else {
+        Some(res.join(", "))
+    }
+}
+
+/// One line per booster of a launch with its serial, how often it flew and
+/// how its landing went.
+pub fn format_boosters(boosters: &[BoosterData]) -> Option<String> {
+    let mut res = String::new();
+
+    for booster in boosters {
+        let serial = booster
+            .serial
+            .as_deref()
+            .unwrap_or("Unknown booster");
+        write!(
+            res,
+            "**{} {serial}**",
+            booster.stage_type
+        )
+        .expect("write to String: can't fail");
+
+        if let Some(flight) = booster.flight_number {
+            write!(res, ": flight {flight}").expect("write to String: can't fail");
+        }
+        if booster.flight_proven {
+            res.push_str(", flight proven");
         }
 
-        return Err(FilterErrorType::Invalid);
+        let landing = match (
+            booster.landing_attempt,
+            booster.landing_success,
+        ) {
+            (Some(false), _) => "no landing".to_owned(),
+            (None, _) => "landing unknown".to_owned(),
+            (Some(true), outcome) => {
+                let location = booster
+                    .landing_location
+                    .as_deref()
+                    .map(|location| format!(" on {location}"))
+                    .unwrap_or_default();
+                let outcome = match outcome {
+                    Some(true) => "✅",
+                    Some(false) => "❌",
+                    None => "pending",
+                };
+                format!("landing{location} {outcome}")
+            },
+        };
+        writeln!(res, ", {landing}").expect("write to String: can't fail");
     }
 
-    let rocket_filter = interaction
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == "rocket")
-        .and_then(|o| {
-            o.value
-                .as_str()
-        })
-        .map(ToOwned::to_owned);
+    if res.is_empty() {
+        None
+    } else {
+        Some(res)
+    }
+}
 
-    if let Some(rocket) = rocket_filter {
-        if let Some(filter) = LAUNCH_VEHICLES.get(rocket.as_str()) {
-            let filtered = launches
-                .into_iter()
-                .filter(|l| {
-                    filter.contains(
-                        &l.vehicle
-                            .as_str(),
-                    )
+/// What is known about the chances of a launch going ahead, like the weather
+/// forecast and why it is on hold or failed.
+pub fn launch_conditions(launch: &LaunchData) -> Option<String> {
+    let mut res = String::new();
+
+    if let Some(precision) = &launch.net_precision {
+        writeln!(res, "**NET precision:** {precision}").expect("write to String: can't fail");
+    }
+    if let Some(probability) = launch.probability {
+        writeln!(
+            res,
+            "**Weather:** {probability}% chance of favorable weather"
+        )
+        .expect("write to String: can't fail");
+    }
+    if let Some(concerns) = &launch.weather_concerns {
+        writeln!(res, "**Weather concerns:** {concerns}").expect("write to String: can't fail");
+    }
+    res.push_str(&launch_reasons(launch));
+
+    if res.is_empty() {
+        None
+    } else {
+        Some(res)
+    }
+}
+
+/// Why a launch is on hold or failed, one reason per line.
+pub fn launch_reasons(launch: &LaunchData) -> String {
+    let mut res = String::new();
+
+    if let Some(reason) = &launch.holdreason {
+        writeln!(res, "**Hold reason:** {reason}").expect("write to String: can't fail");
+    }
+    if let Some(reason) = &launch.failreason {
+        writeln!(res, "**Failure reason:** {reason}").expect("write to String: can't fail");
+    }
+
+    res
+}
+
+/// The launch statuses that can be filtered on, an upcoming launch is always
+/// one of these.
+const STATUS_FILTERS: [(&str, LaunchStatus); 3] = [
+    ("go", LaunchStatus::Go),
+    ("tbd", LaunchStatus::Tbd),
+    ("hold", LaunchStatus::Hold),
+];
+
+/// Dates in component routes are kept short to stay within the custom id
+/// length limit.
+const ROUTE_DATE_FORMAT: &str = "%Y%m%d";
+
+/// A combination of filters to narrow launches down with, a launch has to pass
+/// every filter that is set.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchQuery {
+    pub lsp: Option<String>,
+    pub rocket: Option<String>,
+    pub status: Option<String>,
+    pub range: DateRange,
+    pub mission_type: Option<String>,
+    pub country: Option<String>,
+}
+
+impl LaunchQuery {
+    /// Read the filters from the options of a command, options that the
+    /// command doesn't have are left unset.
+    pub fn from_interaction(interaction: &CommandInteraction) -> Result<Self, String> {
+        let option = |name: &str| -> Option<String> {
+            interaction
+                .data
+                .options
+                .iter()
+                .find(|o| o.name == name)
+                .and_then(|o| {
+                    o.value
+                        .as_str()
+                })
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(ToOwned::to_owned)
+        };
+        let date_option = |name: &str| -> Result<Option<NaiveDate>, String> {
+            option(name)
+                .map(|value| {
+                    NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| {
+                        format!("`{value}` is not a valid date, please use the YYYY-MM-DD format")
+                    })
                 })
-                .collect::<Vec<LaunchData>>();
-            if filtered.is_empty() {
-                return Err(FilterErrorType::Vehicle);
+                .transpose()
+        };
+
+        let range = DateRange {
+            from: date_option("from")?.and_then(|d| d.and_hms_opt(0, 0, 0)),
+            to: date_option("to")?.and_then(|d| d.and_hms_opt(23, 59, 59)),
+        };
+
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from > to {
+                return Err("The `from` date has to be before the `to` date".to_owned());
             }
-            return Ok(filtered);
         }
 
-        return Err(FilterErrorType::Invalid);
+        Ok(Self {
+            lsp: option("lsp").map(|lsp| lsp.to_lowercase()),
+            rocket: option("rocket"),
+            status: option("status").map(|status| status.to_lowercase()),
+            range,
+            mission_type: option("mission").map(|mission| mission.to_lowercase()),
+            country: option("country").map(|country| country.to_uppercase()),
+        })
+    }
+
+    /// Read the filters back from the arguments made by [`Self::route_args`].
+    pub fn from_route_args(args: &[String]) -> Self {
+        let arg = |i: usize| {
+            args.get(i)
+                .filter(|a| !a.is_empty())
+                .cloned()
+        };
+        let date =
+            |i: usize| arg(i).and_then(|d| NaiveDate::parse_from_str(&d, ROUTE_DATE_FORMAT).ok());
+
+        Self {
+            lsp: arg(0),
+            rocket: arg(1),
+            status: arg(2),
+            range: DateRange {
+                from: date(3).and_then(|d| d.and_hms_opt(0, 0, 0)),
+                to: date(4).and_then(|d| d.and_hms_opt(23, 59, 59)),
+            },
+            mission_type: arg(5),
+            country: arg(6),
+        }
+    }
+
+    /// The filters as arguments for a component route, unset filters are left
+    /// empty so every query has the same amount of arguments.
+    ///
+    /// The agency and vehicle filters only pass [`Self::apply`] as catalog
+    /// keys, which never have the separator of component ids in them, but
+    /// there is no route for filters that do.
+    pub fn route_args(&self) -> Option<[String; 7]> {
+        let date = |d: Option<NaiveDateTime>| {
+            d.map(|d| {
+                d.format(ROUTE_DATE_FORMAT)
+                    .to_string()
+            })
+            .unwrap_or_default()
+        };
+
+        let args = [
+            self.lsp
+                .clone()
+                .unwrap_or_default(),
+            self.rocket
+                .clone()
+                .unwrap_or_default(),
+            self.status
+                .clone()
+                .unwrap_or_default(),
+            date(
+                self.range
+                    .from,
+            ),
+            date(
+                self.range
+                    .to,
+            ),
+            self.mission_type
+                .clone()
+                .unwrap_or_default(),
+            self.country
+                .clone()
+                .unwrap_or_default(),
+        ];
+
+        (!args
+            .iter()
+            .any(|arg| arg.contains(':')))
+        .then_some(args)
+    }
+
+    /// Check that the filters that have to be one of a known set of values
+    /// are, looking up the agency and vehicle filters in the catalog.
+    fn resolve(
+        &self,
+        catalog: &Catalog,
+    ) -> Result<
+        (
+            Option<AgencyFilter>,
+            Option<VehicleFilter>,
+        ),
+        FilterErrorType,
+    > {
+        let agency = self
+            .lsp
+            .as_deref()
+            .map(|lsp| {
+                catalog
+                    .agency(lsp)
+                    .ok_or(FilterErrorType::Invalid(
+                        "launch provider",
+                    ))
+            })
+            .transpose()?;
+        let vehicle = self
+            .rocket
+            .as_deref()
+            .map(|rocket| {
+                catalog
+                    .vehicle(rocket)
+                    .ok_or(FilterErrorType::Invalid(
+                        "launch vehicle",
+                    ))
+            })
+            .transpose()?;
+
+        if self
+            .status
+            .as_deref()
+            .is_some_and(|status| {
+                !STATUS_FILTERS
+                    .iter()
+                    .any(|(name, _)| *name == status)
+            })
+        {
+            return Err(FilterErrorType::Invalid("status"));
+        }
+
+        Ok((agency, vehicle))
+    }
+
+    /// Whether a launch passes the filters that don't need the catalog.
+    fn matches(&self, launch: &LaunchData) -> bool {
+        self.status
+            .as_deref()
+            .and_then(|status| {
+                STATUS_FILTERS
+                    .iter()
+                    .find(|(name, _)| *name == status)
+            })
+            .is_none_or(|(_, status)| launch.status == *status)
+            && self
+                .range
+                .from
+                .is_none_or(|from| launch.net >= from)
+            && self
+                .range
+                .to
+                .is_none_or(|to| launch.net <= to)
+            && self
+                .mission_type
+                .as_deref()
+                .is_none_or(|mission| {
+                    launch
+                        .mission_type
+                        .to_lowercase()
+                        .contains(mission)
+                })
+            && self
+                .country
+                .as_deref()
+                .is_none_or(|country| {
+                    launch
+                        .pad
+                        .as_ref()
+                        .is_some_and(|pad| pad.country_code == country)
+                })
+    }
+
+    /// The filters that are set, in words.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(lsp) = &self.lsp {
+            parts.push(format!("launch provider `{lsp}`"));
+        }
+        if let Some(rocket) = &self.rocket {
+            parts.push(format!("launch vehicle `{rocket}`"));
+        }
+        if let Some(status) = &self.status {
+            parts.push(format!("status `{status}`"));
+        }
+        if let Some(from) = self
+            .range
+            .from
+        {
+            parts.push(format!(
+                "a NET on or after `{}`",
+                from.format("%Y-%m-%d")
+            ));
+        }
+        if let Some(to) = self
+            .range
+            .to
+        {
+            parts.push(format!(
+                "a NET on or before `{}`",
+                to.format("%Y-%m-%d")
+            ));
+        }
+        if let Some(mission) = &self.mission_type {
+            parts.push(format!("mission type `{mission}`"));
+        }
+        if let Some(country) = &self.country {
+            parts.push(format!("country `{country}`"));
+        }
+
+        match parts.split_last() {
+            None => "these filters".to_owned(),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        }
+    }
+
+    /// Only keep the launches that pass all filters that are set.
+    pub fn apply(
+        &self,
+        catalog: &Catalog,
+        launches: Vec<LaunchData>,
+    ) -> Result<Vec<LaunchData>, FilterErrorType> {
+        let (agency, vehicle) = self.resolve(catalog)?;
+
+        let filtered = launches
+            .into_iter()
+            .filter(|l| {
+                agency
+                    .as_ref()
+                    .is_none_or(|a| a.matches(l))
+                    && vehicle
+                        .as_ref()
+                        .is_none_or(|v| v.matches(l))
+                    && self.matches(l)
+            })
+            .collect::<Vec<LaunchData>>();
+
+        if filtered.is_empty() {
+            return Err(FilterErrorType::NoMatches(
+                self.describe(),
+            ));
+        }
+
+        Ok(filtered)
+    }
+}
+
+/// Only the launches with a NET within the range, either end can be left open.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateRange {
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+}
+
+/// Get the most recent launches that have already happened from launch
+/// library, newest first. Launch library gives 100 launches at a time, pages
+/// keep getting fetched until `enough` is satisfied with the launches so far
+/// or `pages` pages have been fetched.
+pub async fn fetch_past_launches<F>(
+    agency: Option<AgencyFilter>,
+    range: DateRange,
+    pages: usize,
+    enough: F,
+) -> reqwest::Result<Vec<LaunchData>>
+where
+    F: Fn(&[LaunchData]) -> bool,
+{
+    let mut params = vec![
+        ("limit", "100".to_owned()),
+        ("mode", "detailed".to_owned()),
+        ("ordering", "-net".to_owned()),
+    ];
+
+    match agency {
+        Some(AgencyFilter {
+            ll_id: Some(id),
+            ..
+        }) => params.push(("lsp__id", id.to_string())),
+        Some(AgencyFilter {
+            name,
+            ..
+        }) => params.push(("lsp__name", name)),
+        None => {},
+    }
+    if let Some(from) = range.from {
+        params.push((
+            "net__gte",
+            from.format(DATE_FORMAT)
+                .to_string(),
+        ));
+    }
+    if let Some(to) = range.to {
+        params.push((
+            "net__lte",
+            to.format(DATE_FORMAT)
+                .to_string(),
+        ));
+    }
+
+    let mut launches = Vec::new();
+    for page in 0..pages {
+        let container: LaunchContainer = DEFAULT_CLIENT
+            .get("https://ll.thespacedevs.com/2.2.0/launch/previous/")
+            .header(AUTHORIZATION, LL_KEY.as_str())
+            .query(&params)
+            .query(&[("offset", page * 100)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        launches.extend(
+            container
+                .results
+                .into_iter()
+                .map(LaunchData::from),
+        );
+
+        if container
+            .next
+            .is_none()
+            || enough(&launches)
+        {
+            break;
+        }
     }
 
     Ok(launches)
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Search launch library for rocket configurations by name.
+pub async fn search_launcher_configs(query: &str) -> reqwest::Result<Vec<LauncherConfigDetail>> {
+    let container: LauncherConfigContainer = DEFAULT_CLIENT
+        .get("https://ll.thespacedevs.com/2.2.0/config/launcher/")
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .query(&[
+            ("search", query),
+            ("mode", "detailed"),
+            ("limit", "25"),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(container.results)
+}
+
+pub async fn fetch_launcher_config(id: i32) -> reqwest::Result<LauncherConfigDetail> {
+    DEFAULT_CLIENT
+        .get(format!(
+            "https://ll.thespacedevs.com/2.2.0/config/launcher/{id}/"
+        ))
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
+
+/// Search launch library for launch pads by their name or the name of their
+/// site.
+pub async fn search_pads(query: &str) -> reqwest::Result<Vec<PadInfo>> {
+    let container: PadContainer = DEFAULT_CLIENT
+        .get("https://ll.thespacedevs.com/2.2.0/pad/")
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .query(&[("search", query), ("limit", "25")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(container.results)
+}
+
+pub async fn fetch_pad(id: i32) -> reqwest::Result<PadInfo> {
+    DEFAULT_CLIENT
+        .get(format!(
+            "https://ll.thespacedevs.com/2.2.0/pad/{id}/"
+        ))
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
+
+/// The statistics of a pad and the site it is on, with a link to its map.
+pub fn pad_details(pad: &PadData) -> String {
+    let mut details = format!(
+        "**Site:** {}\n\
+        **Launches from this pad:** {}\n\
+        **Launches from this site:** {}\n\
+        **Landings at this site:** {}",
+        pad.location, pad.launch_count, pad.location_launch_count, pad.location_landing_count,
+    );
+
+    if let (Some(latitude), Some(longitude)) = (&pad.latitude, &pad.longitude) {
+        write!(
+            details,
+            "\n**Coordinates:** {latitude}, {longitude}"
+        )
+        .expect("write to String: can't fail");
+    }
+
+    if let Some(map) = &pad.map_url {
+        write!(details, "\n[Map]({map})").expect("write to String: can't fail");
+    }
+
+    details
+}
+
+/// Get the most recent launches of a rocket configuration, newest first.
+pub async fn fetch_vehicle_launches(config_id: i32) -> reqwest::Result<Vec<LaunchData>> {
+    let container: LaunchContainer = DEFAULT_CLIENT
+        .get("https://ll.thespacedevs.com/2.2.0/launch/previous/")
+        .header(AUTHORIZATION, LL_KEY.as_str())
+        .query(&[
+            (
+                "rocket__configuration__id",
+                config_id.to_string(),
+            ),
+            ("mode", "detailed".to_owned()),
+            ("ordering", "-net".to_owned()),
+            ("limit", "100".to_owned()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(container
+        .results
+        .into_iter()
+        .map(LaunchData::from)
+        .collect())
+}
+
+/// Get the outcomes of launches that have been stored while tracking them,
+/// newest first.
+pub async fn stored_past_launches(
+    db: &Database,
+    range: DateRange,
+    limit: i64,
+) -> Vec<LaunchRecord> {
+    // the NET is stored in a sortable format, so it can be compared as a string
+    let mut net = Document::new();
+    if let Some(from) = range.from {
+        net.insert(
+            "$gte",
+            from.format(DATE_FORMAT)
+                .to_string(),
+        );
+    }
+    if let Some(to) = range.to {
+        net.insert(
+            "$lte",
+            to.format(DATE_FORMAT)
+                .to_string(),
+        );
+    }
+    let filter = if net.is_empty() {
+        doc! {}
+    } else {
+        doc! {"net": net}
+    };
+
+    let cursor = db
+        .collection::<LaunchRecord>("launch_history")
+        .find(
+            filter,
+            FindOptions::builder()
+                .sort(doc! {"net": -1})
+                .limit(limit)
+                .build(),
+        )
+        .await;
+
+    match cursor {
+        Ok(cursor) => {
+            cursor
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("Can't read launch history: {e}");
+                    Vec::new()
+                })
+        },
+        Err(e) => {
+            eprintln!("Can't read launch history: {e}");
+            Vec::new()
+        },
+    }
+}
+
+/// Combine the launches from launch library with the stored history, the
+/// launch library data wins for launches that are in both while the NET slip
+/// is only known from the history.
+pub fn merge_past_launches(
+    fetched: Vec<LaunchData>,
+    stored: Vec<LaunchRecord>,
+) -> Vec<LaunchRecord> {
+    let mut records = fetched
+        .into_iter()
+        .map(|launch| {
+            let net_slip = stored
+                .iter()
+                .find(|r| {
+                    r.launch
+                        .ll_id
+                        == launch.ll_id
+                })
+                .and_then(|r| r.net_slip);
+            LaunchRecord {
+                launch,
+                net_slip,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for record in stored {
+        if !records
+            .iter()
+            .any(|r| {
+                r.launch
+                    .ll_id
+                    == record
+                        .launch
+                        .ll_id
+            })
+        {
+            records.push(record);
+        }
+    }
+
+    records.sort_by_key(|r| {
+        Reverse(
+            r.launch
+                .net,
+        )
+    });
+    records
+}
+
+#[derive(Debug, Clone)]
 pub enum FilterErrorType {
-    Vehicle,
-    Lsp,
-    Invalid,
+    /// The value given for this filter isn't a known one.
+    Invalid(&'static str),
+    /// No launch passes this combination of filters.
+    NoMatches(String),
 }
 
 impl Display for FilterErrorType {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), std::fmt::Error> {
         match self {
-            Self::Vehicle => fmt.write_str("launch vehicle"),
-            Self::Lsp => fmt.write_str("launch provider"),
-            Self::Invalid => fmt.write_str("invalid"),
+            Self::Invalid(filter) => write!(fmt, "invalid {filter}"),
+            Self::NoMatches(filters) => fmt.write_str(filters),
+        }
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+/// The amount of insertions, deletions, substitutions and swaps of adjacent
+/// characters needed to turn one word into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a
+        .chars()
+        .collect::<Vec<char>>();
+    let b = b
+        .chars()
+        .collect::<Vec<char>>();
+    let mut dist = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in dist
+        .iter_mut()
+        .enumerate()
+    {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0]
+        .iter_mut()
+        .enumerate()
+    {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            dist[i][j] = (dist[i - 1][j - 1] + cost)
+                .min(dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dist[i][j] = dist[i][j].min(dist[i - 2][j - 2] + 1);
+            }
         }
     }
+
+    dist[a.len()][b.len()]
+}
+
+/// Whether a word of the query matches a word of a field, allowing for a typo
+/// or two in longer words.
+fn word_matches(query: &str, word: &str) -> bool {
+    let allowed_typos = match query.len() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    word.starts_with(query) || edit_distance(query, word) <= allowed_typos
+}
+
+/// Scores below this only matched with typos.
+const FUZZY_SCORE: u32 = 60;
+
+fn field_score(field: &str, query: &str) -> u32 {
+    let field = field.to_lowercase();
+
+    if field == query {
+        100
+    } else if field.starts_with(query) {
+        80
+    } else if words(&field).any(|w| w.starts_with(query)) {
+        70
+    } else if field.contains(query) {
+        FUZZY_SCORE
+    } else if words(query).all(|q| words(&field).any(|w| word_matches(q, w))) {
+        40
+    } else {
+        0
+    }
+}
+
+/// How well a launch matches a search query that has already been lowercased,
+/// 0 if it doesn't match at all.
+fn match_score(launch: &LaunchData, query: &str) -> u32 {
+    if launch.ll_id == query {
+        return 100;
+    }
+
+    [
+        &launch.launch_name,
+        &launch.payload,
+        &launch.lsp,
+        &launch.vehicle,
+    ]
+    .into_iter()
+    .map(|field| field_score(field, query))
+    .max()
+    .unwrap_or(0)
+}
+
+/// Search the launches on their name, payload, provider, vehicle and id,
+/// giving the matches with the best ones first. An exact match on the id or the
+/// name of a launch is the only result.
+pub fn search_launches(launches: Vec<LaunchData>, query: &str) -> Vec<LaunchData> {
+    let query = query
+        .trim()
+        .to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    if let Some(exact) = launches
+        .iter()
+        .find(|l| {
+            l.ll_id == query
+                || l.launch_name
+                    .to_lowercase()
+                    == query
+        })
+    {
+        return vec![exact.clone()];
+    }
+
+    let mut scored = launches
+        .into_iter()
+        .map(|l| (match_score(&l, &query), l))
+        .filter(|(score, _)| *score > 0)
+        .collect::<Vec<_>>();
+    // the sort is stable, so launches that match equally well stay sorted by date
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+
+    // matches with typos are only interesting when there is nothing better
+    if scored
+        .first()
+        .is_some_and(|(best, _)| *best >= FUZZY_SCORE)
+    {
+        scored.retain(|(score, _)| *score >= FUZZY_SCORE);
+    }
+
+    scored
+        .into_iter()
+        .map(|(_, l)| l)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(ll_id: &str, name: &str, payload: &str, lsp: &str, vehicle: &str) -> LaunchData {
+        LaunchData {
+            ll_id: ll_id.to_owned(),
+            launch_name: name.to_owned(),
+            payload: payload.to_owned(),
+            vehicle: vehicle.to_owned(),
+            lsp: lsp.to_owned(),
+            ..LaunchData::default()
+        }
+    }
+
+    fn launches() -> Vec<LaunchData> {
+        vec![
+            launch(
+                "a1",
+                "Falcon 9 Block 5 | Transporter-9",
+                "Transporter-9",
+                "SpaceX",
+                "Falcon 9",
+            ),
+            launch(
+                "b2",
+                "SLS Block 1 | Artemis II",
+                "Artemis II",
+                "National Aeronautics and Space Administration",
+                "Space Launch System",
+            ),
+            launch(
+                "c3",
+                "Falcon 9 Block 5 | Starlink Group 6-1",
+                "Starlink Group 6-1",
+                "SpaceX",
+                "Falcon 9",
+            ),
+        ]
+    }
+
+    fn ids(found: &[LaunchData]) -> Vec<&str> {
+        found
+            .iter()
+            .map(|l| {
+                l.ll_id
+                    .as_str()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn search_by_fields() {
+        assert_eq!(
+            ids(&search_launches(launches(), "artemis")),
+            ["b2"]
+        );
+        assert_eq!(
+            ids(&search_launches(
+                launches(),
+                "Transporter"
+            )),
+            ["a1"]
+        );
+        assert_eq!(
+            ids(&search_launches(launches(), "spacex")),
+            ["a1", "c3"]
+        );
+        assert_eq!(
+            ids(&search_launches(launches(), "c3")),
+            ["c3"]
+        );
+        assert!(search_launches(launches(), "electron").is_empty());
+    }
+
+    #[test]
+    fn merge_prefers_fetched_launches() {
+        let mut fetched = launches();
+        fetched.truncate(1);
+        fetched[0].status = LaunchStatus::Success;
+
+        let stored = launches()
+            .into_iter()
+            .map(|launch| {
+                LaunchRecord {
+                    launch,
+                    net_slip: Some(60),
+                }
+            })
+            .collect();
+
+        let merged = merge_past_launches(fetched, stored);
+        assert_eq!(merged.len(), 3);
+        let a1 = merged
+            .iter()
+            .find(|r| {
+                r.launch
+                    .ll_id
+                    == "a1"
+            })
+            .unwrap();
+        assert_eq!(
+            a1.launch
+                .status,
+            LaunchStatus::Success
+        );
+        assert_eq!(a1.net_slip, Some(60));
+    }
+
+    #[test]
+    fn search_allows_typos() {
+        assert_eq!(
+            ids(&search_launches(launches(), "artemsi")),
+            ["b2"]
+        );
+        assert_eq!(
+            ids(&search_launches(
+                launches(),
+                "starlnk group"
+            )),
+            ["c3"]
+        );
+    }
+
+    #[test]
+    fn booster_lines() {
+        let core = BoosterData {
+            stage_type: "Core".to_owned(),
+            serial: Some("B1062".to_owned()),
+            flight_number: Some(12),
+            flight_proven: true,
+            landing_attempt: Some(true),
+            landing_success: Some(true),
+            landing_location: Some("OCISLY".to_owned()),
+        };
+        let expended = BoosterData {
+            serial: None,
+            flight_number: None,
+            flight_proven: false,
+            landing_attempt: Some(false),
+            landing_success: None,
+            landing_location: None,
+            ..core.clone()
+        };
+
+        assert_eq!(format_boosters(&[]), None);
+        assert_eq!(
+            format_boosters(&[core, expended]).as_deref(),
+            Some(
+                "**Core B1062**: flight 12, flight proven, landing on OCISLY ✅\n\
+                **Core Unknown booster**, no landing\n"
+            )
+        );
+    }
+
+    #[test]
+    fn pad_lines() {
+        let mut pad = PadData {
+            id: 87,
+            name: "Launch Complex 39A".to_owned(),
+            location: "Kennedy Space Center, FL, USA".to_owned(),
+            country_code: "USA".to_owned(),
+            latitude: Some("28.608".to_owned()),
+            longitude: Some("-80.604".to_owned()),
+            map_url: Some("https://maps.example/39a".to_owned()),
+            map_image: None,
+            launch_count: 190,
+            location_launch_count: 250,
+            location_landing_count: 60,
+        };
+
+        assert_eq!(
+            pad_details(&pad),
+            "**Site:** Kennedy Space Center, FL, USA\n\
+            **Launches from this pad:** 190\n\
+            **Launches from this site:** 250\n\
+            **Landings at this site:** 60\n\
+            **Coordinates:** 28.608, -80.604\n\
+            [Map](https://maps.example/39a)"
+        );
+
+        pad.latitude = None;
+        pad.map_url = None;
+        assert!(!pad_details(&pad).contains("Coordinates"));
+        assert!(!pad_details(&pad).contains("[Map]"));
+    }
+
+    #[test]
+    fn conditions() {
+        let mut l = launch(
+            "a1",
+            "Falcon 9 Block 5 | Crew-9",
+            "Crew-9",
+            "SpaceX",
+            "Falcon 9",
+        );
+        assert_eq!(launch_conditions(&l), None);
+
+        l.probability = Some(90);
+        l.weather_concerns = Some("Cumulus clouds".to_owned());
+        l.holdreason = Some("Ground systems".to_owned());
+        assert_eq!(
+            launch_conditions(&l).as_deref(),
+            Some(
+                "**Weather:** 90% chance of favorable weather\n\
+                **Weather concerns:** Cumulus clouds\n\
+                **Hold reason:** Ground systems\n"
+            )
+        );
+    }
+
+    #[test]
+    fn query_combines_filters() {
+        let mut list = launches();
+        list[0].status = LaunchStatus::Tbd;
+        list[2].mission_type = "Communications".to_owned();
+        list[0].vehicle = "Falcon 9 Block 5".to_owned();
+        list[2].vehicle = "Falcon 9 Block 5".to_owned();
+
+        let query = LaunchQuery {
+            lsp: Some("spacex".to_owned()),
+            rocket: Some("falcon".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(&query
+                .apply(&Catalog::default(), list.clone())
+                .unwrap()),
+            ["a1", "c3"]
+        );
+
+        let query = LaunchQuery {
+            status: Some("go".to_owned()),
+            ..query
+        };
+        assert_eq!(
+            ids(&query
+                .apply(&Catalog::default(), list.clone())
+                .unwrap()),
+            ["c3"]
+        );
+
+        let query = LaunchQuery {
+            mission_type: Some("tourism".to_owned()),
+            ..query
+        };
+        assert!(matches!(
+            query.apply(&Catalog::default(), list.clone()),
+            Err(FilterErrorType::NoMatches(filters)) if filters == "launch provider `spacex`, \
+                launch vehicle `falcon`, status `go` and mission type `tourism`"
+        ));
+
+        let query = LaunchQuery {
+            status: Some("scrubbed".to_owned()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            query.apply(&Catalog::default(), list),
+            Err(FilterErrorType::Invalid("status"))
+        ));
+    }
+
+    #[test]
+    fn query_route_args_round_trip() {
+        let query = LaunchQuery {
+            lsp: Some("spacex".to_owned()),
+            range: DateRange {
+                from: NaiveDate::from_ymd_opt(2024, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0)),
+                to: NaiveDate::from_ymd_opt(2024, 3, 31).and_then(|d| d.and_hms_opt(23, 59, 59)),
+            },
+            country: Some("USA".to_owned()),
+            ..Default::default()
+        };
+
+        let args = query
+            .route_args()
+            .unwrap();
+        assert_eq!(
+            args,
+            ["spacex", "", "", "20240101", "20240331", "", "USA"]
+        );
+
+        let parsed = LaunchQuery::from_route_args(&args);
+        assert_eq!(parsed.lsp, query.lsp);
+        assert_eq!(parsed.rocket, None);
+        assert_eq!(
+            parsed
+                .range
+                .from,
+            query
+                .range
+                .from
+        );
+        assert_eq!(
+            parsed
+                .range
+                .to,
+            query
+                .range
+                .to
+        );
+        assert_eq!(parsed.country, query.country);
+
+        let query = LaunchQuery {
+            lsp: Some("space:x".to_owned()),
+            ..Default::default()
+        };
+        assert!(query
+            .route_args()
+            .is_none());
+    }
+
+    /// The rocket configurations launch library 2.2.0 gives for a search in
+    /// detailed mode.
+    const LL2_LAUNCHER_CONFIGS: &str = r#"{
+        "count": 1,
+        "next": null,
+        "previous": null,
+        "results": [{
+            "id": 164,
+            "url": "https://ll.thespacedevs.com/2.2.0/config/launcher/164/",
+            "name": "Falcon 9",
+            "active": true,
+            "reusable": true,
+            "description": "Falcon 9 is a two-stage rocket designed and manufactured by SpaceX for the reliable and safe transport of satellites and the Dragon spacecraft into orbit.",
+            "family": "Falcon",
+            "full_name": "Falcon 9 Block 5",
+            "manufacturer": {
+                "id": 121,
+                "url": "https://ll.thespacedevs.com/2.2.0/agencies/121/",
+                "name": "SpaceX",
+                "featured": true,
+                "type": "Commercial",
+                "country_code": "USA",
+                "abbrev": "SpX",
+                "description": "Space Exploration Technologies Corp., known as SpaceX, is an American aerospace manufacturer and space transport services company headquartered in Hawthorne, California.",
+                "administrator": "CEO: Elon Musk",
+                "founding_year": "2002",
+                "launchers": "Falcon | Starship",
+                "spacecraft": "Dragon",
+                "parent": null,
+                "image_url": null,
+                "logo_url": null
+            },
+            "program": [],
+            "variant": "Block 5",
+            "alias": "",
+            "min_stage": 1,
+            "max_stage": 2,
+            "length": 70.0,
+            "diameter": 3.65,
+            "maiden_flight": "2018-05-11",
+            "launch_cost": "52000000",
+            "launch_mass": 549,
+            "leo_capacity": 22800.0,
+            "gto_capacity": 8300.0,
+            "to_thrust": 7607,
+            "apogee": null,
+            "vehicle_range": null,
+            "image_url": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/images/falcon_9_block__image_20210506060831.jpg",
+            "infographic_url": null,
+            "info_url": "https://www.spacex.com/vehicles/falcon-9/",
+            "wiki_url": "https://en.wikipedia.org/wiki/Falcon_9",
+            "total_launch_count": 233,
+            "consecutive_successful_launches": 220,
+            "successful_launches": 232,
+            "failed_launches": 0,
+            "pending_launches": 96,
+            "attempted_landings": 227,
+            "successful_landings": 224,
+            "failed_landings": 3,
+            "consecutive_successful_landings": 188
+        }]
+    }"#;
+
+    /// A pad the way launch library 2.2.0 gives it.
+    const LL2_PAD: &str = r#"{
+        "id": 80,
+        "url": "https://ll.thespacedevs.com/2.2.0/pad/80/",
+        "agency_id": 121,
+        "name": "Space Launch Complex 40",
+        "description": null,
+        "info_url": null,
+        "wiki_url": "https://en.wikipedia.org/wiki/Cape_Canaveral_Space_Launch_Complex_40",
+        "map_url": "https://www.google.com/maps?q=28.56194122,-80.57735736",
+        "latitude": "28.56194122",
+        "longitude": "-80.57735736",
+        "location": {
+            "id": 12,
+            "url": "https://ll.thespacedevs.com/2.2.0/location/12/",
+            "name": "Cape Canaveral, FL, USA",
+            "country_code": "USA",
+            "description": "",
+            "map_image": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/launch_images/location_12_20200803142519.jpg",
+            "timezone_name": "America/New_York",
+            "total_launch_count": 925,
+            "total_landing_count": 38
+        },
+        "country_code": "USA",
+        "map_image": "https://spacelaunchnow-prod-east.nyc3.digitaloceanspaces.com/media/launch_images/pad_80_20200803143323.jpg",
+        "total_launch_count": 238,
+        "orbital_launch_attempt_count": 238
+    }"#;
+
+    #[test]
+    fn reads_launch_library_rockets_and_pads() {
+        let configs: LauncherConfigContainer = serde_json::from_str(LL2_LAUNCHER_CONFIGS)
+            .expect("launch library 2.2.0 rockets should parse");
+        let config = &configs.results[0];
+        assert_eq!(config.full_name, "Falcon 9 Block 5");
+        assert_eq!(config.family, "Falcon");
+        assert_eq!(config.max_stage, Some(2));
+        assert_eq!(config.total_launch_count, Some(233));
+        assert_eq!(
+            config
+                .manufacturer
+                .as_ref()
+                .and_then(|m| {
+                    m.agency_type
+                        .as_deref()
+                }),
+            Some("Commercial")
+        );
+
+        let pad: PadInfo =
+            serde_json::from_str(LL2_PAD).expect("launch library 2.2.0 pads should parse");
+        assert_eq!(pad.agency_id, Some(121));
+        assert_eq!(pad.info_url, None);
+        assert_eq!(pad.total_launch_count, 238);
+        assert_eq!(
+            pad.location
+                .country_code,
+            "USA"
+        );
+        assert_eq!(
+            pad.location
+                .total_launch_count,
+            925
+        );
+    }
 }
@@ -1,4 +1,6 @@
+pub mod catalog;
 pub mod constants;
+pub mod crew;
 pub mod default_select_menus;
 pub mod interaction_builder;
 pub mod launches;
@@ -7,5 +9,6 @@ pub mod pictures;
 pub mod preloading;
 pub mod reminders;
 pub mod serde;
+pub mod stats;
 
 pub use other::*;
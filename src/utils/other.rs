@@ -1,14 +1,12 @@
-use std::{
-    fmt::Write,
-    ops::Add,
+use std::fmt::{
+    self,
+    Write,
 };
 
 use chrono::{
     Duration,
     Utc,
 };
-use lazy_static::lazy_static;
-use regex::Regex;
 use serenity::{
     builder::{
         CreateEmbed,
@@ -130,61 +128,121 @@ pub fn format_duration(dur: Duration, include_seconds: bool) -> String {
     res
 }
 
-lazy_static! {
-    pub static ref DAYS_REGEX: Regex = Regex::new("([0-9]+) days?").unwrap();
-    pub static ref HOURS_REGEX: Regex = Regex::new("([0-9]+) hours?").unwrap();
-    pub static ref MINUTES_REGEX: Regex = Regex::new("([0-9]+) minutes?").unwrap();
-    pub static ref SECONDS_REGEX: Regex = Regex::new("([0-9]+) seconds?").unwrap();
+/// Why a duration couldn't be parsed by [`parse_duration`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DurationError {
+    Empty,
+    /// A number that isn't followed by a unit.
+    MissingUnit(i64),
+    UnknownUnit(String),
+    /// Text where a number was expected.
+    Unexpected(String),
+    RepeatedUnit(&'static str),
+    TooLarge,
 }
 
-pub fn parse_duration(input: &str) -> Duration {
-    let mut dur = Duration::zero();
-
-    if let Some(captures) = DAYS_REGEX.captures(input) {
-        if let Some(days_str) = captures.get(1) {
-            if let Ok(days) = days_str
-                .as_str()
-                .parse::<i64>()
-            {
-                dur = dur.add(Duration::days(days));
-            }
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no duration was given"),
+            Self::MissingUnit(num) => {
+                write!(
+                    f,
+                    "`{num}` is missing a unit like `m`, `h` or `d`"
+                )
+            },
+            Self::UnknownUnit(unit) => write!(f, "`{unit}` is not a unit of time"),
+            Self::Unexpected(text) => {
+                write!(
+                    f,
+                    "expected a number instead of `{text}`"
+                )
+            },
+            Self::RepeatedUnit(unit) => write!(f, "{unit} are given more than once"),
+            Self::TooLarge => write!(f, "the duration is too large"),
         }
     }
+}
 
-    if let Some(captures) = HOURS_REGEX.captures(input) {
-        if let Some(hour_str) = captures.get(1) {
-            if let Ok(hours) = hour_str
-                .as_str()
-                .parse::<i64>()
-            {
-                dur = dur.add(Duration::hours(hours));
-            }
-        }
+impl std::error::Error for DurationError {}
+
+fn duration_unit(unit: &str) -> Option<(&'static str, i64)> {
+    match unit {
+        "d" | "day" | "days" => Some(("days", 86_400)),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(("hours", 3_600)),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(("minutes", 60)),
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(("seconds", 1)),
+        _ => None,
     }
+}
 
-    if let Some(captures) = MINUTES_REGEX.captures(input) {
-        if let Some(minutes_str) = captures.get(1) {
-            if let Ok(minutes) = minutes_str
-                .as_str()
-                .parse::<i64>()
-            {
-                dur = dur.add(Duration::minutes(minutes));
-            }
-        }
+/// Skip whitespace, commas and the word "and" between the parts of a duration.
+fn skip_separators(mut input: &str) -> &str {
+    loop {
+        let trimmed = input.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        input = match trimmed.strip_prefix("and") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest,
+            _ => return trimmed,
+        };
     }
+}
 
-    if let Some(captures) = SECONDS_REGEX.captures(input) {
-        if let Some(seconds_str) = captures.get(1) {
-            if let Ok(seconds) = seconds_str
-                .as_str()
-                .parse::<i64>()
-            {
-                dur = dur.add(Duration::seconds(seconds));
-            }
+/// Parse a duration like `1d2h30m`, `90m` or `1 day and 2 hours`, every unit
+/// can only be given once.
+pub fn parse_duration(input: &str) -> Result<Duration, DurationError> {
+    let input = input
+        .trim()
+        .to_lowercase();
+    let mut rest = skip_separators(&input);
+    if rest.is_empty() {
+        return Err(DurationError::Empty);
+    }
+
+    let mut seen = Vec::new();
+    let mut seconds: i64 = 0;
+
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if number_end == 0 {
+            let word = rest
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .next()
+                .unwrap_or(rest);
+            return Err(DurationError::Unexpected(
+                word.to_owned(),
+            ));
+        }
+
+        let amount = rest[..number_end]
+            .parse::<i64>()
+            .map_err(|_| DurationError::TooLarge)?;
+        rest = rest[number_end..].trim_start();
+
+        let unit_end = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        if unit_end == 0 {
+            return Err(DurationError::MissingUnit(amount));
+        }
+
+        let unit = &rest[..unit_end];
+        let (name, unit_seconds) =
+            duration_unit(unit).ok_or_else(|| DurationError::UnknownUnit(unit.to_owned()))?;
+        if seen.contains(&name) {
+            return Err(DurationError::RepeatedUnit(name));
         }
+        seen.push(name);
+
+        seconds = amount
+            .checked_mul(unit_seconds)
+            .and_then(|s| seconds.checked_add(s))
+            .ok_or(DurationError::TooLarge)?;
+        rest = skip_separators(&rest[unit_end..]);
     }
 
-    dur
+    Duration::try_seconds(seconds).ok_or(DurationError::TooLarge)
 }
 
 #[allow(dead_code)]
@@ -306,6 +364,8 @@ pub fn capitalize(s: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::ops::Add;
+
     use super::*;
 
     #[test]
@@ -330,7 +390,48 @@ mod tests {
 
         assert_eq!(
             parse_duration("2 days, 8 hours, 23 minutes and 1 second"),
-            dur
+            Ok(dur)
+        );
+        assert_eq!(parse_duration("2d8h23m1s"), Ok(dur));
+        assert_eq!(
+            parse_duration("90m"),
+            Ok(Duration::minutes(90))
+        );
+        assert_eq!(
+            parse_duration("1 day and 2 hours"),
+            Ok(Duration::hours(26))
+        );
+    }
+
+    #[test]
+    fn invalid_durations() {
+        assert_eq!(
+            parse_duration("  "),
+            Err(DurationError::Empty)
+        );
+        assert_eq!(
+            parse_duration("15"),
+            Err(DurationError::MissingUnit(15))
+        );
+        assert_eq!(
+            parse_duration("2 fortnights"),
+            Err(DurationError::UnknownUnit(
+                "fortnights".to_owned()
+            ))
+        );
+        assert_eq!(
+            parse_duration("soon"),
+            Err(DurationError::Unexpected(
+                "soon".to_owned()
+            ))
+        );
+        assert_eq!(
+            parse_duration("1h 30m 2h"),
+            Err(DurationError::RepeatedUnit("hours"))
+        );
+        assert_eq!(
+            parse_duration("99999999999999999d"),
+            Err(DurationError::TooLarge)
         );
     }
 }
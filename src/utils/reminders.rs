@@ -4,6 +4,7 @@ use std::{
         Display,
     },
     io::ErrorKind as IoErrorKind,
+    str::FromStr,
     sync::Arc,
 };
 
@@ -24,6 +25,7 @@ use serenity::{
     model::id::{
         ChannelId,
         GuildId,
+        RoleId,
         UserId,
     },
     prelude::RwLock,
@@ -35,6 +37,7 @@ use crate::{
         caches::DatabaseKey,
         reminders::{
             GuildSettings,
+            MentionKind,
             UserSettings,
         },
     },
@@ -68,6 +71,39 @@ pub async fn get_guild_settings(db: &Database, id: u64) -> MongoResult<GuildSett
         .and_then(|d| bson::from_document::<GuildSettings>(d).map_err(Into::into))
 }
 
+/// The roles a guild wants mentioned on a message of the given kind, `minutes`
+/// being the offset of a reminder. The roles in `mentions` go with every
+/// reminder, and with scrubs and outcomes only when `mention_others` is on.
+pub fn guild_mentions(
+    settings: &GuildSettings,
+    kind: MentionKind,
+    minutes: Option<i64>,
+) -> Vec<RoleId> {
+    let mut roles = if kind == MentionKind::Reminder || settings.mention_others {
+        settings
+            .mentions
+            .clone()
+    } else {
+        Vec::new()
+    };
+
+    for rule in &settings.mention_rules {
+        if rule.applies(kind, minutes) && !roles.contains(&rule.role) {
+            roles.push(rule.role);
+        }
+    }
+
+    roles
+}
+
+pub fn format_mentions(roles: &[RoleId]) -> String {
+    roles
+        .iter()
+        .fold(String::new(), |acc, role| {
+            acc + &format!(" <@&{}>", role.get())
+        })
+}
+
 #[derive(Copy, Clone)]
 pub enum ID {
     Channel((ChannelId, GuildId)),
@@ -80,6 +116,49 @@ impl ID {
     }
 }
 
+// compact form used to store the ID in the custom_id of a message component
+impl Display for ID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Channel((channel, guild)) => write!(f, "c{channel}.{guild}"),
+            Self::User(user) => write!(f, "u{user}"),
+        }
+    }
+}
+
+impl FromStr for ID {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(user) = s.strip_prefix('u') {
+            return user
+                .parse::<u64>()
+                .ok()
+                .filter(|id| *id != 0)
+                .map(|id| Self::User(UserId::new(id)))
+                .ok_or("invalid user id");
+        }
+
+        let (channel, guild) = s
+            .strip_prefix('c')
+            .and_then(|ids| ids.split_once('.'))
+            .ok_or("invalid reminders target")?;
+
+        match (
+            channel.parse::<u64>(),
+            guild.parse::<u64>(),
+        ) {
+            (Ok(channel), Ok(guild)) if channel != 0 && guild != 0 => {
+                Ok(Self::Channel((
+                    ChannelId::new(channel),
+                    GuildId::new(guild),
+                )))
+            },
+            _ => Err("invalid channel or guild id"),
+        }
+    }
+}
+
 pub async fn get_db(ses: &Arc<RwLock<EmbedSession>>) -> Option<Database> {
     if let Some(db) = ses
         .read()
@@ -146,6 +225,90 @@ pub fn regex_filter_to_string(regex: &Regex) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::reminders::MentionRule;
+
+    #[test]
+    fn id_round_trip() {
+        let channel = ID::Channel((
+            ChannelId::new(429307774804033536),
+            GuildId::new(429307670730637312),
+        ));
+        let user = ID::User(UserId::new(247745860979392512));
+
+        assert_eq!(
+            channel.to_string(),
+            "c429307774804033536.429307670730637312"
+        );
+        assert!(matches!(
+            channel
+                .to_string()
+                .parse::<ID>(),
+            Ok(ID::Channel((c, g))) if c.get() == 429307774804033536 && g.get() == 429307670730637312
+        ));
+        assert!(matches!(
+            user.to_string()
+                .parse::<ID>(),
+            Ok(ID::User(u)) if u.get() == 247745860979392512
+        ));
+        assert!("c123"
+            .parse::<ID>()
+            .is_err());
+        assert!("x123"
+            .parse::<ID>()
+            .is_err());
+    }
+
+    #[test]
+    fn mentions_per_offset_and_kind() {
+        let rule = |role: u64, kind: MentionKind, minutes: Option<i64>| {
+            MentionRule {
+                role: RoleId::new(role),
+                kind,
+                minutes,
+            }
+        };
+        let settings = GuildSettings {
+            mentions: vec![RoleId::new(10)],
+            mention_rules: vec![
+                rule(20, MentionKind::Reminder, Some(60)),
+                rule(30, MentionKind::Reminder, Some(10)),
+                rule(30, MentionKind::Scrub, None),
+                rule(10, MentionKind::Reminder, None),
+            ],
+            ..GuildSettings::default()
+        };
+        let roles = |ids: &[u64]| {
+            ids.iter()
+                .map(|id| RoleId::new(*id))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            guild_mentions(
+                &settings,
+                MentionKind::Reminder,
+                Some(1440)
+            ),
+            roles(&[10])
+        );
+        assert_eq!(
+            guild_mentions(
+                &settings,
+                MentionKind::Reminder,
+                Some(60)
+            ),
+            roles(&[10, 20])
+        );
+        assert_eq!(
+            guild_mentions(&settings, MentionKind::Scrub, None),
+            roles(&[30])
+        );
+        assert!(guild_mentions(&settings, MentionKind::Outcome, None).is_empty());
+        assert_eq!(
+            format_mentions(&roles(&[10, 20])),
+            " <@&10> <@&20>"
+        );
+    }
 
     #[test]
     fn regex_to_string() {
@@ -7,7 +7,8 @@ use serde::{
     Serializer,
 };
 
-const FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+/// The format launch library uses for dates, which also sorts as a string.
+pub const FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
 
 pub fn serialize<S>(date: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
 where
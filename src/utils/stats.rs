@@ -0,0 +1,300 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+};
+
+use chrono::{
+    Datelike,
+    Duration,
+};
+
+use crate::models::launches::{
+    LaunchData,
+    LaunchRecord,
+    LaunchStatus,
+};
+
+/// What to split the launch statistics up by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsGrouping {
+    Provider,
+    Family,
+    Year,
+    Month,
+}
+
+impl StatsGrouping {
+    pub fn from_option(value: &str) -> Option<Self> {
+        match value {
+            "provider" => Some(Self::Provider),
+            "family" => Some(Self::Family),
+            "year" => Some(Self::Year),
+            "month" => Some(Self::Month),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Provider => "launch provider",
+            Self::Family => "vehicle family",
+            Self::Year => "year",
+            Self::Month => "month",
+        }
+    }
+
+    fn key(self, launch: &LaunchData) -> String {
+        match self {
+            Self::Provider => {
+                launch
+                    .lsp
+                    .clone()
+            },
+            Self::Family
+                if launch
+                    .vehicle_family
+                    .is_empty() =>
+            {
+                launch
+                    .vehicle
+                    .clone()
+            },
+            Self::Family => {
+                launch
+                    .vehicle_family
+                    .clone()
+            },
+            Self::Year => {
+                launch
+                    .net
+                    .year()
+                    .to_string()
+            },
+            Self::Month => {
+                launch
+                    .net
+                    .format("%Y-%m")
+                    .to_string()
+            },
+        }
+    }
+
+    /// Periods are shown newest first, the others with the most launches first.
+    fn is_period(self) -> bool {
+        matches!(self, Self::Year | Self::Month)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchStats {
+    pub name: String,
+    pub total: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub partial_failures: usize,
+    slip_seconds: i64,
+    slipped_launches: i64,
+}
+
+impl LaunchStats {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    fn add(&mut self, record: &LaunchRecord) {
+        self.total += 1;
+        match record
+            .launch
+            .status
+        {
+            LaunchStatus::Success => self.successes += 1,
+            LaunchStatus::Failure => self.failures += 1,
+            LaunchStatus::PartialFailure => self.partial_failures += 1,
+            _ => {},
+        }
+
+        if let Some(slip) = record.net_slip {
+            self.slip_seconds += slip;
+            self.slipped_launches += 1;
+        }
+    }
+
+    /// The share of the launches the count is of, as a percentage.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percentage(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            count as f64 / self.total as f64 * 100.0
+        }
+    }
+
+    /// How much the NET moved on average between the launch first being seen
+    /// and it happening, only known for launches that were tracked.
+    pub fn average_slip(&self) -> Option<Duration> {
+        if self.slipped_launches == 0 {
+            None
+        } else {
+            Duration::try_seconds(self.slip_seconds / self.slipped_launches)
+        }
+    }
+}
+
+/// Aggregate the finished launches, giving the statistics over all of them and
+/// the statistics per group.
+pub fn launch_stats(
+    records: &[LaunchRecord],
+    grouping: StatsGrouping,
+) -> (LaunchStats, Vec<LaunchStats>) {
+    let mut overall = LaunchStats::new("All launches".to_owned());
+    let mut groups: HashMap<String, LaunchStats> = HashMap::new();
+
+    for record in records
+        .iter()
+        .filter(|r| {
+            r.launch
+                .status
+                .is_finished()
+        })
+    {
+        overall.add(record);
+
+        let key = grouping.key(&record.launch);
+        groups
+            .entry(key.clone())
+            .or_insert_with(|| LaunchStats::new(key))
+            .add(record);
+    }
+
+    let mut groups = groups
+        .into_values()
+        .collect::<Vec<_>>();
+    if grouping.is_period() {
+        groups.sort_by(|a, b| {
+            b.name
+                .cmp(&a.name)
+        });
+    } else {
+        groups.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+        });
+        groups.sort_by_key(|g| Reverse(g.total));
+    }
+
+    (overall, groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn record(
+        lsp: &str,
+        family: &str,
+        date: (i32, u32),
+        status: LaunchStatus,
+        net_slip: Option<i64>,
+    ) -> LaunchRecord {
+        LaunchRecord {
+            launch: LaunchData {
+                status,
+                vehicle_family: family.to_owned(),
+                net: NaiveDate::from_ymd_opt(date.0, date.1, 1)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap(),
+                lsp: lsp.to_owned(),
+                ..LaunchData::default()
+            },
+            net_slip,
+        }
+    }
+
+    fn records() -> Vec<LaunchRecord> {
+        vec![
+            record(
+                "SpaceX",
+                "Falcon",
+                (2023, 5),
+                LaunchStatus::Success,
+                Some(3600),
+            ),
+            record(
+                "SpaceX",
+                "Falcon",
+                (2024, 1),
+                LaunchStatus::Success,
+                None,
+            ),
+            record(
+                "SpaceX",
+                "Starship",
+                (2024, 3),
+                LaunchStatus::Failure,
+                Some(7200),
+            ),
+            record(
+                "Rocket Lab",
+                "Electron",
+                (2024, 3),
+                LaunchStatus::PartialFailure,
+                None,
+            ),
+            record(
+                "Rocket Lab",
+                "Electron",
+                (2024, 6),
+                LaunchStatus::Go,
+                None,
+            ),
+        ]
+    }
+
+    #[test]
+    fn overall_stats() {
+        let (overall, _) = launch_stats(&records(), StatsGrouping::Provider);
+
+        assert_eq!(overall.total, 4);
+        assert_eq!(overall.successes, 2);
+        assert_eq!(overall.failures, 1);
+        assert_eq!(overall.partial_failures, 1);
+        assert!((overall.percentage(overall.successes) - 50.0).abs() < f64::EPSILON);
+        assert_eq!(
+            overall.average_slip(),
+            Some(Duration::minutes(90))
+        );
+    }
+
+    #[test]
+    fn grouped_stats() {
+        let (_, providers) = launch_stats(&records(), StatsGrouping::Provider);
+        let names = providers
+            .iter()
+            .map(|g| {
+                g.name
+                    .as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["SpaceX", "Rocket Lab"]);
+        assert_eq!(providers[1].average_slip(), None);
+
+        let (_, years) = launch_stats(&records(), StatsGrouping::Year);
+        let names = years
+            .iter()
+            .map(|g| {
+                (
+                    g.name
+                        .as_str(),
+                    g.total,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, [("2024", 3), ("2023", 1)]);
+    }
+}